@@ -42,6 +42,7 @@ impl Material for FireworksMaterial {
             },
             depth_test: DepthTest::Always,
             write_mask: WriteMask::COLOR,
+            ..Default::default()
         }
     }
     fn is_transparent(&self) -> bool {
@@ -106,7 +107,11 @@ pub fn run() {
             camera.set_viewport(frame_input.viewport).unwrap();
 
             control
-                .handle_events(&mut camera, &mut frame_input.events)
+                .handle_events(
+                    &mut camera,
+                    &mut frame_input.events,
+                    frame_input.elapsed_time,
+                )
                 .unwrap();
             let elapsed_time = (frame_input.elapsed_time * 0.001) as f32;
             particles.time += elapsed_time;
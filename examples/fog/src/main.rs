@@ -55,7 +55,11 @@ pub async fn run() {
             let mut change = frame_input.first_frame;
             change |= camera.set_viewport(frame_input.viewport).unwrap();
             change |= control
-                .handle_events(&mut camera, &mut frame_input.events)
+                .handle_events(
+                    &mut camera,
+                    &mut frame_input.events,
+                    frame_input.elapsed_time,
+                )
                 .unwrap();
 
             for event in frame_input.events.iter() {
@@ -127,7 +127,11 @@ pub async fn run() {
             redraw |= camera.set_viewport(frame_input.viewport).unwrap();
 
             redraw |= control
-                .handle_events(&mut camera, &mut frame_input.events)
+                .handle_events(
+                    &mut camera,
+                    &mut frame_input.events,
+                    frame_input.elapsed_time,
+                )
                 .unwrap();
 
             if redraw {
@@ -237,7 +237,11 @@ pub async fn run() {
             };
             change |= camera.set_viewport(viewport).unwrap();
             change |= control
-                .handle_events(&mut camera, &mut frame_input.events)
+                .handle_events(
+                    &mut camera,
+                    &mut frame_input.events,
+                    frame_input.elapsed_time,
+                )
                 .unwrap();
 
             let time = 0.001 * frame_input.accumulated_time;
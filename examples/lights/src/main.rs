@@ -194,7 +194,11 @@ pub async fn run() {
             camera.set_viewport(viewport).unwrap();
 
             control
-                .handle_events(&mut camera, &mut frame_input.events)
+                .handle_events(
+                    &mut camera,
+                    &mut frame_input.events,
+                    frame_input.elapsed_time,
+                )
                 .unwrap();
 
             pipeline
@@ -105,7 +105,11 @@ pub fn main() {
         .render_loop(move |mut frame_input: FrameInput| {
             camera.set_viewport(frame_input.viewport).unwrap();
             control
-                .handle_events(&mut camera, &mut frame_input.events)
+                .handle_events(
+                    &mut camera,
+                    &mut frame_input.events,
+                    frame_input.elapsed_time,
+                )
                 .unwrap();
 
             frame_input
@@ -81,7 +81,11 @@ pub async fn run() {
         .render_loop(move |mut frame_input: FrameInput| {
             camera.set_viewport(frame_input.viewport).unwrap();
             control
-                .handle_events(&mut camera, &mut frame_input.events)
+                .handle_events(
+                    &mut camera,
+                    &mut frame_input.events,
+                    frame_input.elapsed_time,
+                )
                 .unwrap();
 
             frame_input
@@ -173,7 +173,11 @@ pub async fn run() {
             primary_camera.set_viewport(viewport).unwrap();
             secondary_camera.set_viewport(viewport).unwrap();
             control
-                .handle_events(&mut primary_camera, &mut frame_input.events)
+                .handle_events(
+                    &mut primary_camera,
+                    &mut frame_input.events,
+                    frame_input.elapsed_time,
+                )
                 .unwrap();
 
             // draw
@@ -82,7 +82,11 @@ pub async fn run() {
             let mut redraw = frame_input.first_frame;
             redraw |= camera.set_viewport(frame_input.viewport).unwrap();
             redraw |= control
-                .handle_events(&mut camera, &mut frame_input.events)
+                .handle_events(
+                    &mut camera,
+                    &mut frame_input.events,
+                    frame_input.elapsed_time,
+                )
                 .unwrap();
 
             // draw
@@ -38,10 +38,39 @@ mod cpu_volume;
 #[doc(inline)]
 pub use cpu_volume::*;
 
+mod cpu_point_cloud;
+#[doc(inline)]
+pub use cpu_point_cloud::*;
+
+mod marching_cubes;
+
+mod clip_planes;
+#[doc(inline)]
+pub use clip_planes::*;
+
+mod timer_query;
+#[doc(inline)]
+pub use timer_query::*;
+
+mod profiler;
+#[doc(inline)]
+pub use profiler::*;
+
+#[cfg(feature = "text")]
+#[cfg_attr(docsrs, doc(cfg(feature = "text")))]
+mod cpu_font;
+#[doc(inline)]
+#[cfg(feature = "text")]
+pub use cpu_font::*;
+
 mod camera;
 #[doc(inline)]
 pub use camera::*;
 
+mod camera2d;
+#[doc(inline)]
+pub use camera2d::*;
+
 mod image_effect;
 #[doc(inline)]
 pub use image_effect::*;
@@ -128,6 +157,18 @@ pub enum CoreError {
     MinimumLargerThanMaximum,
     #[error("the transformation matrix cannot be inverted and is therefore invalid")]
     FailedInvertingTransformationMatrix,
+    #[error("cannot frame an empty or infinite bounding box")]
+    InvalidBoundingBox,
+    #[error("the zoom must be positive and finite, actual zoom is {0}")]
+    InvalidZoom(f32),
+    #[error("failed creating a new font: {0}")]
+    FontCreation(String),
+    #[error("cannot extract an isosurface from compressed voxel data")]
+    CompressedVoxelData,
+    #[error("timer queries are not supported")]
+    TimerQueriesNotSupported,
+    #[error("failed creating a query: {0}")]
+    QueryCreation(String),
 }
 
 mod data_type;
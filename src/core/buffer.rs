@@ -94,6 +94,18 @@ impl Buffer {
         self.context.error_check()
     }
 
+    pub fn set_label(&self, label: &str) {
+        self.context.set_object_label(
+            crate::context::BUFFER,
+            crate::context::ARRAY_BUFFER_BINDING,
+            || self.bind(),
+            label,
+        );
+        unsafe {
+            self.context.bind_buffer(crate::context::ARRAY_BUFFER, None);
+        }
+    }
+
     pub fn attribute_count(&self) -> u32 {
         self.attribute_count
     }
@@ -51,6 +51,14 @@ impl InstanceBuffer {
         self.buffer.attribute_count()
     }
 
+    ///
+    /// Sets the label of this buffer, which is shown in graphics debuggers and debug messages, see [Context::set_debug_output].
+    /// Requires the `KHR_debug` extension, otherwise this does nothing.
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.buffer.set_label(label);
+    }
+
     pub(crate) fn bind(&self) {
         self.buffer.bind();
     }
@@ -42,6 +42,25 @@ impl UniformBuffer {
         Ok(buffer)
     }
 
+    ///
+    /// Sets the label of this buffer, which is shown in graphics debuggers and debug messages, see [Context::set_debug_output].
+    /// Requires the `KHR_debug` extension, otherwise this does nothing.
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.context.set_object_label(
+            crate::context::BUFFER,
+            crate::context::ARRAY_BUFFER_BINDING,
+            || unsafe {
+                self.context
+                    .bind_buffer(crate::context::ARRAY_BUFFER, Some(self.id));
+            },
+            label,
+        );
+        unsafe {
+            self.context.bind_buffer(crate::context::ARRAY_BUFFER, None);
+        }
+    }
+
     pub(crate) fn bind(&self, id: u32) {
         unsafe {
             self.context
@@ -16,10 +16,58 @@ pub enum ProjectionType {
     },
 }
 
+///
+/// The easing function of a camera animation, see [Camera::animate_to].
+/// Specifies how the progress of the animation depends on the elapsed time.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Easing {
+    /// Constant speed throughout the animation.
+    Linear,
+    /// Starts slowly and accelerates towards the end.
+    EaseIn,
+    /// Starts quickly and decelerates towards the end.
+    EaseOut,
+    /// Starts slowly, accelerates and then decelerates towards the end.
+    EaseInOut,
+}
+
+impl Easing {
+    ///
+    /// Returns the progress of the animation, a value between 0 and 1, given the fraction of the duration that has passed.
+    ///
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t * t,
+            Self::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - 0.5 * (2.0 - 2.0 * t).powi(3)
+                }
+            }
+        }
+    }
+}
+
+struct CameraAnimation {
+    start_position: Vec3,
+    start_target: Vec3,
+    end_position: Vec3,
+    end_target: Vec3,
+    duration: f64,
+    time: f64,
+    easing: Easing,
+}
+
 ///
 /// Used in a render call to define how to view the 3D world.
 ///
 pub struct Camera {
+    context: Context,
     viewport: Viewport,
     projection_type: ProjectionType,
     z_near: f32,
@@ -32,6 +80,7 @@ pub struct Camera {
     screen2ray: Mat4,
     uniform_buffer: UniformBuffer,
     frustrum: [Vec4; 6],
+    animation: Option<CameraAnimation>,
 }
 
 impl Camera {
@@ -383,8 +432,13 @@ impl Camera {
         &self.uniform_buffer
     }
 
+    pub(crate) fn context(&self) -> &Context {
+        &self.context
+    }
+
     fn new(context: &Context, viewport: Viewport) -> ThreeDResult<Camera> {
         Ok(Camera {
+            context: context.clone(),
             viewport,
             projection_type: ProjectionType::Orthographic { height: 1.0 },
             z_near: 0.0,
@@ -397,6 +451,7 @@ impl Camera {
             view: Mat4::identity(),
             projection: Mat4::identity(),
             screen2ray: Mat4::identity(),
+            animation: None,
         })
     }
 
@@ -556,4 +611,102 @@ impl Camera {
         }
         Ok(())
     }
+
+    ///
+    /// Returns the position and target of a view with the same view direction as the current view, but where the given bounding box fits inside the camera frustum.
+    /// Use [set_view](Self::set_view) or [animate_to](Self::animate_to) to apply the view or [frame_aabb](Self::frame_aabb) to do both at once.
+    ///
+    pub fn framing_view(&self, aabb: &AxisAlignedBoundingBox) -> ThreeDResult<(Vec3, Vec3)> {
+        if aabb.is_empty() || aabb.is_infinite() {
+            Err(CoreError::InvalidBoundingBox)?;
+        }
+        let target = aabb.center();
+        let radius = (0.5 * aabb.size().magnitude()).max(f32::EPSILON);
+        let distance = match self.projection_type {
+            ProjectionType::Perspective { field_of_view_y } => {
+                let half_fov_y = 0.5 * field_of_view_y.0;
+                let half_fov_x = (half_fov_y.tan() * self.viewport.aspect()).atan();
+                radius / half_fov_y.min(half_fov_x).sin()
+            }
+            ProjectionType::Orthographic { .. } => {
+                // The distance does not influence the size, so just make sure the bounding box is in front of the camera
+                radius + self.z_near
+            }
+        };
+        Ok((target - self.view_direction() * distance, target))
+    }
+
+    ///
+    /// Changes the view such that the given bounding box fits inside the camera frustum while keeping the same view direction and up direction.
+    /// For an orthographic camera, the height of the projection is also changed.
+    ///
+    pub fn frame_aabb(&mut self, aabb: &AxisAlignedBoundingBox) -> ThreeDResult<()> {
+        let (position, target) = self.framing_view(aabb)?;
+        self.animation = None;
+        self.set_view(position, target, self.up)?;
+        if let ProjectionType::Orthographic { .. } = self.projection_type {
+            let diameter = aabb.size().magnitude();
+            let height = diameter / self.viewport.aspect().min(1.0);
+            self.set_orthographic_projection(height, self.z_near, self.z_far)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Starts an animation that moves the camera from the current position and target to the given position and target over the given duration in milliseconds.
+    /// The up direction stays the same and the progress of the animation is given by the easing function.
+    /// Call [update_animation](Self::update_animation) each frame to advance the animation.
+    ///
+    pub fn animate_to(&mut self, position: Vec3, target: Vec3, duration: f64, easing: Easing) {
+        self.animation = Some(CameraAnimation {
+            start_position: self.position,
+            start_target: self.target,
+            end_position: position,
+            end_target: target,
+            duration,
+            time: 0.0,
+            easing,
+        });
+    }
+
+    ///
+    /// Advances the animation started with [animate_to](Self::animate_to) by the given elapsed time in milliseconds,
+    /// for example [FrameInput::elapsed_time](crate::FrameInput::elapsed_time). Must be called each frame.
+    /// Returns whether or not the view changed.
+    ///
+    pub fn update_animation(&mut self, elapsed_time: f64) -> ThreeDResult<bool> {
+        if let Some(mut animation) = self.animation.take() {
+            animation.time += elapsed_time;
+            let t = if animation.duration > 0.0 {
+                animation
+                    .easing
+                    .apply((animation.time / animation.duration) as f32)
+            } else {
+                1.0
+            };
+            let position = animation.start_position.lerp(animation.end_position, t);
+            let target = animation.start_target.lerp(animation.end_target, t);
+            if animation.time < animation.duration {
+                self.animation = Some(animation);
+            }
+            self.set_view(position, target, self.up)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    ///
+    /// Returns whether or not the camera is currently animating, see [animate_to](Self::animate_to).
+    ///
+    pub fn is_animating(&self) -> bool {
+        self.animation.is_some()
+    }
+
+    ///
+    /// Stops the current animation, if any, leaving the camera at its current position and target.
+    ///
+    pub fn stop_animation(&mut self) {
+        self.animation = None;
+    }
 }
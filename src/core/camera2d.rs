@@ -0,0 +1,201 @@
+use crate::core::*;
+
+///
+/// A camera for viewing 2D content in world units, which can be panned, zoomed and rotated, see for example [PanZoomControl](crate::PanZoomControl).
+/// The 2D objects, for example [Rectangle](crate::Rectangle) and [Circle](crate::Circle), can be rendered with this camera instead of in pixels.
+/// Like the pixel coordinates used when rendering 2D objects without a camera, the x-axis points to the right and the y-axis points down when the camera is not rotated.
+///
+/// The pixel positions used by this camera are in physical pixels relative to the top left corner of the viewport.
+/// The camera dereferences to a [Camera], so it can also be used for rendering any 3D object in the xy-plane.
+///
+pub struct Camera2D {
+    camera: Camera,
+    center: Vec2,
+    zoom: f32,
+    rotation: Radians,
+}
+
+impl Camera2D {
+    ///
+    /// Creates a new 2D camera where the given center in world units is at the center of the viewport and
+    /// the zoom is the number of pixels per world unit. Returns an error if the zoom is not positive and finite.
+    ///
+    pub fn new(
+        context: &Context,
+        viewport: Viewport,
+        center: Vec2,
+        zoom: f32,
+    ) -> ThreeDResult<Self> {
+        check_zoom(zoom)?;
+        let mut camera = Self {
+            camera: Camera::new_orthographic(
+                context,
+                viewport,
+                vec3(0.0, 0.0, -1.0),
+                vec3(0.0, 0.0, 0.0),
+                vec3(0.0, -1.0, 0.0),
+                1.0,
+                0.0,
+                10.0,
+            )?,
+            center,
+            zoom,
+            rotation: radians(0.0),
+        };
+        camera.update()?;
+        Ok(camera)
+    }
+
+    ///
+    /// Creates a new 2D camera where one world unit is one pixel and the origin is at the top left corner of the viewport,
+    /// which is the same as rendering the 2D objects without a camera.
+    ///
+    pub fn new_pixels(context: &Context, viewport: Viewport) -> ThreeDResult<Self> {
+        Self::new(
+            context,
+            viewport,
+            vec2(0.5 * viewport.width as f32, 0.5 * viewport.height as f32),
+            1.0,
+        )
+    }
+
+    ///
+    /// Returns the point in world units at the center of the viewport.
+    ///
+    pub fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    ///
+    /// Set the point in world units at the center of the viewport.
+    ///
+    pub fn set_center(&mut self, center: Vec2) -> ThreeDResult<()> {
+        self.center = center;
+        self.update()
+    }
+
+    ///
+    /// Returns the zoom, ie. the number of pixels per world unit.
+    ///
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    ///
+    /// Set the zoom, ie. the number of pixels per world unit. Returns an error if the zoom is not positive and finite.
+    ///
+    pub fn set_zoom(&mut self, zoom: f32) -> ThreeDResult<()> {
+        check_zoom(zoom)?;
+        self.zoom = zoom;
+        self.update()
+    }
+
+    ///
+    /// Returns the rotation of the view around the center.
+    ///
+    pub fn rotation(&self) -> Radians {
+        self.rotation
+    }
+
+    ///
+    /// Set the rotation of the view around the center. A positive angle rotates the content counterclockwise on the screen.
+    ///
+    pub fn set_rotation(&mut self, rotation: impl Into<Radians>) -> ThreeDResult<()> {
+        self.rotation = rotation.into();
+        self.update()
+    }
+
+    ///
+    /// Set the current viewport. The center of the viewport stays at the same world position.
+    /// Returns whether or not the viewport actually changed.
+    ///
+    pub fn set_viewport(&mut self, viewport: Viewport) -> ThreeDResult<bool> {
+        if self.camera.set_viewport(viewport)? {
+            self.update()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    ///
+    /// Moves the view by the given number of pixels, such that the content follows a cursor moving by the given amount.
+    ///
+    pub fn pan(&mut self, pixels: Vec2) -> ThreeDResult<()> {
+        self.center -= self.pixel_to_world_direction(pixels);
+        self.update()
+    }
+
+    ///
+    /// Multiplies the zoom by the given factor while keeping the world position at the given pixel fixed on the screen.
+    /// Returns an error if the resulting zoom is not positive and finite.
+    ///
+    pub fn zoom_towards(&mut self, pixel: Vec2, factor: f32) -> ThreeDResult<()> {
+        let zoom = self.zoom * factor;
+        check_zoom(zoom)?;
+        let position = self.position_at_pixel(pixel);
+        self.zoom = zoom;
+        self.center = position - self.pixel_to_world_direction(pixel - self.viewport_center());
+        self.update()
+    }
+
+    ///
+    /// Rotates the view by the given angle while keeping the world position at the given pixel fixed on the screen.
+    ///
+    pub fn rotate_around(&mut self, pixel: Vec2, angle: impl Into<Radians>) -> ThreeDResult<()> {
+        let position = self.position_at_pixel(pixel);
+        self.rotation += angle.into();
+        self.center = position - self.pixel_to_world_direction(pixel - self.viewport_center());
+        self.update()
+    }
+
+    ///
+    /// Returns the world position at the given pixel.
+    ///
+    pub fn position_at_pixel(&self, pixel: Vec2) -> Vec2 {
+        self.center + self.pixel_to_world_direction(pixel - self.viewport_center())
+    }
+
+    ///
+    /// Returns the pixel at which the given world position is shown.
+    ///
+    pub fn pixel_at_position(&self, position: Vec2) -> Vec2 {
+        self.viewport_center()
+            + Mat2::from_angle(-self.rotation) * (position - self.center) * self.zoom
+    }
+
+    fn viewport_center(&self) -> Vec2 {
+        let viewport = self.camera.viewport();
+        vec2(0.5 * viewport.width as f32, 0.5 * viewport.height as f32)
+    }
+
+    fn pixel_to_world_direction(&self, pixels: Vec2) -> Vec2 {
+        Mat2::from_angle(self.rotation) * pixels / self.zoom
+    }
+
+    fn update(&mut self) -> ThreeDResult<()> {
+        let up = Mat2::from_angle(self.rotation) * vec2(0.0, -1.0);
+        self.camera.set_view(
+            self.center.extend(-1.0),
+            self.center.extend(0.0),
+            up.extend(0.0),
+        )?;
+        let height = self.camera.viewport().height as f32 / self.zoom;
+        self.camera.set_orthographic_projection(height, 0.0, 10.0)
+    }
+}
+
+fn check_zoom(zoom: f32) -> ThreeDResult<()> {
+    if zoom <= 0.0 || !zoom.is_finite() {
+        Err(CoreError::InvalidZoom(zoom))?;
+    }
+    Ok(())
+}
+
+impl std::ops::Deref for Camera2D {
+    type Target = Camera;
+
+    fn deref(&self) -> &Self::Target {
+        &self.camera
+    }
+}
@@ -0,0 +1,21 @@
+uniform vec4 clipPlanes[8];
+uniform int clipPlaneCount;
+uniform int clipIntersection;
+in vec3 clipPosition;
+
+void main()
+{
+    if(clipPlaneCount > 0) {
+        bool clipped = clipIntersection == 1;
+        for(int i = 0; i < 8; i++) {
+            if(i < clipPlaneCount) {
+                bool c = dot(clipPlanes[i].xyz, clipPosition) + clipPlanes[i].w < 0.0;
+                clipped = clipIntersection == 1 ? clipped && c : clipped || c;
+            }
+        }
+        if(clipped) {
+            discard;
+        }
+    }
+    unclipped_main();
+}
@@ -0,0 +1,188 @@
+use crate::core::*;
+
+///
+/// The maximum number of clip planes, see [ClipPlanes].
+///
+pub const MAX_CLIP_PLANES: usize = 8;
+
+///
+/// Defines how the half spaces clipped away by each of the [ClipPlanes] are combined.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ClipOperation {
+    /// A point is clipped away if it is clipped away by at least one of the planes.
+    /// This is for example used to cut away everything outside a box.
+    #[default]
+    Union,
+    /// A point is only clipped away if it is clipped away by all of the planes.
+    /// This is for example used to cut a corner out of a model.
+    Intersection,
+}
+
+///
+/// User defined clip planes which cut away part of everything that is rendered, for example to create a section view of a model.
+/// Set the clip planes using [Context::set_clip_planes] and they are applied to all subsequent render calls of the built-in geometries,
+/// independent of the material, including when rendering shadow maps and picking.
+///
+/// Each plane is given as the normal in the xyz components and the distance in the w component (in world space),
+/// and the points `p` where `dot(normal, p) + distance < 0` are clipped away by that plane.
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ClipPlanes {
+    /// Up to [MAX_CLIP_PLANES] planes. If empty, nothing is clipped away.
+    pub planes: Vec<Vec4>,
+    /// How the half spaces clipped away by each plane are combined.
+    pub operation: ClipOperation,
+}
+
+impl ClipPlanes {
+    ///
+    /// Creates clip planes where a point is clipped away if it is clipped away by at least one of the given planes, see [ClipOperation::Union].
+    ///
+    pub fn new(planes: Vec<Vec4>) -> Self {
+        Self {
+            planes,
+            operation: ClipOperation::Union,
+        }
+    }
+
+    ///
+    /// Returns a plane, in the format used by [field@ClipPlanes::planes], which goes through the given point and clips away the half space on the opposite side of the given normal.
+    ///
+    pub fn plane(point: Vec3, normal: Vec3) -> Vec4 {
+        let normal = normal.normalize();
+        normal.extend(-normal.dot(point))
+    }
+
+    ///
+    /// Returns true if no planes are defined, ie. nothing is clipped away.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.planes.is_empty()
+    }
+
+    ///
+    /// Returns true if the given point is clipped away.
+    ///
+    pub fn is_clipped(&self, point: Vec3) -> bool {
+        let mut clipped = self
+            .planes()
+            .iter()
+            .map(|plane| plane.truncate().dot(point) + plane.w < 0.0);
+        match self.operation {
+            ClipOperation::Union => clipped.any(|c| c),
+            ClipOperation::Intersection => !self.is_empty() && clipped.all(|c| c),
+        }
+    }
+
+    fn planes(&self) -> &[Vec4] {
+        &self.planes[..self.planes.len().min(MAX_CLIP_PLANES)]
+    }
+
+    ///
+    /// Sends the clip planes and the inverse of the view projection matrix of the given camera to the program.
+    ///
+    pub(in crate::core) fn use_uniforms(
+        &self,
+        program: &Program,
+        camera: &Camera,
+    ) -> ThreeDResult<()> {
+        if !program.requires_uniform("clipPlaneCount") {
+            return Ok(());
+        }
+        let planes = self.planes();
+        program.use_uniform("clipPlaneCount", planes.len() as i32)?;
+        if !planes.is_empty() {
+            program.use_uniform_array("clipPlanes", planes)?;
+            program.use_uniform(
+                "clipIntersection",
+                (self.operation == ClipOperation::Intersection) as i32,
+            )?;
+            program.use_uniform(
+                "clipInverseViewProjection",
+                (camera.projection() * camera.view())
+                    .invert()
+                    .ok_or(CoreError::FailedInvertingTransformationMatrix)?,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+///
+/// Adds clipping to the given vertex and fragment shader sources by renaming the main functions and adding new main functions,
+/// which pass the world position from the vertex shader to the fragment shader and discard the fragments that are clipped away.
+/// A fragment shader which declares the clip plane uniforms itself, for example to clip the samples along a ray, is responsible for the clipping and is left unchanged.
+///
+pub(in crate::core) fn clipped_shader_sources(
+    vertex_shader_source: &str,
+    fragment_shader_source: &str,
+) -> (String, String) {
+    let rename = |source: &str| match main_function(source) {
+        Some(i) => format!("{}unclipped_{}", &source[..i], &source[i..]),
+        None => source.to_string(),
+    };
+    (
+        format!(
+            "{}\n{}",
+            rename(vertex_shader_source),
+            include_str!("clip_planes.vert")
+        ),
+        if fragment_shader_source.contains("uniform int clipIntersection;") {
+            fragment_shader_source.to_string()
+        } else {
+            format!(
+                "{}\n{}",
+                rename(fragment_shader_source),
+                include_str!("clip_planes.frag")
+            )
+        },
+    )
+}
+
+///
+/// Returns the byte index of the name of the main function in the given shader source, skipping comments.
+///
+fn main_function(source: &str) -> Option<usize> {
+    let is_identifier = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut i = 0;
+    while i < source.len() {
+        let rest = &source[i..];
+        if rest.starts_with("//") {
+            i += rest.find('\n').unwrap_or(rest.len());
+        } else if rest.starts_with("/*") {
+            i += rest.find("*/").map(|end| end + 2).unwrap_or(rest.len());
+        } else if rest.starts_with("main")
+            && !source[..i].ends_with(is_identifier)
+            && rest[4..].trim_start().starts_with('(')
+            && source[..i]
+                .trim_end()
+                .strip_suffix("void")
+                .map(|before| !before.ends_with(is_identifier))
+                .unwrap_or(false)
+        {
+            return Some(i);
+        } else {
+            i += rest.chars().next().map(char::len_utf8).unwrap_or(1);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main_function_is_found() {
+        for source in [
+            "void main() {}",
+            "void main () {}",
+            "void main(void) {}",
+            "// void main() {}\n/* void main() {} */\nvoid\nmain\n() {}",
+        ] {
+            assert_eq!(main_function(source), source.rfind("main"), "{}", source);
+        }
+        assert_eq!(main_function("void domain() {}\nint main;"), None);
+    }
+}
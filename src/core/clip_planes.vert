@@ -0,0 +1,9 @@
+uniform mat4 clipInverseViewProjection;
+out vec3 clipPosition;
+
+void main()
+{
+    unclipped_main();
+    vec4 p = clipInverseViewProjection * gl_Position;
+    clipPosition = p.xyz / p.w;
+}
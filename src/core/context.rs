@@ -1,4 +1,5 @@
 use super::*;
+use std::any::{Any, TypeId};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -14,12 +15,18 @@ pub use crate::context::HasContext;
 #[derive(Clone)]
 pub struct Context {
     context: Rc<crate::context::Context>,
-    pub(super) vao: crate::context::VertexArray,
+    vao: Rc<RefCell<crate::context::VertexArray>>,
     programs: Rc<RefCell<HashMap<String, Program>>>,
     effects: Rc<RefCell<HashMap<String, ImageEffect>>>,
     camera2d: Rc<RefCell<Option<Camera>>>,
+    objects: Rc<RefCell<HashMap<TypeId, Box<dyn Any>>>>,
+    clip_planes: Rc<RefCell<ClipPlanes>>,
+    render_statistics: Rc<RefCell<RenderStatistics>>,
+    last_draw: Rc<RefCell<Option<(crate::context::Program, RenderStates)>>>,
     #[cfg(all(feature = "glutin-window", not(target_arch = "wasm32")))]
     pub(crate) glutin_context: Option<Rc<glutin::Context<glutin::PossiblyCurrent>>>,
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) webgl_context: Option<web_sys::WebGl2RenderingContext>,
 }
 
 impl Context {
@@ -30,42 +37,93 @@ impl Context {
     /// you can also call this method with a reference counter to a glow context created using glow and not the re-export in [context](crate::context).
     ///
     pub fn from_gl_context(context: Rc<crate::context::Context>) -> ThreeDResult<Self> {
-        #[cfg(not(target_arch = "wasm32"))]
-        unsafe {
-            // Enable seamless cube map textures
-            context.enable(crate::context::TEXTURE_CUBE_MAP_SEAMLESS);
-            context.pixel_store_i32(crate::context::UNPACK_ALIGNMENT, 1);
-            context.pixel_store_i32(crate::context::PACK_ALIGNMENT, 1);
-        };
         let c = unsafe {
-            // Create one Vertex Array Object which is then reused all the time.
-            let vao = context
-                .create_vertex_array()
-                .map_err(|e| CoreError::ContextCreation(e))?;
+            let vao = Self::init_gl_context(&context)?;
             Self {
                 context,
-                vao,
+                vao: Rc::new(RefCell::new(vao)),
                 programs: Rc::new(RefCell::new(HashMap::new())),
                 effects: Rc::new(RefCell::new(HashMap::new())),
                 camera2d: Rc::new(RefCell::new(None)),
+                objects: Rc::new(RefCell::new(HashMap::new())),
+                clip_planes: Rc::new(RefCell::new(ClipPlanes::default())),
+                render_statistics: Rc::new(RefCell::new(RenderStatistics::default())),
+                last_draw: Rc::new(RefCell::new(None)),
                 #[cfg(all(feature = "glutin-window", not(target_arch = "wasm32")))]
                 glutin_context: None,
+                #[cfg(target_arch = "wasm32")]
+                webgl_context: None,
             }
         };
         c.error_check()?;
         Ok(c)
     }
 
+    ///
+    /// Sets up the low-level graphics context which is current and returns a Vertex Array Object which is then reused all the time.
+    ///
+    unsafe fn init_gl_context(
+        context: &crate::context::Context,
+    ) -> ThreeDResult<crate::context::VertexArray> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            // Enable seamless cube map textures
+            context.enable(crate::context::TEXTURE_CUBE_MAP_SEAMLESS);
+            context.pixel_store_i32(crate::context::UNPACK_ALIGNMENT, 1);
+            context.pixel_store_i32(crate::context::PACK_ALIGNMENT, 1);
+        };
+        Ok(context
+            .create_vertex_array()
+            .map_err(|e| CoreError::ContextCreation(e))?)
+    }
+
+    ///
+    /// Sets up a new low-level graphics context which shares resources (buffers, textures, programs etc.) with this context.
+    /// The new low-level context must be current when calling this method.
+    /// Returns the Vertex Array Object for the new low-level context, since these cannot be shared between contexts.
+    ///
+    #[cfg(all(feature = "glutin-window", not(target_arch = "wasm32")))]
+    pub(crate) fn init_shared_gl_context(&self) -> ThreeDResult<crate::context::VertexArray> {
+        let vao = unsafe { Self::init_gl_context(&self.context)? };
+        self.error_check()?;
+        Ok(vao)
+    }
+
+    ///
+    /// Specifies the Vertex Array Object to use, which must belong to the low-level graphics context that is current.
+    /// Should be called each time a different low-level context, sharing resources with this context, is made current.
+    ///
+    #[cfg(all(feature = "glutin-window", not(target_arch = "wasm32")))]
+    pub(crate) fn set_vertex_array(&self, vao: crate::context::VertexArray) {
+        *self.vao.borrow_mut() = vao;
+    }
+
+    #[allow(clippy::clone_on_copy)] // Only Copy on native
+    pub(crate) fn vertex_array(&self) -> crate::context::VertexArray {
+        self.vao.borrow().clone()
+    }
+
     ///
     /// Compiles a [Program] with the given vertex and fragment shader source and stores it for later use.
     /// If it has already been created, then it is just returned.
     ///
+    /// If clip planes are set (see [Context::set_clip_planes]), clipping is added to the shader sources,
+    /// and [Program::use_clip_planes] should be called in the callback to apply the clip planes.
+    ///
     pub fn program(
         &self,
         vertex_shader_source: &str,
         fragment_shader_source: &str,
         callback: impl FnOnce(&Program) -> ThreeDResult<()>,
     ) -> ThreeDResult<()> {
+        let clipped;
+        let (vertex_shader_source, fragment_shader_source) = if self.clip_planes.borrow().is_empty()
+        {
+            (vertex_shader_source, fragment_shader_source)
+        } else {
+            clipped = clipped_shader_sources(vertex_shader_source, fragment_shader_source);
+            (clipped.0.as_str(), clipped.1.as_str())
+        };
         let key = format!("{}{}", vertex_shader_source, fragment_shader_source);
         if !self.programs.borrow().contains_key(&key) {
             self.programs.borrow_mut().insert(
@@ -76,6 +134,167 @@ impl Context {
         callback(self.programs.borrow().get(&key).unwrap())
     }
 
+    ///
+    /// Sets the clip planes which cut away part of everything that is rendered after this call, see [ClipPlanes].
+    /// Use [ClipPlanes::default] to disable clipping again, for example before rendering a GUI or other objects that should not be clipped.
+    ///
+    pub fn set_clip_planes(&self, clip_planes: ClipPlanes) {
+        *self.clip_planes.borrow_mut() = clip_planes;
+    }
+
+    ///
+    /// Returns the clip planes that are currently applied, see [Context::set_clip_planes].
+    ///
+    pub fn clip_planes(&self) -> ClipPlanes {
+        self.clip_planes.borrow().clone()
+    }
+
+    ///
+    /// Returns the counters for all render calls made using this context since it was created or since the last call to [Context::reset_render_statistics].
+    ///
+    pub fn render_statistics(&self) -> RenderStatistics {
+        *self.render_statistics.borrow()
+    }
+
+    ///
+    /// Resets the counters returned by [Context::render_statistics] to zero.
+    ///
+    pub fn reset_render_statistics(&self) {
+        *self.render_statistics.borrow_mut() = RenderStatistics::default();
+    }
+
+    #[allow(clippy::clone_on_copy)] // Only Copy on native
+    pub(in crate::core) fn count_draw_call(
+        &self,
+        program: &crate::context::Program,
+        render_states: RenderStates,
+        triangles: u64,
+    ) {
+        let mut statistics = self.render_statistics.borrow_mut();
+        let mut last_draw = self.last_draw.borrow_mut();
+        statistics.draw_calls += 1;
+        statistics.triangles += triangles;
+        if last_draw
+            .as_ref()
+            .map(|(p, _)| p != program)
+            .unwrap_or(true)
+        {
+            statistics.program_changes += 1;
+        }
+        if last_draw
+            .as_ref()
+            .map(|(_, r)| *r != render_states)
+            .unwrap_or(true)
+        {
+            statistics.render_state_changes += 1;
+        }
+        *last_draw = Some((program.clone(), render_states));
+    }
+
+    ///
+    /// Routes the debug messages from the graphics driver with the given severity or higher to the [log](https://crates.io/crates/log) crate,
+    /// or stops the debug messages if `None` is given.
+    /// The messages are logged with the target `three_d::gl` and a log level corresponding to the severity, see [DebugSeverity].
+    /// Since the messages are sent synchronously, the log message is written at the call causing the message, which makes it easy to find using a debugger.
+    ///
+    /// **Note:** Requires the `KHR_debug` extension, which is not available on web, otherwise this does nothing.
+    /// Some drivers only send debug messages if the context is created as a debug context.
+    ///
+    pub fn set_debug_output(&self, severity: Option<DebugSeverity>) {
+        if !self.supports_debug() {
+            return;
+        }
+        unsafe {
+            if let Some(severity) = severity {
+                for s in [
+                    DebugSeverity::Notification,
+                    DebugSeverity::Low,
+                    DebugSeverity::Medium,
+                    DebugSeverity::High,
+                ] {
+                    self.debug_message_control(
+                        crate::context::DONT_CARE,
+                        crate::context::DONT_CARE,
+                        s.to_gl(),
+                        &[],
+                        s >= severity,
+                    );
+                }
+                // The callback must not capture anything, since the callback is not stored by glow
+                self.debug_message_callback(|source, message_type, id, severity, message| {
+                    log_debug_message(source, message_type, id, severity, message)
+                });
+                self.enable(crate::context::DEBUG_OUTPUT);
+                self.enable(crate::context::DEBUG_OUTPUT_SYNCHRONOUS);
+            } else {
+                self.disable(crate::context::DEBUG_OUTPUT);
+            }
+        }
+    }
+
+    ///
+    /// Groups the render calls made in the callback under the given name in graphics debuggers like [RenderDoc](https://renderdoc.org/) and in debug messages.
+    /// Groups can be nested.
+    ///
+    /// **Note:** Requires the `KHR_debug` extension, which is not available on web, otherwise the callback is just called.
+    ///
+    pub fn debug_group<T>(
+        &self,
+        name: &str,
+        callback: impl FnOnce() -> ThreeDResult<T>,
+    ) -> ThreeDResult<T> {
+        if !self.supports_debug() {
+            return callback();
+        }
+        unsafe {
+            self.push_debug_group(crate::context::DEBUG_SOURCE_APPLICATION, 0, name);
+        }
+        let result = callback();
+        unsafe {
+            self.pop_debug_group();
+        }
+        result
+    }
+
+    ///
+    /// Sets the label of the object of the given type (for example [TEXTURE](crate::context::TEXTURE)) which is bound by the `bind` callback
+    /// and returned when querying the given binding parameter (for example [TEXTURE_BINDING_2D](crate::context::TEXTURE_BINDING_2D)).
+    ///
+    pub(in crate::core) fn set_object_label(
+        &self,
+        identifier: u32,
+        binding: u32,
+        bind: impl FnOnce(),
+        label: &str,
+    ) {
+        if !self.supports_debug() {
+            return;
+        }
+        bind();
+        unsafe {
+            // The object name used by the driver is not exposed by glow, so it is found by querying the binding
+            let name = self.get_parameter_i32(binding) as u32;
+            self.object_label(identifier, name, Some(label));
+        }
+    }
+
+    ///
+    /// Returns whether timer queries, used to measure the time the GPU spends on render calls, are supported, see [TimerQuery].
+    ///
+    pub fn supports_timer_queries(&self) -> bool {
+        let extensions = self.supported_extensions();
+        #[cfg(target_arch = "wasm32")]
+        {
+            // The queries are made directly on the WebGL context, since the results cannot be read through the graphics context
+            self.webgl_context.is_some() && extensions.contains("EXT_disjoint_timer_query_webgl2")
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            // Part of desktop OpenGL since version 3.3
+            !self.version().is_embedded || extensions.contains("GL_EXT_disjoint_timer_query")
+        }
+    }
+
     ///
     /// Compiles an [ImageEffect] with the given fragment shader source and stores it for later use.
     /// If it has already been created, then it is just returned.
@@ -137,6 +356,26 @@ impl Context {
         callback(camera2d.as_ref().unwrap())
     }
 
+    ///
+    /// Gives the object of the given type, which is stored in this context, to the callback.
+    /// If it has not been created yet, it is created using the given function and stored for later use.
+    ///
+    pub(crate) fn object<T: 'static>(
+        &self,
+        create: impl FnOnce(&Context) -> ThreeDResult<T>,
+        callback: impl FnOnce(&mut T) -> ThreeDResult<()>,
+    ) -> ThreeDResult<()> {
+        // The object is taken out of the map while in use, so the callback can use other stored objects
+        let object = self.objects.borrow_mut().remove(&TypeId::of::<T>());
+        let mut object = match object {
+            Some(object) => object,
+            None => Box::new(create(self)?),
+        };
+        let result = callback(object.downcast_mut::<T>().unwrap());
+        self.objects.borrow_mut().insert(TypeId::of::<T>(), object);
+        result
+    }
+
     ///
     /// Set the scissor test for this context (see [ScissorBox]).
     ///
@@ -276,6 +515,62 @@ impl Context {
         }
     }
 
+    ///
+    /// Set the stencil test and stencil operations for this context (see [Stencil]).
+    ///
+    pub fn set_stencil(&self, stencil: Stencil) {
+        unsafe {
+            if let Stencil::Enabled { front, back } = stencil {
+                self.enable(crate::context::STENCIL_TEST);
+                for (face, settings) in
+                    [(crate::context::FRONT, front), (crate::context::BACK, back)]
+                {
+                    self.stencil_func_separate(
+                        face,
+                        Self::stencil_const_from_test(settings.test),
+                        settings.reference as i32,
+                        settings.read_mask as u32,
+                    );
+                    self.stencil_op_separate(
+                        face,
+                        Self::stencil_const_from_operation(settings.fail),
+                        Self::stencil_const_from_operation(settings.depth_fail),
+                        Self::stencil_const_from_operation(settings.pass),
+                    );
+                    self.stencil_mask_separate(face, settings.write_mask as u32);
+                }
+            } else {
+                self.disable(crate::context::STENCIL_TEST);
+            }
+        }
+    }
+
+    fn stencil_const_from_test(test: StencilTest) -> u32 {
+        match test {
+            StencilTest::Never => crate::context::NEVER,
+            StencilTest::Less => crate::context::LESS,
+            StencilTest::Equal => crate::context::EQUAL,
+            StencilTest::LessOrEqual => crate::context::LEQUAL,
+            StencilTest::Greater => crate::context::GREATER,
+            StencilTest::NotEqual => crate::context::NOTEQUAL,
+            StencilTest::GreaterOrEqual => crate::context::GEQUAL,
+            StencilTest::Always => crate::context::ALWAYS,
+        }
+    }
+
+    fn stencil_const_from_operation(operation: StencilOperation) -> u32 {
+        match operation {
+            StencilOperation::Keep => crate::context::KEEP,
+            StencilOperation::Zero => crate::context::ZERO,
+            StencilOperation::Replace => crate::context::REPLACE,
+            StencilOperation::Increment => crate::context::INCR,
+            StencilOperation::IncrementWrap => crate::context::INCR_WRAP,
+            StencilOperation::Decrement => crate::context::DECR,
+            StencilOperation::DecrementWrap => crate::context::DECR_WRAP,
+            StencilOperation::Invert => crate::context::INVERT,
+        }
+    }
+
     fn blend_const_from_multiplier(multiplier: BlendMultiplierType) -> u32 {
         match multiplier {
             BlendMultiplierType::Zero => crate::context::ZERO,
@@ -313,6 +608,7 @@ impl Context {
             unsafe { self.disable(crate::context::DEPTH_TEST) }
         }
         self.set_blend(render_states.blend);
+        self.set_stencil(render_states.stencil);
         self.error_check()
     }
 
@@ -400,6 +696,69 @@ impl std::fmt::Debug for Context {
     }
 }
 
+///
+/// The severity of a debug message from the graphics driver, see [Context::set_debug_output].
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DebugSeverity {
+    /// Information, for example about buffer usage. Logged as [log::Level::Debug].
+    Notification,
+    /// Minor performance issues and redundant state changes. Logged as [log::Level::Info].
+    Low,
+    /// Major performance issues and use of deprecated functionality. Logged as [log::Level::Warn].
+    Medium,
+    /// Errors and undefined behaviour. Logged as [log::Level::Error].
+    High,
+}
+
+impl DebugSeverity {
+    fn to_gl(self) -> u32 {
+        match self {
+            Self::Notification => crate::context::DEBUG_SEVERITY_NOTIFICATION,
+            Self::Low => crate::context::DEBUG_SEVERITY_LOW,
+            Self::Medium => crate::context::DEBUG_SEVERITY_MEDIUM,
+            Self::High => crate::context::DEBUG_SEVERITY_HIGH,
+        }
+    }
+}
+
+fn log_debug_message(source: u32, message_type: u32, id: u32, severity: u32, message: &str) {
+    let level = match severity {
+        crate::context::DEBUG_SEVERITY_HIGH => log::Level::Error,
+        crate::context::DEBUG_SEVERITY_MEDIUM => log::Level::Warn,
+        crate::context::DEBUG_SEVERITY_LOW => log::Level::Info,
+        _ => log::Level::Debug,
+    };
+    let source = match source {
+        crate::context::DEBUG_SOURCE_API => "API",
+        crate::context::DEBUG_SOURCE_WINDOW_SYSTEM => "window system",
+        crate::context::DEBUG_SOURCE_SHADER_COMPILER => "shader compiler",
+        crate::context::DEBUG_SOURCE_THIRD_PARTY => "third party",
+        crate::context::DEBUG_SOURCE_APPLICATION => "application",
+        _ => "other",
+    };
+    let message_type = match message_type {
+        crate::context::DEBUG_TYPE_ERROR => "error",
+        crate::context::DEBUG_TYPE_DEPRECATED_BEHAVIOR => "deprecated behavior",
+        crate::context::DEBUG_TYPE_UNDEFINED_BEHAVIOR => "undefined behavior",
+        crate::context::DEBUG_TYPE_PORTABILITY => "portability",
+        crate::context::DEBUG_TYPE_PERFORMANCE => "performance",
+        crate::context::DEBUG_TYPE_MARKER => "marker",
+        crate::context::DEBUG_TYPE_PUSH_GROUP => "push group",
+        crate::context::DEBUG_TYPE_POP_GROUP => "pop group",
+        _ => "other",
+    };
+    log::log!(
+        target: "three_d::gl",
+        level,
+        "{} {} ({}): {}",
+        source,
+        message_type,
+        id,
+        message
+    );
+}
+
 impl std::ops::Deref for Context {
     type Target = crate::context::Context;
     fn deref(&self) -> &Self::Target {
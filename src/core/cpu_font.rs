@@ -0,0 +1,31 @@
+use crate::core::*;
+
+///
+/// A font stored on the CPU, for example loaded from a TrueType (.ttf) or OpenType (.otf) file using [Loaded::font](crate::io::Loaded::font).
+/// Use it to construct a [Font](crate::renderer::Font) which can be used for rendering text.
+///
+#[derive(Clone)]
+pub struct CpuFont {
+    font: ab_glyph::FontArc,
+}
+
+impl CpuFont {
+    ///
+    /// Parses the given TrueType (.ttf) or OpenType (.otf) font data.
+    ///
+    pub fn new(bytes: Vec<u8>) -> ThreeDResult<Self> {
+        let font = ab_glyph::FontArc::try_from_vec(bytes)
+            .map_err(|e| CoreError::FontCreation(e.to_string()))?;
+        Ok(Self { font })
+    }
+
+    pub(crate) fn font(&self) -> &ab_glyph::FontArc {
+        &self.font
+    }
+}
+
+impl std::fmt::Debug for CpuFont {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CpuFont").finish()
+    }
+}
@@ -6,6 +6,7 @@ use crate::core::*;
 /// Lighting models which specify how the lighting is computed when rendering a material.
 /// This is a trade-off between how fast the computations are versus how physically correct they look.
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum LightingModel {
     /// Phong lighting model.
     /// The fastest lighting model to calculate.
@@ -21,6 +22,7 @@ pub enum LightingModel {
 /// The geometry function used in a Cook-Torrance lighting model.
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[allow(missing_docs)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum GeometryFunction {
     SmithSchlickGGX,
 }
@@ -28,6 +30,7 @@ pub enum GeometryFunction {
 /// The normal distribution function used in a Cook-Torrance lighting model.
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[allow(missing_docs)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum NormalDistributionFunction {
     Blinn,
     Beckmann,
@@ -39,6 +42,7 @@ pub enum NormalDistributionFunction {
 /// Can be constructed manually or loaded via [io](crate::io).
 ///
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CpuMaterial {
     /// Name. Used for matching geometry and material.
     pub name: String,
@@ -4,6 +4,7 @@ use crate::core::*;
 /// An array of indices. Supports different data types.
 ///
 #[derive(Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum Indices {
     /// Uses unsigned 8 bit integer for each index.
     U8(Vec<u8>),
@@ -70,6 +71,7 @@ impl std::fmt::Debug for Indices {
 /// An array of positions. Supports f32 and f64 data types.
 ///
 #[derive(Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum Positions {
     /// Uses 32 bit float for the vertex positions.
     F32(Vec<Vector3<f32>>),
@@ -138,6 +140,7 @@ impl std::fmt::Debug for Positions {
 /// or via the utility functions for generating simple triangle meshes.
 ///
 #[derive(Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CpuMesh {
     /// Name.
     pub name: String,
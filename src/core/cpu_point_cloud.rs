@@ -0,0 +1,83 @@
+use crate::core::*;
+
+///
+/// A CPU-side version of a point cloud, for example a lidar scan, which can be used to construct a [PointCloud](crate::renderer::PointCloud).
+///
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct CpuPointCloud {
+    /// Name.
+    pub name: String,
+    /// The positions of the points.
+    pub positions: Vec<Vec3>,
+    /// The colors of the points. The colors are assumed to be in linear color space.
+    pub colors: Option<Vec<Color>>,
+    /// The normals of the points, which are used when rendering the points with a material that is affected by lights.
+    pub normals: Option<Vec<Vec3>>,
+    /// The intensity of the points, for example the strength of the returned lidar pulse, which is multiplied onto the color of each point.
+    /// The intensities are expected to be in the range `[0..1]`.
+    pub intensities: Option<Vec<f32>>,
+}
+
+impl CpuPointCloud {
+    ///
+    /// Returns the number of points.
+    ///
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    ///
+    /// Returns whether the point cloud contains any points.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    ///
+    /// Returns an error if the point cloud is not valid, ie. if the number of colors, normals or intensities are not the same as the number of positions.
+    ///
+    pub fn validate(&self) -> ThreeDResult<()> {
+        let count = self.positions.len();
+        let buffer_check = |length: Option<usize>, name: &str| -> ThreeDResult<()> {
+            if let Some(length) = length {
+                if length != count {
+                    Err(CoreError::InvalidBufferLength(
+                        name.to_string(),
+                        count,
+                        length,
+                    ))?;
+                }
+            }
+            Ok(())
+        };
+
+        buffer_check(self.colors.as_ref().map(|b| b.len()), "color")?;
+        buffer_check(self.normals.as_ref().map(|b| b.len()), "normal")?;
+        buffer_check(self.intensities.as_ref().map(|b| b.len()), "intensity")?;
+
+        Ok(())
+    }
+
+    ///
+    /// Computes the [AxisAlignedBoundingBox] of the points.
+    ///
+    pub fn compute_aabb(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::new_with_positions(&self.positions)
+    }
+}
+
+impl From<&CpuMesh> for CpuPointCloud {
+    ///
+    /// Constructs a point cloud from the vertices of the mesh, for example a mesh loaded from a .ply file without faces.
+    ///
+    fn from(cpu_mesh: &CpuMesh) -> Self {
+        Self {
+            name: cpu_mesh.name.clone(),
+            positions: cpu_mesh.positions.to_f32(),
+            colors: cpu_mesh.colors.clone(),
+            normals: cpu_mesh.normals.clone(),
+            intensities: None,
+        }
+    }
+}
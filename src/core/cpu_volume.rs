@@ -4,6 +4,7 @@ use crate::core::*;
 /// Volume data consisting of voxel data inside a cube.
 ///
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CpuVolume {
     /// Name.
     pub name: String,
@@ -13,6 +14,10 @@ pub struct CpuVolume {
 
     /// The size of the cube that is spanned by the voxel data.
     pub size: Vec3,
+
+    /// The range of the original values, for example the Hounsfield units of a CT scan, which are scaled to the range `[0..1]` in the voxel data.
+    /// A value `v` sampled from the voxel data corresponds to the original value `value_range.0 + v * (value_range.1 - value_range.0)`.
+    pub value_range: (f32, f32),
 }
 
 impl std::default::Default for CpuVolume {
@@ -21,6 +26,22 @@ impl std::default::Default for CpuVolume {
             name: String::default(),
             voxels: CpuTexture3D::default(),
             size: vec3(2.0, 2.0, 2.0),
+            value_range: (0.0, 1.0),
+        }
+    }
+}
+
+impl CpuVolume {
+    ///
+    /// Returns the value in the range `[0..1]`, as sampled from the voxel data, which corresponds to the given original value (see [CpuVolume::value_range]).
+    /// This can for example be used to find the threshold of an isosurface given in Hounsfield units.
+    ///
+    pub fn normalized_value(&self, value: f32) -> f32 {
+        let (min, max) = self.value_range;
+        if max > min {
+            (value - min) / (max - min)
+        } else {
+            0.0
         }
     }
 }
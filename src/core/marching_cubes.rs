@@ -0,0 +1,496 @@
+use crate::core::*;
+use std::collections::HashMap;
+
+impl CpuVolume {
+    ///
+    /// Extracts the isosurface at the given threshold from the red channel of the voxel data using the marching cubes algorithm and returns it as a [CpuMesh].
+    /// The voxel values are in the range `[0..1]` for 8-bit data, exactly like the values sampled in a shader, so the same threshold as for the [IsourfaceMaterial](crate::renderer::IsourfaceMaterial) can be used.
+    /// Use [CpuVolume::normalized_value] to find the threshold corresponding to an original value, for example in Hounsfield units.
+    /// Voxels with a value larger than or equal to the threshold are inside the surface.
+    ///
+    /// The mesh is in the same coordinate system as the volume, ie. a cube with center in origo and the [CpuVolume::size] as size,
+    /// and has smooth normals computed from the gradient of the voxel data.
+    /// The vertices are shared between neighbouring triangles, so the mesh is closed, except where the surface is cut by the boundary of the volume.
+    ///
+    pub fn marching_cubes(&self, threshold: f32) -> ThreeDResult<CpuMesh> {
+        let voxels = &self.voxels;
+        let (width, height, depth) = (
+            voxels.width as usize,
+            voxels.height as usize,
+            voxels.depth as usize,
+        );
+        let values = red_channel(&voxels.data)?;
+        if values.len() != width * height * depth {
+            Err(CoreError::InvalidBufferLength(
+                "voxel".to_string(),
+                width * height * depth,
+                values.len(),
+            ))?;
+        }
+
+        let index = |x: usize, y: usize, z: usize| x + y * width + z * width * height;
+        let value = |x: usize, y: usize, z: usize| values[index(x, y, z)];
+        let spacing = vec3(
+            self.size.x / width as f32,
+            self.size.y / height as f32,
+            self.size.z / depth as f32,
+        );
+        // The position of the center of a voxel
+        let position = |x: usize, y: usize, z: usize| {
+            vec3(
+                (x as f32 + 0.5) * spacing.x,
+                (y as f32 + 0.5) * spacing.y,
+                (z as f32 + 0.5) * spacing.z,
+            ) - 0.5 * self.size
+        };
+        // The gradient at the center of a voxel using central differences, or one-sided differences at the boundary
+        let gradient = |x: usize, y: usize, z: usize| {
+            let difference = |i: usize, n: usize, f: &dyn Fn(usize) -> f32| {
+                let (i0, i1) = (i.saturating_sub(1), (i + 1).min(n - 1));
+                if i1 > i0 {
+                    (f(i1) - f(i0)) / (i1 - i0) as f32
+                } else {
+                    0.0
+                }
+            };
+            vec3(
+                difference(x, width, &|i| value(i, y, z)) / spacing.x,
+                difference(y, height, &|i| value(x, i, z)) / spacing.y,
+                difference(z, depth, &|i| value(x, y, i)) / spacing.z,
+            )
+        };
+
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut indices = Vec::new();
+        // Maps an edge, given by the voxel at the lower end and the axis, to the vertex on that edge
+        let mut vertices: HashMap<(usize, usize), u32> = HashMap::new();
+        for z in 0..depth.saturating_sub(1) {
+            for y in 0..height.saturating_sub(1) {
+                for x in 0..width.saturating_sub(1) {
+                    let corner = |c: usize| {
+                        let [dx, dy, dz] = CORNERS[c];
+                        (x + dx, y + dy, z + dz)
+                    };
+                    let mut case = 0;
+                    for c in 0..8 {
+                        let (cx, cy, cz) = corner(c);
+                        if value(cx, cy, cz) >= threshold {
+                            case |= 1 << c;
+                        }
+                    }
+                    for edge in TRIANGLE_TABLE[case] {
+                        let [c0, c1] = EDGES[*edge as usize];
+                        let (p0, p1) = (corner(c0), corner(c1));
+                        let lower = (p0.0.min(p1.0), p0.1.min(p1.1), p0.2.min(p1.2));
+                        let vertex = *vertices
+                            .entry((index(lower.0, lower.1, lower.2), EDGE_AXES[*edge as usize]))
+                            .or_insert_with(|| {
+                                let (v0, v1) = (value(p0.0, p0.1, p0.2), value(p1.0, p1.1, p1.2));
+                                let t = if v1 != v0 {
+                                    ((threshold - v0) / (v1 - v0)).clamp(0.0, 1.0)
+                                } else {
+                                    0.5
+                                };
+                                positions.push(
+                                    position(p0.0, p0.1, p0.2).lerp(position(p1.0, p1.1, p1.2), t),
+                                );
+                                // The normal points towards lower values, ie. out of the surface
+                                let normal =
+                                    -gradient(p0.0, p0.1, p0.2).lerp(gradient(p1.0, p1.1, p1.2), t);
+                                normals.push(if normal.magnitude2() > 0.0 {
+                                    normal.normalize()
+                                } else {
+                                    vec3(0.0, 0.0, 0.0)
+                                });
+                                positions.len() as u32 - 1
+                            });
+                        indices.push(vertex);
+                    }
+                }
+            }
+        }
+
+        Ok(CpuMesh {
+            name: self.name.clone(),
+            positions: Positions::F32(positions),
+            indices: Some(Indices::U32(indices)),
+            normals: Some(normals),
+            ..Default::default()
+        })
+    }
+}
+
+///
+/// Returns the values in the red channel converted to floating point in the same way as when sampled in a shader.
+///
+fn red_channel(data: &TextureData) -> ThreeDResult<Vec<f32>> {
+    let from_u8 = |v: u8| v as f32 / 255.0;
+    Ok(match data {
+        TextureData::RU8(values) => values.iter().map(|v| from_u8(*v)).collect(),
+        TextureData::RgU8(values) => values.iter().map(|v| from_u8(v[0])).collect(),
+        TextureData::RgbU8(values) => values.iter().map(|v| from_u8(v[0])).collect(),
+        TextureData::RgbaU8(values) => values.iter().map(|v| from_u8(v[0])).collect(),
+        TextureData::RF16(values) => values.iter().map(|v| v.to_f32()).collect(),
+        TextureData::RgF16(values) => values.iter().map(|v| v[0].to_f32()).collect(),
+        TextureData::RgbF16(values) => values.iter().map(|v| v[0].to_f32()).collect(),
+        TextureData::RgbaF16(values) => values.iter().map(|v| v[0].to_f32()).collect(),
+        TextureData::RF32(values) => values.clone(),
+        TextureData::RgF32(values) => values.iter().map(|v| v[0]).collect(),
+        TextureData::RgbF32(values) => values.iter().map(|v| v[0]).collect(),
+        TextureData::RgbaF32(values) => values.iter().map(|v| v[0]).collect(),
+        TextureData::Compressed { .. } => Err(CoreError::CompressedVoxelData)?,
+    })
+}
+
+// The corners of a cell, where bit i in the case index is set if corner i is inside the surface
+const CORNERS: [[usize; 3]; 8] = [
+    [0, 0, 0],
+    [1, 0, 0],
+    [1, 1, 0],
+    [0, 1, 0],
+    [0, 0, 1],
+    [1, 0, 1],
+    [1, 1, 1],
+    [0, 1, 1],
+];
+
+// The edges of a cell given by the two corners
+const EDGES: [[usize; 2]; 12] = [
+    [0, 1],
+    [1, 2],
+    [2, 3],
+    [3, 0],
+    [4, 5],
+    [5, 6],
+    [6, 7],
+    [7, 4],
+    [0, 4],
+    [1, 5],
+    [2, 6],
+    [3, 7],
+];
+
+// The axis along which each edge goes
+const EDGE_AXES: [usize; 12] = [0, 1, 0, 1, 0, 1, 0, 1, 2, 2, 2, 2];
+
+// The triangles for each of the 256 cases as triplets of edges, with counterclockwise winding seen from outside the surface.
+// Ambiguous faces are resolved by separating the inside corners, which is consistent between neighbouring cells, so the surface has no holes.
+// The polygons are triangulated without any diagonals on the faces of the cell, so each edge inside the volume is shared by exactly two triangles.
+const TRIANGLE_TABLE: [&[u8]; 256] = [
+    &[],
+    &[0, 3, 8],
+    &[0, 9, 1],
+    &[1, 3, 8, 1, 8, 9],
+    &[1, 10, 2],
+    &[0, 3, 8, 1, 10, 2],
+    &[0, 9, 10, 0, 10, 2],
+    &[2, 3, 8, 2, 8, 9, 2, 9, 10],
+    &[2, 11, 3],
+    &[0, 2, 11, 0, 11, 8],
+    &[0, 9, 1, 2, 11, 3],
+    &[1, 2, 11, 1, 11, 8, 1, 8, 9],
+    &[1, 10, 11, 1, 11, 3],
+    &[0, 1, 10, 0, 10, 11, 0, 11, 8],
+    &[0, 9, 10, 0, 10, 11, 0, 11, 3],
+    &[8, 9, 10, 8, 10, 11],
+    &[4, 8, 7],
+    &[0, 3, 7, 0, 7, 4],
+    &[0, 9, 1, 4, 8, 7],
+    &[1, 3, 7, 1, 7, 4, 1, 4, 9],
+    &[1, 10, 2, 4, 8, 7],
+    &[0, 3, 7, 0, 7, 4, 1, 10, 2],
+    &[0, 9, 10, 0, 10, 2, 4, 8, 7],
+    &[2, 3, 7, 2, 7, 4, 2, 4, 9, 2, 9, 10],
+    &[2, 11, 3, 4, 8, 7],
+    &[0, 2, 11, 0, 11, 7, 0, 7, 4],
+    &[0, 9, 1, 2, 11, 3, 4, 8, 7],
+    &[1, 2, 11, 1, 11, 7, 1, 7, 4, 1, 4, 9],
+    &[1, 10, 11, 1, 11, 3, 4, 8, 7],
+    &[0, 1, 10, 0, 10, 11, 0, 11, 7, 0, 7, 4],
+    &[0, 9, 10, 0, 10, 11, 0, 11, 3, 4, 8, 7],
+    &[4, 9, 10, 4, 10, 11, 4, 11, 7],
+    &[4, 5, 9],
+    &[0, 3, 8, 4, 5, 9],
+    &[0, 4, 5, 0, 5, 1],
+    &[1, 3, 8, 1, 8, 4, 1, 4, 5],
+    &[1, 10, 2, 4, 5, 9],
+    &[0, 3, 8, 1, 10, 2, 4, 5, 9],
+    &[0, 4, 5, 0, 5, 10, 0, 10, 2],
+    &[2, 3, 8, 2, 8, 4, 2, 4, 5, 2, 5, 10],
+    &[2, 11, 3, 4, 5, 9],
+    &[0, 2, 11, 0, 11, 8, 4, 5, 9],
+    &[0, 4, 5, 0, 5, 1, 2, 11, 3],
+    &[1, 2, 11, 1, 11, 8, 1, 8, 4, 1, 4, 5],
+    &[1, 10, 11, 1, 11, 3, 4, 5, 9],
+    &[0, 1, 10, 0, 10, 11, 0, 11, 8, 4, 5, 9],
+    &[0, 4, 5, 0, 5, 10, 0, 10, 11, 0, 11, 3],
+    &[4, 5, 10, 4, 10, 11, 4, 11, 8],
+    &[5, 9, 8, 5, 8, 7],
+    &[0, 3, 7, 0, 7, 5, 0, 5, 9],
+    &[0, 8, 7, 0, 7, 5, 0, 5, 1],
+    &[1, 3, 7, 1, 7, 5],
+    &[1, 10, 2, 5, 9, 8, 5, 8, 7],
+    &[0, 3, 7, 0, 7, 5, 0, 5, 9, 1, 10, 2],
+    &[0, 8, 7, 0, 7, 5, 0, 5, 10, 0, 10, 2],
+    &[2, 3, 7, 2, 7, 5, 2, 5, 10],
+    &[2, 11, 3, 5, 9, 8, 5, 8, 7],
+    &[0, 2, 11, 0, 11, 7, 0, 7, 5, 0, 5, 9],
+    &[0, 8, 7, 0, 7, 5, 0, 5, 1, 2, 11, 3],
+    &[1, 2, 11, 1, 11, 7, 1, 7, 5],
+    &[1, 10, 11, 1, 11, 3, 5, 9, 8, 5, 8, 7],
+    &[0, 1, 10, 0, 10, 11, 0, 11, 7, 0, 7, 5, 0, 5, 9],
+    &[0, 8, 7, 0, 7, 5, 0, 5, 10, 0, 10, 11, 0, 11, 3],
+    &[5, 10, 11, 5, 11, 7],
+    &[5, 6, 10],
+    &[0, 3, 8, 5, 6, 10],
+    &[0, 9, 1, 5, 6, 10],
+    &[1, 3, 8, 1, 8, 9, 5, 6, 10],
+    &[1, 5, 6, 1, 6, 2],
+    &[0, 3, 8, 1, 5, 6, 1, 6, 2],
+    &[0, 9, 5, 0, 5, 6, 0, 6, 2],
+    &[2, 3, 8, 2, 8, 9, 2, 9, 5, 2, 5, 6],
+    &[2, 11, 3, 5, 6, 10],
+    &[0, 2, 11, 0, 11, 8, 5, 6, 10],
+    &[0, 9, 1, 2, 11, 3, 5, 6, 10],
+    &[1, 2, 11, 1, 11, 8, 1, 8, 9, 5, 6, 10],
+    &[1, 5, 6, 1, 6, 11, 1, 11, 3],
+    &[0, 1, 5, 0, 5, 6, 0, 6, 11, 0, 11, 8],
+    &[0, 9, 5, 0, 5, 6, 0, 6, 11, 0, 11, 3],
+    &[5, 6, 11, 5, 11, 8, 5, 8, 9],
+    &[4, 8, 7, 5, 6, 10],
+    &[0, 3, 7, 0, 7, 4, 5, 6, 10],
+    &[0, 9, 1, 4, 8, 7, 5, 6, 10],
+    &[1, 3, 7, 1, 7, 4, 1, 4, 9, 5, 6, 10],
+    &[1, 5, 6, 1, 6, 2, 4, 8, 7],
+    &[0, 3, 7, 0, 7, 4, 1, 5, 6, 1, 6, 2],
+    &[0, 9, 5, 0, 5, 6, 0, 6, 2, 4, 8, 7],
+    &[2, 3, 7, 2, 7, 4, 2, 4, 9, 2, 9, 5, 2, 5, 6],
+    &[2, 11, 3, 4, 8, 7, 5, 6, 10],
+    &[0, 2, 11, 0, 11, 7, 0, 7, 4, 5, 6, 10],
+    &[0, 9, 1, 2, 11, 3, 4, 8, 7, 5, 6, 10],
+    &[1, 2, 11, 1, 11, 7, 1, 7, 4, 1, 4, 9, 5, 6, 10],
+    &[1, 5, 6, 1, 6, 11, 1, 11, 3, 4, 8, 7],
+    &[0, 1, 5, 0, 5, 6, 0, 6, 11, 0, 11, 7, 0, 7, 4],
+    &[0, 9, 5, 0, 5, 6, 0, 6, 11, 0, 11, 3, 4, 8, 7],
+    &[4, 9, 11, 9, 5, 6, 9, 6, 11, 4, 11, 7],
+    &[4, 6, 10, 4, 10, 9],
+    &[0, 3, 8, 4, 6, 10, 4, 10, 9],
+    &[0, 4, 6, 0, 6, 10, 0, 10, 1],
+    &[1, 3, 8, 1, 8, 4, 1, 4, 6, 1, 6, 10],
+    &[1, 9, 4, 1, 4, 6, 1, 6, 2],
+    &[0, 3, 8, 1, 9, 4, 1, 4, 6, 1, 6, 2],
+    &[0, 4, 6, 0, 6, 2],
+    &[2, 3, 8, 2, 8, 4, 2, 4, 6],
+    &[2, 11, 3, 4, 6, 10, 4, 10, 9],
+    &[0, 2, 11, 0, 11, 8, 4, 6, 10, 4, 10, 9],
+    &[0, 4, 6, 0, 6, 10, 0, 10, 1, 2, 11, 3],
+    &[1, 2, 11, 1, 11, 8, 1, 8, 4, 1, 4, 6, 1, 6, 10],
+    &[1, 9, 4, 1, 4, 6, 1, 6, 11, 1, 11, 3],
+    &[0, 1, 6, 1, 9, 4, 1, 4, 6, 0, 6, 11, 0, 11, 8],
+    &[0, 4, 6, 0, 6, 11, 0, 11, 3],
+    &[4, 6, 11, 4, 11, 8],
+    &[6, 10, 9, 6, 9, 8, 6, 8, 7],
+    &[0, 3, 7, 0, 7, 6, 0, 6, 10, 0, 10, 9],
+    &[0, 8, 7, 0, 7, 6, 0, 6, 10, 0, 10, 1],
+    &[1, 3, 7, 1, 7, 6, 1, 6, 10],
+    &[1, 9, 8, 1, 8, 7, 1, 7, 6, 1, 6, 2],
+    &[0, 3, 7, 0, 7, 6, 0, 6, 9, 6, 2, 1, 6, 1, 9],
+    &[0, 8, 7, 0, 7, 6, 0, 6, 2],
+    &[2, 3, 7, 2, 7, 6],
+    &[2, 11, 3, 6, 10, 9, 6, 9, 8, 6, 8, 7],
+    &[0, 2, 11, 0, 11, 7, 0, 7, 6, 0, 6, 10, 0, 10, 9],
+    &[0, 8, 7, 0, 7, 6, 0, 6, 10, 0, 10, 1, 2, 11, 3],
+    &[1, 2, 11, 1, 11, 7, 1, 7, 6, 1, 6, 10],
+    &[1, 9, 8, 1, 8, 7, 1, 7, 6, 1, 6, 11, 1, 11, 3],
+    &[0, 1, 9, 6, 11, 7],
+    &[0, 8, 7, 0, 7, 6, 0, 6, 11, 0, 11, 3],
+    &[6, 11, 7],
+    &[6, 7, 11],
+    &[0, 3, 8, 6, 7, 11],
+    &[0, 9, 1, 6, 7, 11],
+    &[1, 3, 8, 1, 8, 9, 6, 7, 11],
+    &[1, 10, 2, 6, 7, 11],
+    &[0, 3, 8, 1, 10, 2, 6, 7, 11],
+    &[0, 9, 10, 0, 10, 2, 6, 7, 11],
+    &[2, 3, 8, 2, 8, 9, 2, 9, 10, 6, 7, 11],
+    &[2, 6, 7, 2, 7, 3],
+    &[0, 2, 6, 0, 6, 7, 0, 7, 8],
+    &[0, 9, 1, 2, 6, 7, 2, 7, 3],
+    &[1, 2, 6, 1, 6, 7, 1, 7, 8, 1, 8, 9],
+    &[1, 10, 6, 1, 6, 7, 1, 7, 3],
+    &[0, 1, 10, 0, 10, 6, 0, 6, 7, 0, 7, 8],
+    &[0, 9, 10, 0, 10, 6, 0, 6, 7, 0, 7, 3],
+    &[6, 7, 8, 6, 8, 9, 6, 9, 10],
+    &[4, 8, 11, 4, 11, 6],
+    &[0, 3, 11, 0, 11, 6, 0, 6, 4],
+    &[0, 9, 1, 4, 8, 11, 4, 11, 6],
+    &[1, 3, 11, 1, 11, 6, 1, 6, 4, 1, 4, 9],
+    &[1, 10, 2, 4, 8, 11, 4, 11, 6],
+    &[0, 3, 11, 0, 11, 6, 0, 6, 4, 1, 10, 2],
+    &[0, 9, 10, 0, 10, 2, 4, 8, 11, 4, 11, 6],
+    &[2, 3, 4, 3, 11, 6, 3, 6, 4, 2, 4, 9, 2, 9, 10],
+    &[2, 6, 4, 2, 4, 8, 2, 8, 3],
+    &[0, 2, 6, 0, 6, 4],
+    &[0, 9, 1, 2, 6, 4, 2, 4, 8, 2, 8, 3],
+    &[1, 2, 6, 1, 6, 4, 1, 4, 9],
+    &[1, 10, 6, 1, 6, 4, 1, 4, 8, 1, 8, 3],
+    &[0, 1, 10, 0, 10, 6, 0, 6, 4],
+    &[0, 9, 10, 0, 10, 6, 0, 6, 3, 6, 4, 8, 6, 8, 3],
+    &[4, 9, 10, 4, 10, 6],
+    &[4, 5, 9, 6, 7, 11],
+    &[0, 3, 8, 4, 5, 9, 6, 7, 11],
+    &[0, 4, 5, 0, 5, 1, 6, 7, 11],
+    &[1, 3, 8, 1, 8, 4, 1, 4, 5, 6, 7, 11],
+    &[1, 10, 2, 4, 5, 9, 6, 7, 11],
+    &[0, 3, 8, 1, 10, 2, 4, 5, 9, 6, 7, 11],
+    &[0, 4, 5, 0, 5, 10, 0, 10, 2, 6, 7, 11],
+    &[2, 3, 8, 2, 8, 4, 2, 4, 5, 2, 5, 10, 6, 7, 11],
+    &[2, 6, 7, 2, 7, 3, 4, 5, 9],
+    &[0, 2, 6, 0, 6, 7, 0, 7, 8, 4, 5, 9],
+    &[0, 4, 5, 0, 5, 1, 2, 6, 7, 2, 7, 3],
+    &[1, 2, 6, 1, 6, 7, 1, 7, 8, 1, 8, 4, 1, 4, 5],
+    &[1, 10, 6, 1, 6, 7, 1, 7, 3, 4, 5, 9],
+    &[0, 1, 10, 0, 10, 6, 0, 6, 7, 0, 7, 8, 4, 5, 9],
+    &[0, 4, 5, 0, 5, 10, 0, 10, 6, 0, 6, 7, 0, 7, 3],
+    &[4, 5, 10, 4, 10, 8, 10, 6, 7, 10, 7, 8],
+    &[5, 9, 8, 5, 8, 11, 5, 11, 6],
+    &[0, 3, 11, 0, 11, 6, 0, 6, 5, 0, 5, 9],
+    &[0, 8, 11, 0, 11, 6, 0, 6, 5, 0, 5, 1],
+    &[1, 3, 11, 1, 11, 6, 1, 6, 5],
+    &[1, 10, 2, 5, 9, 8, 5, 8, 11, 5, 11, 6],
+    &[0, 3, 11, 0, 11, 6, 0, 6, 5, 0, 5, 9, 1, 10, 2],
+    &[0, 8, 11, 0, 11, 6, 0, 6, 5, 0, 5, 10, 0, 10, 2],
+    &[2, 3, 5, 3, 11, 6, 3, 6, 5, 2, 5, 10],
+    &[2, 6, 5, 2, 5, 9, 2, 9, 8, 2, 8, 3],
+    &[0, 2, 6, 0, 6, 5, 0, 5, 9],
+    &[0, 8, 6, 8, 3, 2, 8, 2, 6, 0, 6, 5, 0, 5, 1],
+    &[1, 2, 6, 1, 6, 5],
+    &[1, 10, 6, 1, 6, 8, 6, 5, 9, 6, 9, 8, 1, 8, 3],
+    &[0, 1, 10, 0, 10, 6, 0, 6, 5, 0, 5, 9],
+    &[0, 8, 3, 5, 10, 6],
+    &[5, 10, 6],
+    &[5, 7, 11, 5, 11, 10],
+    &[0, 3, 8, 5, 7, 11, 5, 11, 10],
+    &[0, 9, 1, 5, 7, 11, 5, 11, 10],
+    &[1, 3, 8, 1, 8, 9, 5, 7, 11, 5, 11, 10],
+    &[1, 5, 7, 1, 7, 11, 1, 11, 2],
+    &[0, 3, 8, 1, 5, 7, 1, 7, 11, 1, 11, 2],
+    &[0, 9, 5, 0, 5, 7, 0, 7, 11, 0, 11, 2],
+    &[2, 3, 8, 2, 8, 9, 2, 9, 5, 2, 5, 7, 2, 7, 11],
+    &[2, 10, 5, 2, 5, 7, 2, 7, 3],
+    &[0, 2, 10, 0, 10, 5, 0, 5, 7, 0, 7, 8],
+    &[0, 9, 1, 2, 10, 5, 2, 5, 7, 2, 7, 3],
+    &[1, 2, 7, 2, 10, 5, 2, 5, 7, 1, 7, 8, 1, 8, 9],
+    &[1, 5, 7, 1, 7, 3],
+    &[0, 1, 5, 0, 5, 7, 0, 7, 8],
+    &[0, 9, 5, 0, 5, 7, 0, 7, 3],
+    &[5, 7, 8, 5, 8, 9],
+    &[4, 8, 11, 4, 11, 10, 4, 10, 5],
+    &[0, 3, 11, 0, 11, 10, 0, 10, 5, 0, 5, 4],
+    &[0, 9, 1, 4, 8, 11, 4, 11, 10, 4, 10, 5],
+    &[1, 3, 11, 1, 11, 4, 11, 10, 5, 11, 5, 4, 1, 4, 9],
+    &[1, 5, 4, 1, 4, 8, 1, 8, 11, 1, 11, 2],
+    &[0, 3, 11, 0, 11, 5, 11, 2, 1, 11, 1, 5, 0, 5, 4],
+    &[0, 9, 5, 0, 5, 11, 5, 4, 8, 5, 8, 11, 0, 11, 2],
+    &[2, 3, 11, 4, 9, 5],
+    &[2, 10, 5, 2, 5, 4, 2, 4, 8, 2, 8, 3],
+    &[0, 2, 10, 0, 10, 5, 0, 5, 4],
+    &[0, 9, 1, 2, 10, 5, 2, 5, 4, 2, 4, 8, 2, 8, 3],
+    &[1, 2, 4, 2, 10, 5, 2, 5, 4, 1, 4, 9],
+    &[1, 5, 4, 1, 4, 8, 1, 8, 3],
+    &[0, 1, 5, 0, 5, 4],
+    &[0, 9, 5, 0, 5, 3, 5, 4, 8, 5, 8, 3],
+    &[4, 9, 5],
+    &[4, 7, 11, 4, 11, 10, 4, 10, 9],
+    &[0, 3, 8, 4, 7, 11, 4, 11, 10, 4, 10, 9],
+    &[0, 4, 7, 0, 7, 11, 0, 11, 10, 0, 10, 1],
+    &[1, 3, 8, 1, 8, 4, 1, 4, 7, 1, 7, 11, 1, 11, 10],
+    &[1, 9, 4, 1, 4, 7, 1, 7, 11, 1, 11, 2],
+    &[0, 3, 8, 1, 9, 4, 1, 4, 7, 1, 7, 11, 1, 11, 2],
+    &[0, 4, 7, 0, 7, 11, 0, 11, 2],
+    &[2, 3, 8, 2, 8, 4, 2, 4, 7, 2, 7, 11],
+    &[2, 10, 9, 2, 9, 4, 2, 4, 7, 2, 7, 3],
+    &[0, 2, 10, 0, 10, 7, 10, 9, 4, 10, 4, 7, 0, 7, 8],
+    &[0, 4, 7, 0, 7, 10, 7, 3, 2, 7, 2, 10, 0, 10, 1],
+    &[1, 2, 10, 4, 7, 8],
+    &[1, 9, 4, 1, 4, 7, 1, 7, 3],
+    &[0, 1, 7, 1, 9, 4, 1, 4, 7, 0, 7, 8],
+    &[0, 4, 7, 0, 7, 3],
+    &[4, 7, 8],
+    &[8, 11, 10, 8, 10, 9],
+    &[0, 3, 11, 0, 11, 10, 0, 10, 9],
+    &[0, 8, 11, 0, 11, 10, 0, 10, 1],
+    &[1, 3, 11, 1, 11, 10],
+    &[1, 9, 8, 1, 8, 11, 1, 11, 2],
+    &[0, 3, 11, 0, 11, 9, 11, 2, 1, 11, 1, 9],
+    &[0, 8, 11, 0, 11, 2],
+    &[2, 3, 11],
+    &[2, 10, 9, 2, 9, 8, 2, 8, 3],
+    &[0, 2, 10, 0, 10, 9],
+    &[0, 8, 10, 8, 3, 2, 8, 2, 10, 0, 10, 1],
+    &[1, 2, 10],
+    &[1, 9, 8, 1, 8, 3],
+    &[0, 1, 9],
+    &[0, 8, 3],
+    &[],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interior_edges_are_shared_by_two_triangles() {
+        let size = 12;
+        let mut seed = 0x2545_F491_4F6C_DD1Du64;
+        for _ in 0..200 {
+            let data = (0..size * size * size)
+                .map(|_| {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    (seed >> 56) as u8
+                })
+                .collect();
+            let volume = CpuVolume {
+                voxels: CpuTexture3D {
+                    data: TextureData::RU8(data),
+                    width: size,
+                    height: size,
+                    depth: size,
+                    ..Default::default()
+                },
+                size: vec3(1.0, 1.0, 1.0),
+                ..Default::default()
+            };
+            let mesh = volume.marching_cubes(0.5).unwrap();
+            let positions = match mesh.positions {
+                Positions::F32(positions) => positions,
+                _ => unreachable!(),
+            };
+            let indices = mesh.indices.unwrap().to_u32();
+
+            let mut edges = HashMap::new();
+            for triangle in indices.chunks_exact(3) {
+                for i in 0..3 {
+                    let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                    *edges.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+                }
+            }
+            // Edges on the boundary of the volume, where the surface is cut, lie in the plane through the outermost voxel centers
+            let limit = 0.5 - 0.5 / size as f32;
+            let on_boundary = |a: Vec3, b: Vec3| {
+                (0..3).any(|i| (a[i] - b[i]).abs() < 1e-5 && (a[i].abs() - limit).abs() < 1e-5)
+            };
+            for ((a, b), count) in edges {
+                let (a, b) = (positions[a as usize], positions[b as usize]);
+                if on_boundary(a, b) {
+                    assert!(count <= 2);
+                } else {
+                    assert_eq!(count, 2);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use crate::core::*;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+///
+/// Counters for the render calls made using a [Context], see [Context::render_statistics].
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct RenderStatistics {
+    /// The number of draw calls.
+    pub draw_calls: u64,
+    /// The number of triangles drawn, including all instances.
+    pub triangles: u64,
+    /// The number of draw calls using a different [Program] than the previous draw call.
+    pub program_changes: u64,
+    /// The number of draw calls using different [RenderStates] than the previous draw call.
+    pub render_state_changes: u64,
+}
+
+// Saturates at zero, since the statistics can be reset between the two measurements (see [Context::reset_render_statistics])
+impl std::ops::Sub for RenderStatistics {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self {
+            draw_calls: self.draw_calls.saturating_sub(other.draw_calls),
+            triangles: self.triangles.saturating_sub(other.triangles),
+            program_changes: self.program_changes.saturating_sub(other.program_changes),
+            render_state_changes: self
+                .render_state_changes
+                .saturating_sub(other.render_state_changes),
+        }
+    }
+}
+
+///
+/// The measurements for one pass in a [ProfiledFrame].
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfiledPass {
+    /// The name of the pass given to [Profiler::pass].
+    pub name: String,
+    /// The number of passes this pass is nested inside.
+    pub depth: usize,
+    /// The GPU time in milliseconds, including nested passes, or `None` if timer queries are not supported or the measurement is invalidated by a disjoint event (see [TimerQuery::elapsed]).
+    pub gpu_time: Option<f64>,
+    /// The render calls made in the pass, including nested passes.
+    pub statistics: RenderStatistics,
+}
+
+///
+/// The measurements for one frame, see [Profiler::last_frame].
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProfiledFrame {
+    /// The GPU time in milliseconds for all passes in the frame, or `None` if timer queries are not supported or the measurement is invalidated by a disjoint event (see [TimerQuery::elapsed]).
+    pub gpu_time: Option<f64>,
+    /// All render calls made in the frame, including the render calls outside of the passes.
+    pub statistics: RenderStatistics,
+    /// The passes in the order they were started, where nested passes follow the pass they are nested inside.
+    pub passes: Vec<ProfiledPass>,
+}
+
+struct PendingFrame {
+    frame: ProfiledFrame,
+    // The timer queries and the index of the pass each of them measured a part of
+    segments: Vec<(TimerQuery, usize)>,
+}
+
+#[derive(Default)]
+struct ProfilerState {
+    current: Option<PendingFrame>,
+    frame_start: RenderStatistics,
+    // The index of each of the currently active passes and the statistics when it started
+    stack: Vec<(usize, RenderStatistics)>,
+    // Whether the last timer query in the current frame is measuring
+    measuring: bool,
+    pending: VecDeque<PendingFrame>,
+    unused_queries: Vec<TimerQuery>,
+    last_frame: Option<ProfiledFrame>,
+}
+
+///
+/// A frame profiler which measures the GPU time and counts the render calls (see [RenderStatistics]) for named passes, for example the shadow pass, the geometry pass and the lighting pass.
+/// Call [Profiler::begin_frame] at the start of each frame, wrap each pass in a call to [Profiler::pass] and call [Profiler::end_frame] at the end of the frame.
+/// Passes can be nested, in which case the measurements of a pass include the nested passes.
+///
+/// The GPU time is measured using [TimerQuery] if supported. Since the GPU results are not available until a later frame,
+/// [Profiler::last_frame] returns the last frame for which all the results are available, which is usually one or two frames behind.
+///
+pub struct Profiler {
+    context: Context,
+    state: RefCell<ProfilerState>,
+}
+
+impl Profiler {
+    ///
+    /// The maximum number of frames waiting for the GPU results. If more frames are waiting, the oldest is discarded.
+    ///
+    const MAX_PENDING_FRAMES: usize = 5;
+
+    ///
+    /// Creates a new profiler.
+    ///
+    pub fn new(context: &Context) -> Self {
+        Self {
+            context: context.clone(),
+            state: RefCell::new(ProfilerState::default()),
+        }
+    }
+
+    ///
+    /// Starts profiling a new frame.
+    ///
+    pub fn begin_frame(&self) {
+        self.end_frame();
+        let mut state = self.state.borrow_mut();
+        state.frame_start = self.context.render_statistics();
+        state.current = Some(PendingFrame {
+            frame: ProfiledFrame::default(),
+            segments: Vec::new(),
+        });
+    }
+
+    ///
+    /// Ends profiling of the current frame, if any.
+    ///
+    pub fn end_frame(&self) {
+        let mut state = self.state.borrow_mut();
+        while !state.stack.is_empty() {
+            Self::end_pass(&self.context, &mut state);
+        }
+        if let Some(mut pending) = state.current.take() {
+            pending.frame.statistics = self.context.render_statistics() - state.frame_start;
+            state.pending.push_back(pending);
+            if state.pending.len() > Self::MAX_PENDING_FRAMES {
+                let discarded = state.pending.pop_front().unwrap();
+                state
+                    .unused_queries
+                    .extend(discarded.segments.into_iter().map(|(q, _)| q));
+            }
+        }
+        Self::resolve(&mut state);
+    }
+
+    ///
+    /// Measures the render calls made in the callback as a pass with the given name.
+    /// Does nothing except calling the callback if not called between [Profiler::begin_frame] and [Profiler::end_frame].
+    ///
+    pub fn pass<T>(
+        &self,
+        name: &str,
+        callback: impl FnOnce() -> ThreeDResult<T>,
+    ) -> ThreeDResult<T> {
+        if self.state.borrow().current.is_none() {
+            return callback();
+        }
+        self.begin_pass(name);
+        let result = callback();
+        Self::end_pass(&self.context, &mut self.state.borrow_mut());
+        result
+    }
+
+    ///
+    /// Returns the measurements of the last frame for which all the GPU results are available.
+    ///
+    pub fn last_frame(&self) -> Option<ProfiledFrame> {
+        self.state.borrow().last_frame.clone()
+    }
+
+    fn begin_pass(&self, name: &str) {
+        let mut state = self.state.borrow_mut();
+        let statistics = self.context.render_statistics();
+        let depth = state.stack.len();
+        let current = state.current.as_mut().unwrap();
+        current.frame.passes.push(ProfiledPass {
+            name: name.to_string(),
+            depth,
+            gpu_time: None,
+            statistics: RenderStatistics::default(),
+        });
+        let index = current.frame.passes.len() - 1;
+        // Only one timer query can measure at a time, so the measurement of the parent pass is split around this pass
+        Self::end_segment(&mut state);
+        state.stack.push((index, statistics));
+        Self::begin_segment(&self.context, &mut state, index);
+    }
+
+    fn end_pass(context: &Context, state: &mut ProfilerState) {
+        let (index, statistics) = state.stack.pop().unwrap();
+        state.current.as_mut().unwrap().frame.passes[index].statistics =
+            context.render_statistics() - statistics;
+        Self::end_segment(state);
+        if let Some((parent, _)) = state.stack.last().copied() {
+            Self::begin_segment(context, state, parent);
+        }
+    }
+
+    fn begin_segment(context: &Context, state: &mut ProfilerState, index: usize) {
+        if !context.supports_timer_queries() {
+            return;
+        }
+        let query = match state.unused_queries.pop() {
+            Some(query) => Some(query),
+            None => TimerQuery::new(context).ok(),
+        };
+        if let Some(mut query) = query {
+            query.begin();
+            state
+                .current
+                .as_mut()
+                .unwrap()
+                .segments
+                .push((query, index));
+            state.measuring = true;
+        }
+    }
+
+    fn end_segment(state: &mut ProfilerState) {
+        if state.measuring {
+            if let Some((query, _)) = state.current.as_ref().unwrap().segments.last() {
+                query.end();
+            }
+            state.measuring = false;
+        }
+    }
+
+    fn resolve(state: &mut ProfilerState) {
+        while let Some(pending) = state.pending.front() {
+            let mut times = vec![0.0; pending.frame.passes.len()];
+            let mut available = true;
+            let mut discarded = false;
+            for (query, index) in pending.segments.iter() {
+                match query.poll() {
+                    Some(Some(time)) => times[*index] += time,
+                    Some(None) => discarded = true,
+                    None => {
+                        available = false;
+                        break;
+                    }
+                }
+            }
+            if !available {
+                break;
+            }
+            let mut pending = state.pending.pop_front().unwrap();
+            // The GPU times are left out if one of the measurements is invalidated by a disjoint event
+            if !discarded && !pending.segments.is_empty() {
+                // Add the time of each nested pass to the pass it is nested inside
+                let passes = &mut pending.frame.passes;
+                for i in (0..passes.len()).rev() {
+                    if let Some(parent) = (0..i).rev().find(|j| passes[*j].depth < passes[i].depth)
+                    {
+                        if passes[parent].depth + 1 == passes[i].depth {
+                            times[parent] += times[i];
+                        }
+                    }
+                }
+                for (pass, time) in passes.iter_mut().zip(times.iter()) {
+                    pass.gpu_time = Some(*time);
+                }
+                pending.frame.gpu_time = Some(
+                    passes
+                        .iter()
+                        .zip(times.iter())
+                        .filter(|(pass, _)| pass.depth == 0)
+                        .map(|(_, time)| *time)
+                        .sum(),
+                );
+            }
+            state
+                .unused_queries
+                .extend(pending.segments.into_iter().map(|(q, _)| q));
+            state.last_frame = Some(pending.frame);
+        }
+    }
+}
@@ -462,7 +462,8 @@ impl Program {
             buffer.bind();
             let loc = self.location(name)?;
             unsafe {
-                self.context.bind_vertex_array(Some(self.context.vao));
+                self.context
+                    .bind_vertex_array(Some(self.context.vertex_array()));
                 self.context.enable_vertex_attrib_array(loc);
                 self.context.vertex_attrib_pointer_f32(
                     loc,
@@ -494,7 +495,8 @@ impl Program {
             buffer.bind();
             let loc = self.location(name)?;
             unsafe {
-                self.context.bind_vertex_array(Some(self.context.vao));
+                self.context
+                    .bind_vertex_array(Some(self.context.vertex_array()));
                 self.context.enable_vertex_attrib_array(loc);
                 self.context.vertex_attrib_pointer_f32(
                     loc,
@@ -526,6 +528,8 @@ impl Program {
     ) -> ThreeDResult<()> {
         self.context.set_viewport(viewport);
         self.context.set_render_states(render_states)?;
+        self.context
+            .count_draw_call(&self.id, render_states, count as u64 / 3);
         self.use_program();
         unsafe {
             self.context
@@ -552,6 +556,11 @@ impl Program {
     ) -> ThreeDResult<()> {
         self.context.set_viewport(viewport);
         self.context.set_render_states(render_states)?;
+        self.context.count_draw_call(
+            &self.id,
+            render_states,
+            count as u64 / 3 * instance_count as u64,
+        );
         self.use_program();
         unsafe {
             self.context.draw_arrays_instanced(
@@ -606,6 +615,8 @@ impl Program {
     ) -> ThreeDResult<()> {
         self.context.set_viewport(viewport);
         self.context.set_render_states(render_states)?;
+        self.context
+            .count_draw_call(&self.id, render_states, count as u64 / 3);
         self.use_program();
         element_buffer.bind();
         unsafe {
@@ -663,6 +674,11 @@ impl Program {
     ) -> ThreeDResult<()> {
         self.context.set_viewport(viewport);
         self.context.set_render_states(render_states)?;
+        self.context.count_draw_call(
+            &self.id,
+            render_states,
+            count as u64 / 3 * instance_count as u64,
+        );
         self.use_program();
         element_buffer.bind();
         unsafe {
@@ -684,6 +700,15 @@ impl Program {
         self.context.error_check()
     }
 
+    ///
+    /// Sends the clip planes set on the context (see [Context::set_clip_planes]) to this program, together with the inverse view projection matrix of the given camera,
+    /// which is used to compute the position of each fragment in world space.
+    /// Does nothing if the program was created without clip planes.
+    ///
+    pub fn use_clip_planes(&self, camera: &Camera) -> ThreeDResult<()> {
+        self.context.clip_planes().use_uniforms(self, camera)
+    }
+
     ///
     /// Returns true if this program uses the uniform with the given name.
     ///
@@ -707,6 +732,20 @@ impl Program {
         Ok(*location)
     }
 
+    ///
+    /// Sets the label of this program, which is shown in graphics debuggers and debug messages, see [Context::set_debug_output].
+    /// Requires the `KHR_debug` extension, otherwise this does nothing.
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.context.set_object_label(
+            crate::context::PROGRAM,
+            crate::context::CURRENT_PROGRAM,
+            || self.use_program(),
+            label,
+        );
+        self.unuse_program();
+    }
+
     fn use_program(&self) {
         unsafe {
             self.context.use_program(Some(self.id));
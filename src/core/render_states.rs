@@ -5,7 +5,7 @@
 ///
 /// A set of render specific states that has to be specified at each render call.
 ///
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct RenderStates {
     ///
     /// Defines which channels (red, green, blue, alpha and depth) to write to in a render call.
@@ -31,6 +31,13 @@ pub struct RenderStates {
     /// Defines whether the triangles that are backfacing, frontfacing or both should be skipped in a render call.
     ///
     pub cull: Cull,
+
+    ///
+    /// Defines the stencil test and how the stencil buffer is updated in a render call.
+    /// The stencil test determines whether or not a fragment from the current render call should be discarded
+    /// based on the value in the stencil buffer, which for example can be used to mask out parts of the render target.
+    ///
+    pub stencil: Stencil,
 }
 
 impl Default for RenderStates {
@@ -40,6 +47,7 @@ impl Default for RenderStates {
             depth_test: DepthTest::default(),
             blend: Blend::default(),
             cull: Cull::default(),
+            stencil: Stencil::default(),
         }
     }
 }
@@ -90,6 +98,136 @@ impl Default for DepthTest {
     }
 }
 
+///
+/// Defines the stencil test and how the stencil buffer is updated in a render call, see [RenderStates::stencil].
+///
+/// **Note:** The stencil test only has an effect if the render call is writing to a render target with a stencil buffer,
+/// for example a depth target with the [DepthFormat::Depth24Stencil8](crate::core::DepthFormat::Depth24Stencil8) format or the screen.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Stencil {
+    /// The stencil test is enabled with separate settings for front- and backfacing triangles.
+    Enabled {
+        /// The stencil settings for frontfacing triangles.
+        front: StencilFace,
+        /// The stencil settings for backfacing triangles.
+        back: StencilFace,
+    },
+    /// The stencil test is disabled and the stencil buffer is not changed.
+    #[default]
+    Disabled,
+}
+
+impl Stencil {
+    ///
+    /// Enables the stencil test with the same settings for both front- and backfacing triangles.
+    ///
+    pub const fn both(face: StencilFace) -> Self {
+        Self::Enabled {
+            front: face,
+            back: face,
+        }
+    }
+}
+
+///
+/// The stencil test and stencil operations for either front- or backfacing triangles, see [Stencil].
+/// The stencil test compares the reference value with the value in the stencil buffer, both masked by the read mask,
+/// and depending on the result of the stencil test and the depth test, one of the operations are applied to the value in the stencil buffer.
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StencilFace {
+    /// The test which compares the reference value (left hand side) to the value in the stencil buffer (right hand side).
+    pub test: StencilTest,
+    /// The reference value used in the stencil test and by the [StencilOperation::Replace] operation.
+    pub reference: u8,
+    /// The bits of the reference value and the value in the stencil buffer that are compared in the stencil test.
+    pub read_mask: u8,
+    /// The bits of the stencil buffer that can be changed by the stencil operations.
+    pub write_mask: u8,
+    /// The operation applied when the stencil test fails.
+    pub fail: StencilOperation,
+    /// The operation applied when the stencil test passes but the depth test fails.
+    pub depth_fail: StencilOperation,
+    /// The operation applied when both the stencil test and the depth test passes.
+    pub pass: StencilOperation,
+}
+
+impl StencilFace {
+    ///
+    /// Always passes the stencil test and writes the reference value to the stencil buffer where the depth test passes.
+    /// This is usually used to mark the pixels covered by an object.
+    ///
+    pub const fn write(reference: u8) -> Self {
+        Self {
+            test: StencilTest::Always,
+            reference,
+            read_mask: 0xFF,
+            write_mask: 0xFF,
+            fail: StencilOperation::Keep,
+            depth_fail: StencilOperation::Keep,
+            pass: StencilOperation::Replace,
+        }
+    }
+
+    ///
+    /// Only passes the stencil test when the given test between the reference value and the value in the stencil buffer succeeds.
+    /// The stencil buffer is not changed.
+    /// This is usually used to only render inside or outside of the pixels marked using [StencilFace::write].
+    ///
+    pub const fn test(test: StencilTest, reference: u8) -> Self {
+        Self {
+            test,
+            reference,
+            read_mask: 0xFF,
+            write_mask: 0x00,
+            fail: StencilOperation::Keep,
+            depth_fail: StencilOperation::Keep,
+            pass: StencilOperation::Keep,
+        }
+    }
+}
+
+///
+/// The test which compares the reference value of a [StencilFace] to the value in the stencil buffer.
+/// If the test fails, the fragment/pixel is discarded.
+///
+#[allow(missing_docs)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StencilTest {
+    Never,
+    Less,
+    Equal,
+    LessOrEqual,
+    Greater,
+    NotEqual,
+    GreaterOrEqual,
+    Always,
+}
+
+///
+/// An operation applied to the value in the stencil buffer, see [StencilFace].
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StencilOperation {
+    /// Keeps the current value.
+    Keep,
+    /// Sets the value to zero.
+    Zero,
+    /// Sets the value to the reference value.
+    Replace,
+    /// Increments the value, but not above the maximum value.
+    Increment,
+    /// Increments the value and wraps to zero when incrementing the maximum value.
+    IncrementWrap,
+    /// Decrements the value, but not below zero.
+    Decrement,
+    /// Decrements the value and wraps to the maximum value when decrementing zero.
+    DecrementWrap,
+    /// Inverts the bits of the value.
+    Invert,
+}
+
 ///
 /// Defines which channels (red, green, blue, alpha and depth) to write to in a render call.
 ///
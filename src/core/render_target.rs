@@ -21,9 +21,9 @@ pub use render_target_cube_map::*;
 use crate::core::*;
 
 ///
-/// Defines which channels (red, green, blue, alpha and depth) to clear when starting to write to a
+/// Defines which channels (red, green, blue, alpha, depth and stencil) to clear when starting to write to a
 /// [RenderTarget] or the [Screen].
-/// If `None` then the channel is not cleared and if `Some(value)` the channel is cleared to that value (the value must be between 0 and 1).
+/// If `None` then the channel is not cleared and if `Some(value)` the channel is cleared to that value (the value must be between 0 and 1 except for the stencil value).
 ///
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct ClearState {
@@ -37,6 +37,9 @@ pub struct ClearState {
     pub alpha: Option<f32>,
     /// Defines the clear value for the depth channel. A value of 1 means a depth value equal to the far plane and 0 means a depth value equal to the near plane.
     pub depth: Option<f32>,
+    /// Defines the clear value for the stencil channel. Only has an effect if the render target has a stencil buffer,
+    /// for example a depth target with the [DepthFormat::Depth24Stencil8] format or the screen.
+    pub stencil: Option<u8>,
 }
 
 impl ClearState {
@@ -50,6 +53,7 @@ impl ClearState {
             blue: None,
             alpha: None,
             depth: None,
+            stencil: None,
         }
     }
 
@@ -63,6 +67,21 @@ impl ClearState {
             blue: None,
             alpha: None,
             depth: Some(depth),
+            stencil: None,
+        }
+    }
+
+    ///
+    /// The stencil will be cleared to the given value.
+    ///
+    pub const fn stencil(stencil: u8) -> Self {
+        Self {
+            red: None,
+            green: None,
+            blue: None,
+            alpha: None,
+            depth: None,
+            stencil: Some(stencil),
         }
     }
 
@@ -76,6 +95,7 @@ impl ClearState {
             blue: Some(blue),
             alpha: Some(alpha),
             depth: None,
+            stencil: None,
         }
     }
 
@@ -89,10 +109,11 @@ impl ClearState {
             blue: Some(blue),
             alpha: Some(alpha),
             depth: Some(depth),
+            stencil: None,
         }
     }
 
-    pub(in crate::core) fn apply(&self, context: &Context) {
+    pub(crate) fn apply(&self, context: &Context) {
         context.set_write_mask(WriteMask {
             red: self.red.is_some(),
             green: self.green.is_some(),
@@ -116,15 +137,21 @@ impl ClearState {
             if let Some(depth) = self.depth {
                 context.clear_depth_f32(depth);
             }
-            context.clear(if clear_color && self.depth.is_some() {
-                crate::context::COLOR_BUFFER_BIT | crate::context::DEPTH_BUFFER_BIT
-            } else {
-                if clear_color {
-                    crate::context::COLOR_BUFFER_BIT
-                } else {
-                    crate::context::DEPTH_BUFFER_BIT
-                }
-            });
+            if let Some(stencil) = self.stencil {
+                context.stencil_mask(0xFF);
+                context.clear_stencil(stencil as i32);
+            }
+            let mut mask = 0;
+            if clear_color {
+                mask |= crate::context::COLOR_BUFFER_BIT;
+            }
+            if self.depth.is_some() {
+                mask |= crate::context::DEPTH_BUFFER_BIT;
+            }
+            if self.stencil.is_some() {
+                mask |= crate::context::STENCIL_BUFFER_BIT;
+            }
+            context.clear(mask);
         }
     }
 }
@@ -95,6 +95,7 @@ impl<'a> ColorTarget<'a> {
             scissor_box,
             ClearState {
                 depth: None,
+                stencil: None,
                 ..clear_state
             },
         )?;
@@ -308,14 +309,14 @@ impl<'a> DepthTarget<'a> {
     }
 
     ///
-    /// Clears the depth of this depth target as defined by the given clear state.
+    /// Clears the depth and stencil of this depth target as defined by the given clear state.
     ///
     pub fn clear(&self, clear_state: ClearState) -> ThreeDResult<&Self> {
         self.clear_partially(self.scissor_box(), clear_state)
     }
 
     ///
-    /// Clears the depth of the part of this depth target that is inside the given scissor box.
+    /// Clears the depth and stencil of the part of this depth target that is inside the given scissor box.
     ///
     pub fn clear_partially(
         &self,
@@ -326,6 +327,7 @@ impl<'a> DepthTarget<'a> {
             scissor_box,
             ClearState {
                 depth: clear_state.depth,
+                stencil: clear_state.stencil,
                 ..ClearState::none()
             },
         )?;
@@ -465,14 +467,14 @@ impl<'a> RenderTarget<'a> {
     }
 
     ///
-    /// Clears the color and depth of this render target as defined by the given clear state.
+    /// Clears the color, depth and stencil of this render target as defined by the given clear state.
     ///
     pub fn clear(&self, clear_state: ClearState) -> ThreeDResult<&Self> {
         self.clear_partially(self.scissor_box(), clear_state)
     }
 
     ///
-    /// Clears the color and depth of the part of this render target that is inside the given scissor box.
+    /// Clears the color, depth and stencil of the part of this render target that is inside the given scissor box.
     ///
     pub fn clear_partially(
         &self,
@@ -114,6 +114,7 @@ impl<'a, 'b> RenderTargetCubeMap<'a, 'b> {
                 blue: self.color_texture.as_ref().and(clear_state.blue),
                 alpha: self.color_texture.as_ref().and(clear_state.alpha),
                 depth: self.depth_texture.as_ref().and(clear_state.depth),
+                stencil: self.depth_texture.as_ref().and(clear_state.stencil),
             },
         );
         render()?;
@@ -29,11 +29,20 @@ mod depth_target_texture_cube_map;
 #[doc(inline)]
 pub use depth_target_texture_cube_map::*;
 
+mod texture_atlas;
+#[doc(inline)]
+pub use texture_atlas::*;
+
+mod compressed;
+#[doc(inline)]
+pub use compressed::*;
+
 ///
 /// Possible modes of interpolation which determines the texture output between texture pixels.
 ///
 #[allow(missing_docs)]
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum Interpolation {
     Nearest,
     Linear,
@@ -45,6 +54,7 @@ pub enum Interpolation {
 ///
 #[allow(missing_docs)]
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum Wrapping {
     Repeat,
     MirroredRepeat,
@@ -97,6 +107,7 @@ impl<T: TextureDataType + ?Sized> TextureDataType for &T {}
 /// ```
 ///
 #[derive(Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureData {
     /// One byte in the red channel.
     RU8(Vec<u8>),
@@ -124,6 +135,21 @@ pub enum TextureData {
     RgbF32(Vec<[f32; 3]>),
     /// 32-bit float in the red, green, blue and alpha channel.
     RgbaF32(Vec<[f32; 4]>),
+
+    ///
+    /// Block compressed data in the given format, see [CompressedFormat], with the bytes of each mip level starting with the largest.
+    /// The blocks are stored one row of blocks at a time, starting with the block in the top left corner.
+    /// If the GPU does not support the format, the data is decoded on the CPU, see [CpuTexture::decompress].
+    ///
+    /// **Note:** Unlike uncompressed data, the blocks cannot be flipped when uploaded to a [Texture2D], so the first row ends up at v = 0 instead of v = 1.
+    /// Only supported by [Texture2D].
+    ///
+    Compressed {
+        /// The format of the blocks.
+        format: CompressedFormat,
+        /// The bytes of each mip level.
+        mip_levels: Vec<Vec<u8>>,
+    },
 }
 
 impl std::fmt::Debug for TextureData {
@@ -141,6 +167,9 @@ impl std::fmt::Debug for TextureData {
             Self::RgF32(values) => write!(f, "RG f32 ({:?})", values.len()),
             Self::RgbF32(values) => write!(f, "RGB f32 ({:?})", values.len()),
             Self::RgbaF32(values) => write!(f, "RGBA f32 ({:?})", values.len()),
+            Self::Compressed { format, mip_levels } => {
+                write!(f, "{:?} ({:?} mip levels)", format, mip_levels.len())
+            }
         }
     }
 }
@@ -150,6 +179,7 @@ impl std::fmt::Debug for TextureData {
 /// Can be constructed manually or loaded via [Loader](crate::Loader).
 ///
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CpuTexture {
     /// The pixel data for the image
     pub data: TextureData,
@@ -189,6 +219,7 @@ impl Default for CpuTexture {
 /// A CPU-side version of a [Texture3D].
 ///
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CpuTexture3D {
     /// The pixel data for the image
     pub data: TextureData,
@@ -234,6 +265,7 @@ impl Default for CpuTexture3D {
 /// The pixel data for a [CpuTextureCube].
 ///
 #[derive(Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureCubeData {
     /// byte in the red channel.
     RU8(Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>),
@@ -330,6 +362,7 @@ pub enum TextureCubeData {
 /// A CPU-side version of a [TextureCubeMap]. All 6 images must have the same dimensions.
 /// Can be constructed manually or loaded via [Loader](crate::Loader).
 ///
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CpuTextureCube {
     /// The pixel data for the cube image
     pub data: TextureCubeData,
@@ -494,6 +527,17 @@ fn internal_format_from_depth(format: DepthFormat) -> u32 {
         DepthFormat::Depth16 => crate::context::DEPTH_COMPONENT16,
         DepthFormat::Depth24 => crate::context::DEPTH_COMPONENT24,
         DepthFormat::Depth32F => crate::context::DEPTH_COMPONENT32F,
+        DepthFormat::Depth24Stencil8 => crate::context::DEPTH24_STENCIL8,
+        DepthFormat::Depth32FStencil8 => crate::context::DEPTH32F_STENCIL8,
+    }
+}
+
+fn depth_attachment_from_format(format: DepthFormat) -> u32 {
+    match format {
+        DepthFormat::Depth24Stencil8 | DepthFormat::Depth32FStencil8 => {
+            crate::context::DEPTH_STENCIL_ATTACHMENT
+        }
+        _ => crate::context::DEPTH_ATTACHMENT,
     }
 }
 
@@ -0,0 +1,1234 @@
+use crate::core::texture::*;
+
+///
+/// The block compressed formats which can be used for [TextureData::Compressed].
+/// All of the formats divide the image into blocks of a fixed number of pixels which are each compressed into a fixed number of bytes.
+///
+/// **Note:** The sRGB variants of the formats are not distinguished from the linear variants,
+/// since the color space conversion is done in the shaders, like for the uncompressed texture data.
+///
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum CompressedFormat {
+    /// BC1 (DXT1) without alpha. Requires the S3TC extension.
+    Bc1Rgb,
+    /// BC1 (DXT1) with 1-bit alpha. Requires the S3TC extension.
+    Bc1Rgba,
+    /// BC2 (DXT3). Requires the S3TC extension.
+    Bc2,
+    /// BC3 (DXT5). Requires the S3TC extension.
+    Bc3,
+    /// BC4 with one channel. Requires the RGTC extension.
+    Bc4,
+    /// BC5 with two channels. Requires the RGTC extension.
+    Bc5,
+    /// BC7. Requires the BPTC extension.
+    Bc7,
+    /// ETC2 without alpha (also decodes ETC1 data). Requires the ETC extension on the web.
+    Etc2Rgb,
+    /// ETC2 with 1-bit (punchthrough) alpha. Requires the ETC extension on the web.
+    Etc2RgbA1,
+    /// ETC2 with an EAC alpha channel. Requires the ETC extension on the web.
+    Etc2Rgba,
+    /// EAC with one channel. Requires the ETC extension on the web.
+    EacR11,
+    /// EAC with two channels. Requires the ETC extension on the web.
+    EacRg11,
+    /// ASTC (LDR profile) with the given block size in pixels. Requires the ASTC extension.
+    Astc {
+        /// The width of a block in pixels.
+        block_width: u8,
+        /// The height of a block in pixels.
+        block_height: u8,
+    },
+}
+
+impl CompressedFormat {
+    ///
+    /// Returns the width and height of a block in pixels.
+    ///
+    pub fn block_size(&self) -> (u32, u32) {
+        match self {
+            Self::Astc {
+                block_width,
+                block_height,
+            } => (*block_width as u32, *block_height as u32),
+            _ => (4, 4),
+        }
+    }
+
+    ///
+    /// Returns the number of bytes in a block.
+    ///
+    pub fn block_byte_size(&self) -> usize {
+        match self {
+            Self::Bc1Rgb
+            | Self::Bc1Rgba
+            | Self::Bc4
+            | Self::Etc2Rgb
+            | Self::Etc2RgbA1
+            | Self::EacR11 => 8,
+            _ => 16,
+        }
+    }
+
+    ///
+    /// Returns the number of bytes of an image with the given size in pixels, for example one mip level.
+    ///
+    pub fn byte_size(&self, width: u32, height: u32) -> usize {
+        let (block_width, block_height) = self.block_size();
+        let blocks_x = width.div_ceil(block_width).max(1);
+        let blocks_y = height.div_ceil(block_height).max(1);
+        blocks_x as usize * blocks_y as usize * self.block_byte_size()
+    }
+
+    ///
+    /// Returns whether or not the GPU supports this format, ie. whether the compressed data can be uploaded directly.
+    /// If not, the data is decoded on the CPU when creating a [Texture2D], see [CpuTexture::decompress].
+    ///
+    pub fn is_supported(&self, context: &Context) -> bool {
+        let suffixes: &[&str] = match self {
+            Self::Bc1Rgb | Self::Bc1Rgba | Self::Bc2 | Self::Bc3 => {
+                &["texture_compression_s3tc", "compressed_texture_s3tc"]
+            }
+            Self::Bc4 | Self::Bc5 => &["texture_compression_rgtc"],
+            Self::Bc7 => &["texture_compression_bptc"],
+            Self::Etc2Rgb | Self::Etc2RgbA1 | Self::Etc2Rgba | Self::EacR11 | Self::EacRg11 => {
+                // Part of OpenGL ES 3.0 and therefore available on desktop through the ES3 compatibility extension
+                &["compressed_texture_etc", "ES3_compatibility"]
+            }
+            Self::Astc { .. } => &["texture_compression_astc_ldr", "compressed_texture_astc"],
+        };
+        context
+            .supported_extensions()
+            .iter()
+            .any(|extension| suffixes.iter().any(|suffix| extension.ends_with(suffix)))
+    }
+
+    pub(in crate::core) fn internal_format(&self) -> ThreeDResult<u32> {
+        Ok(match self {
+            Self::Bc1Rgb => crate::context::COMPRESSED_RGB_S3TC_DXT1_EXT,
+            Self::Bc1Rgba => crate::context::COMPRESSED_RGBA_S3TC_DXT1_EXT,
+            Self::Bc2 => crate::context::COMPRESSED_RGBA_S3TC_DXT3_EXT,
+            Self::Bc3 => crate::context::COMPRESSED_RGBA_S3TC_DXT5_EXT,
+            Self::Bc4 => crate::context::COMPRESSED_RED_RGTC1,
+            Self::Bc5 => crate::context::COMPRESSED_RG_RGTC2,
+            Self::Bc7 => crate::context::COMPRESSED_RGBA_BPTC_UNORM,
+            Self::Etc2Rgb => crate::context::COMPRESSED_RGB8_ETC2,
+            Self::Etc2RgbA1 => crate::context::COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2,
+            Self::Etc2Rgba => crate::context::COMPRESSED_RGBA8_ETC2_EAC,
+            Self::EacR11 => crate::context::COMPRESSED_R11_EAC,
+            Self::EacRg11 => crate::context::COMPRESSED_RG11_EAC,
+            Self::Astc {
+                block_width,
+                block_height,
+            } => match (block_width, block_height) {
+                (4, 4) => crate::context::COMPRESSED_RGBA_ASTC_4x4_KHR,
+                (5, 4) => crate::context::COMPRESSED_RGBA_ASTC_5x4_KHR,
+                (5, 5) => crate::context::COMPRESSED_RGBA_ASTC_5x5_KHR,
+                (6, 5) => crate::context::COMPRESSED_RGBA_ASTC_6x5_KHR,
+                (6, 6) => crate::context::COMPRESSED_RGBA_ASTC_6x6_KHR,
+                (8, 5) => crate::context::COMPRESSED_RGBA_ASTC_8x5_KHR,
+                (8, 6) => crate::context::COMPRESSED_RGBA_ASTC_8x6_KHR,
+                (8, 8) => crate::context::COMPRESSED_RGBA_ASTC_8x8_KHR,
+                (10, 5) => crate::context::COMPRESSED_RGBA_ASTC_10x5_KHR,
+                (10, 6) => crate::context::COMPRESSED_RGBA_ASTC_10x6_KHR,
+                (10, 8) => crate::context::COMPRESSED_RGBA_ASTC_10x8_KHR,
+                (10, 10) => crate::context::COMPRESSED_RGBA_ASTC_10x10_KHR,
+                (12, 10) => crate::context::COMPRESSED_RGBA_ASTC_12x10_KHR,
+                (12, 12) => crate::context::COMPRESSED_RGBA_ASTC_12x12_KHR,
+                _ => Err(CoreError::TextureCreation(format!(
+                    "invalid ASTC block size {}x{}",
+                    block_width, block_height
+                )))?,
+            },
+        })
+    }
+}
+
+impl CpuTexture {
+    ///
+    /// Returns a copy of this texture where [TextureData::Compressed] data is decoded into uncompressed data
+    /// ([TextureData::RU8] for one channel, [TextureData::RgU8] for two channels and otherwise [TextureData::RgbaU8]).
+    /// Only the first mip level is decoded. Uncompressed data is returned unchanged.
+    ///
+    /// # Errors
+    /// Returns an error if the data is too short.
+    ///
+    pub fn decompress(&self) -> ThreeDResult<CpuTexture> {
+        let (format, mip_levels) = match &self.data {
+            TextureData::Compressed { format, mip_levels } => (*format, mip_levels),
+            _ => return Ok(self.clone()),
+        };
+        let bytes = mip_levels
+            .first()
+            .map(|level| level.as_slice())
+            .unwrap_or(&[]);
+        let expected_bytes = format.byte_size(self.width, self.height);
+        if bytes.len() < expected_bytes {
+            Err(CoreError::InvalidTextureLength(bytes.len(), expected_bytes))?;
+        }
+        let (block_width, block_height) = format.block_size();
+        let (block_width, block_height) = (block_width as usize, block_height as usize);
+        let decode_block = |b: &[u8], out: &mut [[u8; 4]]| match format {
+            CompressedFormat::Bc1Rgb => decode_bc1(b, out, false, true),
+            CompressedFormat::Bc1Rgba => decode_bc1(b, out, true, true),
+            CompressedFormat::Bc2 => {
+                decode_bc1(&b[8..], out, false, false);
+                for (i, pixel) in out.iter_mut().enumerate() {
+                    pixel[3] = ((b[i / 2] >> (4 * (i % 2))) & 0xF) * 17;
+                }
+            }
+            CompressedFormat::Bc3 => {
+                decode_bc1(&b[8..], out, false, false);
+                decode_bc4(b, out, 3);
+            }
+            CompressedFormat::Bc4 => decode_bc4(b, out, 0),
+            CompressedFormat::Bc5 => {
+                decode_bc4(b, out, 0);
+                decode_bc4(&b[8..], out, 1);
+            }
+            CompressedFormat::Bc7 => decode_bc7(b, out),
+            CompressedFormat::Etc2Rgb => decode_etc2(b, out, false),
+            CompressedFormat::Etc2RgbA1 => decode_etc2(b, out, true),
+            CompressedFormat::Etc2Rgba => {
+                decode_etc2(&b[8..], out, false);
+                decode_eac(b, out, 3, false);
+            }
+            CompressedFormat::EacR11 => decode_eac(b, out, 0, true),
+            CompressedFormat::EacRg11 => {
+                decode_eac(b, out, 0, true);
+                decode_eac(&b[8..], out, 1, true);
+            }
+            CompressedFormat::Astc { .. } => decode_astc(b, out, block_width, block_height),
+        };
+
+        let width = self.width as usize;
+        let height = self.height as usize;
+        let blocks_x = width.div_ceil(block_width);
+        let mut pixels = vec![[0, 0, 0, 255]; width * height];
+        let mut block = vec![[0, 0, 0, 255]; block_width * block_height];
+        for (i, block_bytes) in bytes[..expected_bytes]
+            .chunks_exact(format.block_byte_size())
+            .enumerate()
+        {
+            decode_block(block_bytes, &mut block);
+            let (block_x, block_y) = (block_width * (i % blocks_x), block_height * (i / blocks_x));
+            for (j, pixel) in block.iter().enumerate() {
+                let (x, y) = (block_x + j % block_width, block_y + j / block_width);
+                if x < width && y < height {
+                    pixels[y * width + x] = *pixel;
+                }
+            }
+        }
+        let data = match format {
+            CompressedFormat::Bc4 | CompressedFormat::EacR11 => {
+                TextureData::RU8(pixels.iter().map(|p| p[0]).collect())
+            }
+            CompressedFormat::Bc5 | CompressedFormat::EacRg11 => {
+                TextureData::RgU8(pixels.iter().map(|p| [p[0], p[1]]).collect())
+            }
+            _ => TextureData::RgbaU8(pixels),
+        };
+        Ok(CpuTexture {
+            data,
+            ..self.clone()
+        })
+    }
+}
+
+// The blocks are decoded into one pixel per texel of the block, one row at a time.
+
+fn decode_bc1(bytes: &[u8], out: &mut [[u8; 4]], has_alpha: bool, allow_three_colors: bool) {
+    let c0 = u16::from_le_bytes([bytes[0], bytes[1]]);
+    let c1 = u16::from_le_bytes([bytes[2], bytes[3]]);
+    let expand = |c: u16| {
+        let r = ((c >> 11) & 31) as u32;
+        let g = ((c >> 5) & 63) as u32;
+        let b = (c & 31) as u32;
+        [
+            (r << 3) | (r >> 2),
+            (g << 2) | (g >> 4),
+            (b << 3) | (b >> 2),
+        ]
+    };
+    let (e0, e1) = (expand(c0), expand(c1));
+    let mix = |w0: u32, w1: u32| {
+        let sum = w0 + w1;
+        [
+            ((w0 * e0[0] + w1 * e1[0]) / sum) as u8,
+            ((w0 * e0[1] + w1 * e1[1]) / sum) as u8,
+            ((w0 * e0[2] + w1 * e1[2]) / sum) as u8,
+            255,
+        ]
+    };
+    let colors = if c0 > c1 || !allow_three_colors {
+        [mix(1, 0), mix(0, 1), mix(2, 1), mix(1, 2)]
+    } else {
+        [
+            mix(1, 0),
+            mix(0, 1),
+            mix(1, 1),
+            [0, 0, 0, if has_alpha { 0 } else { 255 }],
+        ]
+    };
+    let indices = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    for (i, pixel) in out.iter_mut().enumerate() {
+        let color = colors[((indices >> (2 * i)) & 3) as usize];
+        pixel[..3].copy_from_slice(&color[..3]);
+        if has_alpha || allow_three_colors {
+            pixel[3] = color[3];
+        }
+    }
+}
+
+fn decode_bc4(bytes: &[u8], out: &mut [[u8; 4]], channel: usize) {
+    let a0 = bytes[0] as u32;
+    let a1 = bytes[1] as u32;
+    let mut values = [0u32; 8];
+    values[0] = a0;
+    values[1] = a1;
+    if a0 > a1 {
+        for (i, value) in values.iter_mut().enumerate().skip(2) {
+            *value = ((8 - i as u32) * a0 + (i as u32 - 1) * a1) / 7;
+        }
+    } else {
+        for (i, value) in values.iter_mut().enumerate().take(6).skip(2) {
+            *value = ((6 - i as u32) * a0 + (i as u32 - 1) * a1) / 5;
+        }
+        values[6] = 0;
+        values[7] = 255;
+    }
+    let mut indices = [0u8; 8];
+    indices[..6].copy_from_slice(&bytes[2..8]);
+    let indices = u64::from_le_bytes(indices);
+    for (i, pixel) in out.iter_mut().enumerate() {
+        pixel[channel] = values[((indices >> (3 * i)) & 7) as usize] as u8;
+    }
+}
+
+// The number of subsets, partition bits, rotation bits, index selection bits, color bits, alpha bits,
+// p-bits per endpoint, p-bits per subset, index bits and secondary index bits of each BC7 mode
+const BC7_MODES: [[u32; 10]; 8] = [
+    [3, 4, 0, 0, 4, 0, 1, 0, 3, 0],
+    [2, 6, 0, 0, 6, 0, 0, 1, 3, 0],
+    [3, 6, 0, 0, 5, 0, 0, 0, 2, 0],
+    [2, 6, 0, 0, 7, 0, 1, 0, 2, 0],
+    [1, 0, 2, 1, 5, 6, 0, 0, 2, 3],
+    [1, 0, 2, 0, 7, 8, 0, 0, 2, 2],
+    [1, 0, 0, 0, 7, 7, 1, 0, 4, 0],
+    [2, 6, 0, 0, 5, 5, 1, 0, 2, 0],
+];
+
+const BC7_WEIGHTS_2: [u32; 4] = [0, 21, 43, 64];
+const BC7_WEIGHTS_3: [u32; 8] = [0, 9, 18, 27, 37, 46, 55, 64];
+const BC7_WEIGHTS_4: [u32; 16] = [0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64];
+
+// The subset of each texel in the partitions with two and three subsets, stored with two bits per texel
+const BC7_PARTITIONS_2: [u32; 64] = [
+    0x50505050, 0x40404040, 0x54545454, 0x54505040, 0x50404000, 0x55545450, 0x55545040, 0x54504000,
+    0x50400000, 0x55555450, 0x55544000, 0x54400000, 0x55555440, 0x55550000, 0x55555500, 0x55000000,
+    0x55150100, 0x00004054, 0x15010000, 0x00405054, 0x00004050, 0x15050100, 0x05010000, 0x40505054,
+    0x00404050, 0x05010100, 0x14141414, 0x05141450, 0x01155440, 0x00555500, 0x15014054, 0x05414150,
+    0x44444444, 0x55005500, 0x11441144, 0x05055050, 0x05500550, 0x11114444, 0x41144114, 0x44111144,
+    0x15055054, 0x01055040, 0x05041050, 0x05455150, 0x14414114, 0x50050550, 0x41411414, 0x00141400,
+    0x00041504, 0x00105410, 0x10541000, 0x04150400, 0x50410514, 0x41051450, 0x05415014, 0x14054150,
+    0x41050514, 0x41505014, 0x40011554, 0x54150140, 0x50505500, 0x00555050, 0x15151010, 0x54540404,
+];
+
+const BC7_PARTITIONS_3: [u32; 64] = [
+    0xAA685050, 0x6A5A5040, 0x5A5A4200, 0x5450A0A8, 0xA5A50000, 0xA0A05050, 0x5555A0A0, 0x5A5A5050,
+    0xAA550000, 0xAA555500, 0xAAAA5500, 0x90909090, 0x94949494, 0xA4A4A4A4, 0xA9A59450, 0x2A0A4250,
+    0xA5945040, 0x0A425054, 0xA5A5A500, 0x55A0A0A0, 0xA8A85454, 0x6A6A4040, 0xA4A45000, 0x1A1A0500,
+    0x0050A4A4, 0xAAA59090, 0x14696914, 0x69691400, 0xA08585A0, 0xAA821414, 0x50A4A450, 0x6A5A0200,
+    0xA9A58000, 0x5090A0A8, 0xA8A09050, 0x24242424, 0x00AA5500, 0x24924924, 0x24499224, 0x50A50A50,
+    0x500AA550, 0xAAAA4444, 0x66660000, 0xA5A0A5A0, 0x50A050A0, 0x69286928, 0x44AAAA44, 0x66666600,
+    0xAA444444, 0x54A854A8, 0x95809580, 0x96969600, 0xA85454A8, 0x80959580, 0xAA141414, 0x96960000,
+    0xAAAA1414, 0xA05050A0, 0xA0A5A5A0, 0x96000000, 0x40804080, 0xA9A8A9A8, 0xAAAAAA44, 0x2A4A5254,
+];
+
+// The index of the texel with an implicit most significant index bit in the second and third subset
+const BC7_ANCHORS_2: [u8; 64] = [
+    15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 2, 8, 2, 2, 8, 8, 15, 2, 8,
+    2, 2, 8, 8, 2, 2, 15, 15, 6, 8, 2, 8, 15, 15, 2, 8, 2, 2, 2, 15, 15, 6, 6, 2, 6, 8, 15, 15, 2,
+    2, 15, 15, 15, 15, 15, 2, 2, 15,
+];
+
+const BC7_ANCHORS_3: [[u8; 2]; 64] = [
+    [3, 15],
+    [3, 8],
+    [15, 8],
+    [15, 3],
+    [8, 15],
+    [3, 15],
+    [15, 3],
+    [15, 8],
+    [8, 15],
+    [8, 15],
+    [6, 15],
+    [6, 15],
+    [6, 15],
+    [5, 15],
+    [3, 15],
+    [3, 8],
+    [3, 15],
+    [3, 8],
+    [8, 15],
+    [15, 3],
+    [3, 15],
+    [3, 8],
+    [6, 15],
+    [10, 8],
+    [5, 3],
+    [8, 15],
+    [8, 6],
+    [6, 10],
+    [8, 15],
+    [5, 15],
+    [15, 10],
+    [15, 8],
+    [8, 15],
+    [15, 3],
+    [3, 15],
+    [5, 10],
+    [6, 10],
+    [10, 8],
+    [8, 9],
+    [15, 10],
+    [15, 6],
+    [3, 15],
+    [15, 8],
+    [5, 15],
+    [15, 3],
+    [15, 6],
+    [15, 6],
+    [15, 8],
+    [3, 15],
+    [15, 3],
+    [5, 15],
+    [5, 15],
+    [5, 15],
+    [8, 15],
+    [5, 15],
+    [10, 15],
+    [5, 15],
+    [10, 15],
+    [8, 15],
+    [13, 15],
+    [15, 3],
+    [12, 15],
+    [3, 15],
+    [3, 8],
+];
+
+fn decode_bc7(bytes: &[u8], out: &mut [[u8; 4]]) {
+    let block = u128::from_le_bytes(bytes[..16].try_into().unwrap());
+    let mode = block.trailing_zeros() as usize;
+    if mode >= 8 {
+        // Reserved mode
+        out.fill([0, 0, 0, 0]);
+        return;
+    }
+    let [subsets, partition_bits, rotation_bits, selection_bits, color_bits, alpha_bits, endpoint_pbits, subset_pbits, index_bits, index_bits2] =
+        BC7_MODES[mode];
+    let subsets = subsets as usize;
+    let mut offset = mode as u32 + 1;
+    let mut read = |count: u32| {
+        let value = ((block >> offset) & ((1 << count) - 1)) as u32;
+        offset += count;
+        value
+    };
+    let partition = read(partition_bits) as usize;
+    let rotation = read(rotation_bits) as usize;
+    let selection = read(selection_bits);
+
+    let mut endpoints = [[0u32; 4]; 6];
+    for channel in 0..4 {
+        let bits = if channel < 3 { color_bits } else { alpha_bits };
+        for endpoint in endpoints.iter_mut().take(2 * subsets) {
+            endpoint[channel] = read(bits);
+        }
+    }
+    let pbits = (0..(2 * endpoint_pbits + subset_pbits) * subsets as u32)
+        .map(|_| read(1))
+        .collect::<Vec<_>>();
+    for (i, endpoint) in endpoints.iter_mut().take(2 * subsets).enumerate() {
+        let pbit = pbits.get(if endpoint_pbits == 1 { i } else { i / 2 });
+        for (channel, value) in endpoint.iter_mut().enumerate() {
+            let bits = if channel < 3 { color_bits } else { alpha_bits };
+            if bits == 0 {
+                *value = 255;
+                continue;
+            }
+            let (v, bits) = match pbit {
+                Some(pbit) => (*value << 1 | pbit, bits + 1),
+                None => (*value, bits),
+            };
+            let v = v << (8 - bits);
+            *value = v | (v >> bits);
+        }
+    }
+
+    let partitions = match subsets {
+        2 => BC7_PARTITIONS_2[partition],
+        3 => BC7_PARTITIONS_3[partition],
+        _ => 0,
+    };
+    // The most significant index bit of the first texel in each subset is implicitly zero
+    let is_anchor = |i: usize| {
+        i == 0
+            || match subsets {
+                2 => i == BC7_ANCHORS_2[partition] as usize,
+                3 => BC7_ANCHORS_3[partition].contains(&(i as u8)),
+                _ => false,
+            }
+    };
+    let mut indices = [0; 16];
+    for (i, index) in indices.iter_mut().enumerate() {
+        *index = read(index_bits - is_anchor(i) as u32) as usize;
+    }
+    let mut indices2 = [0; 16];
+    if index_bits2 > 0 {
+        for (i, index) in indices2.iter_mut().enumerate() {
+            *index = read(index_bits2 - (i == 0) as u32) as usize;
+        }
+    }
+
+    let weight = |bits: u32, index: usize| match bits {
+        2 => BC7_WEIGHTS_2[index],
+        3 => BC7_WEIGHTS_3[index],
+        _ => BC7_WEIGHTS_4[index],
+    };
+    for (i, pixel) in out.iter_mut().enumerate() {
+        let subset = ((partitions >> (2 * i)) & 3) as usize;
+        let (e0, e1) = (endpoints[2 * subset], endpoints[2 * subset + 1]);
+        // The index selection bit swaps which indices are used for the color and the alpha channel
+        let (color_weight, alpha_weight) = if index_bits2 == 0 {
+            let w = weight(index_bits, indices[i]);
+            (w, w)
+        } else if selection == 0 {
+            (
+                weight(index_bits, indices[i]),
+                weight(index_bits2, indices2[i]),
+            )
+        } else {
+            (
+                weight(index_bits2, indices2[i]),
+                weight(index_bits, indices[i]),
+            )
+        };
+        for (channel, value) in pixel.iter_mut().enumerate() {
+            let w = if channel < 3 {
+                color_weight
+            } else {
+                alpha_weight
+            };
+            *value = (((64 - w) * e0[channel] + w * e1[channel] + 32) >> 6) as u8;
+        }
+        // The rotation swaps the alpha channel with one of the color channels
+        if rotation > 0 {
+            pixel.swap(rotation - 1, 3);
+        }
+    }
+}
+
+const ETC_MODIFIERS: [[i32; 2]; 8] = [
+    [2, 8],
+    [5, 17],
+    [9, 29],
+    [13, 42],
+    [18, 60],
+    [24, 80],
+    [33, 106],
+    [47, 183],
+];
+
+const ETC_DISTANCES: [i32; 8] = [3, 6, 11, 16, 23, 32, 41, 64];
+
+fn decode_etc2(bytes: &[u8], out: &mut [[u8; 4]], punchthrough: bool) {
+    let block = u64::from_be_bytes([
+        bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+    ]);
+    let bits = |offset: u32, count: u32| ((block >> offset) & ((1 << count) - 1)) as i32;
+    let extend4 = |v: i32| v * 17;
+    let extend5 = |v: i32| (v << 3) | (v >> 2);
+    let extend6 = |v: i32| (v << 2) | (v >> 4);
+    let extend7 = |v: i32| (v << 1) | (v >> 6);
+    let clamp = |v: i32| v.clamp(0, 255);
+    let add = |c: [i32; 3], d: i32| [clamp(c[0] + d), clamp(c[1] + d), clamp(c[2] + d)];
+    // In punchthrough mode, the differential bit instead tells whether the block is opaque
+    let differential = punchthrough || bits(33, 1) == 1;
+    let opaque = !punchthrough || bits(33, 1) == 1;
+    // The pixel indices are stored one column at a time
+    let index = |x: usize, y: usize| {
+        let j = x * 4 + y;
+        (bits(16 + j as u32, 1) << 1 | bits(j as u32, 1)) as usize
+    };
+    let write = |out: &mut [[u8; 4]], x: usize, y: usize, color: Option<[i32; 3]>| {
+        out[y * 4 + x] = match color {
+            Some(c) => [c[0] as u8, c[1] as u8, c[2] as u8, 255],
+            None => [0, 0, 0, 0],
+        };
+    };
+
+    let r = bits(59, 5) + (bits(56, 3) << 29 >> 29);
+    let g = bits(51, 5) + (bits(48, 3) << 29 >> 29);
+    let b = bits(43, 5) + (bits(40, 3) << 29 >> 29);
+    if differential && !(0..32).contains(&r) {
+        // T mode
+        let c0 = [
+            extend4(bits(59, 2) << 2 | bits(56, 2)),
+            extend4(bits(52, 4)),
+            extend4(bits(48, 4)),
+        ];
+        let c1 = [
+            extend4(bits(44, 4)),
+            extend4(bits(40, 4)),
+            extend4(bits(36, 4)),
+        ];
+        let d = ETC_DISTANCES[(bits(34, 2) << 1 | bits(32, 1)) as usize];
+        let paint = [c0, add(c1, d), c1, add(c1, -d)];
+        for y in 0..4 {
+            for x in 0..4 {
+                let i = index(x, y);
+                write(out, x, y, Some(paint[i]).filter(|_| opaque || i != 2));
+            }
+        }
+    } else if differential && !(0..32).contains(&g) {
+        // H mode
+        let r0 = bits(59, 4);
+        let g0 = bits(56, 3) << 1 | bits(52, 1);
+        let b0 = bits(51, 1) << 3 | bits(47, 3);
+        let r1 = bits(43, 4);
+        let g1 = bits(39, 4);
+        let b1 = bits(35, 4);
+        let mut d = bits(34, 1) << 2 | bits(32, 1) << 1;
+        if (r0 << 8 | g0 << 4 | b0) >= (r1 << 8 | g1 << 4 | b1) {
+            d |= 1;
+        }
+        let d = ETC_DISTANCES[d as usize];
+        let c0 = [extend4(r0), extend4(g0), extend4(b0)];
+        let c1 = [extend4(r1), extend4(g1), extend4(b1)];
+        let paint = [add(c0, d), add(c0, -d), add(c1, d), add(c1, -d)];
+        for y in 0..4 {
+            for x in 0..4 {
+                let i = index(x, y);
+                write(out, x, y, Some(paint[i]).filter(|_| opaque || i != 2));
+            }
+        }
+    } else if differential && !(0..32).contains(&b) {
+        // Planar mode
+        let o = [
+            extend6(bits(57, 6)),
+            extend7(bits(56, 1) << 6 | bits(49, 6)),
+            extend6(bits(48, 1) << 5 | bits(43, 2) << 3 | bits(39, 3)),
+        ];
+        let h = [
+            extend6(bits(34, 5) << 1 | bits(32, 1)),
+            extend7(bits(25, 7)),
+            extend6(bits(19, 6)),
+        ];
+        let v = [
+            extend6(bits(13, 6)),
+            extend7(bits(6, 7)),
+            extend6(bits(0, 6)),
+        ];
+        for y in 0..4 {
+            for x in 0..4 {
+                let (xi, yi) = (x as i32, y as i32);
+                let c = [0, 1, 2]
+                    .map(|k| clamp((xi * (h[k] - o[k]) + yi * (v[k] - o[k]) + 4 * o[k] + 2) >> 2));
+                write(out, x, y, Some(c));
+            }
+        }
+    } else {
+        // Individual or differential mode, like ETC1
+        let (c0, c1) = if differential {
+            (
+                [
+                    extend5(bits(59, 5)),
+                    extend5(bits(51, 5)),
+                    extend5(bits(43, 5)),
+                ],
+                [extend5(r), extend5(g), extend5(b)],
+            )
+        } else {
+            (
+                [
+                    extend4(bits(60, 4)),
+                    extend4(bits(52, 4)),
+                    extend4(bits(44, 4)),
+                ],
+                [
+                    extend4(bits(56, 4)),
+                    extend4(bits(48, 4)),
+                    extend4(bits(40, 4)),
+                ],
+            )
+        };
+        let tables = [bits(37, 3) as usize, bits(34, 3) as usize];
+        let flip = bits(32, 1) == 1;
+        for y in 0..4 {
+            for x in 0..4 {
+                let sub_block = if flip { y / 2 } else { x / 2 };
+                let base = if sub_block == 0 { c0 } else { c1 };
+                let [a, b] = ETC_MODIFIERS[tables[sub_block]];
+                let color = match index(x, y) {
+                    0 if !opaque => Some(base),
+                    0 => Some(add(base, a)),
+                    1 => Some(add(base, b)),
+                    2 if !opaque => None,
+                    2 => Some(add(base, -a)),
+                    _ => Some(add(base, -b)),
+                };
+                write(out, x, y, color);
+            }
+        }
+    }
+}
+
+const EAC_MODIFIERS: [[i32; 8]; 16] = [
+    [-3, -6, -9, -15, 2, 5, 8, 14],
+    [-3, -7, -10, -13, 2, 6, 9, 12],
+    [-2, -5, -8, -13, 1, 4, 7, 12],
+    [-2, -4, -6, -13, 1, 3, 5, 12],
+    [-3, -6, -8, -12, 2, 5, 7, 11],
+    [-3, -7, -9, -11, 2, 6, 8, 10],
+    [-4, -7, -8, -11, 3, 6, 7, 10],
+    [-3, -5, -8, -11, 2, 4, 7, 10],
+    [-2, -6, -8, -10, 1, 5, 7, 9],
+    [-2, -5, -8, -10, 1, 4, 7, 9],
+    [-2, -4, -8, -10, 1, 3, 7, 9],
+    [-2, -5, -7, -10, 1, 4, 6, 9],
+    [-3, -4, -7, -10, 2, 3, 6, 9],
+    [-1, -2, -3, -10, 0, 1, 2, 9],
+    [-4, -6, -8, -9, 3, 5, 7, 8],
+    [-3, -5, -7, -9, 2, 4, 6, 8],
+];
+
+fn decode_eac(bytes: &[u8], out: &mut [[u8; 4]], channel: usize, eleven_bit: bool) {
+    let block = u64::from_be_bytes([
+        bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+    ]);
+    let base = (block >> 56) as i32;
+    let multiplier = ((block >> 52) & 0xF) as i32;
+    let modifiers = EAC_MODIFIERS[((block >> 48) & 0xF) as usize];
+    for x in 0..4 {
+        for y in 0..4 {
+            // The pixel indices are stored one column at a time, starting with the most significant bits
+            let j = x * 4 + y;
+            let modifier = modifiers[((block >> (45 - 3 * j)) & 7) as usize];
+            out[y * 4 + x][channel] = if eleven_bit {
+                let value = if multiplier == 0 {
+                    base * 8 + 4 + modifier
+                } else {
+                    base * 8 + 4 + modifier * multiplier * 8
+                };
+                ((value.clamp(0, 2047) * 255 + 1023) / 2047) as u8
+            } else {
+                (base + modifier * multiplier).clamp(0, 255) as u8
+            };
+        }
+    }
+}
+
+fn decode_astc(bytes: &[u8], out: &mut [[u8; 4]], block_width: usize, block_height: usize) {
+    let block = u128::from_le_bytes(bytes[..16].try_into().unwrap());
+    if decode_astc_block(block, out, block_width, block_height).is_none() {
+        // Invalid blocks, including blocks which require the HDR profile, are decoded to magenta
+        out.fill([255, 0, 255, 255]);
+    }
+}
+
+fn decode_astc_block(
+    block: u128,
+    out: &mut [[u8; 4]],
+    block_width: usize,
+    block_height: usize,
+) -> Option<()> {
+    let bits = |offset: usize, count: usize| astc_bits(block, offset, count);
+    let mode = bits(0, 11);
+    if mode & 0x1FF == 0x1FC {
+        // Void-extent block with a constant color
+        if mode & 0x200 != 0 || bits(10, 2) != 3 {
+            return None;
+        }
+        let extent = [12, 25, 38, 51].map(|offset| bits(offset, 13));
+        if extent != [0x1FFF; 4] && (extent[0] >= extent[1] || extent[2] >= extent[3]) {
+            return None;
+        }
+        out.fill([64, 80, 96, 112].map(|offset| (bits(offset, 16) >> 8) as u8));
+        return Some(());
+    }
+
+    // The block mode defines the size of the weight grid, the range of the weights and whether there are two weight planes
+    let a = (mode >> 5) & 3;
+    let b = (mode >> 7) & 3;
+    let (grid_width, grid_height, range) = if mode & 3 != 0 {
+        let size = match (mode >> 2) & 3 {
+            0 => (b + 4, a + 2),
+            1 => (b + 8, a + 2),
+            2 => (a + 2, b + 8),
+            _ if mode & 0x100 != 0 => ((b & 1) + 2, a + 2),
+            _ => (a + 2, (b & 1) + 6),
+        };
+        (size.0, size.1, (mode >> 4) & 1 | (mode & 3) << 1)
+    } else {
+        let size = match b {
+            _ if mode & 0xC == 0 => return None,
+            0 => (12, a + 2),
+            1 => (a + 2, 12),
+            2 => (a + 6, ((mode >> 9) & 3) + 6),
+            _ if a == 0 => (6, 10),
+            _ if a == 1 => (10, 6),
+            _ => return None,
+        };
+        (size.0, size.1, (mode >> 4) & 1 | (mode & 0xC) >> 1)
+    };
+    let (grid_width, grid_height) = (grid_width as usize, grid_height as usize);
+    // The grid size with a = 6..9 and b = 6..9 uses the bits which otherwise define the precision and dual planes
+    let is_large_grid = mode & 3 == 0 && b == 2;
+    let is_dual_plane = !is_large_grid && mode & 0x400 != 0;
+    let weight_levels = if !is_large_grid && mode & 0x200 != 0 {
+        [10, 12, 16, 20, 24, 32][range as usize - 2]
+    } else {
+        [2, 3, 4, 5, 6, 8][range as usize - 2]
+    };
+    let planes = if is_dual_plane { 2 } else { 1 };
+    let weight_count = grid_width * grid_height * planes;
+    let weight_bits = astc_sequence_bits(weight_levels, weight_count);
+    if grid_width > block_width
+        || grid_height > block_height
+        || weight_count > 64
+        || !(24..=96).contains(&weight_bits)
+    {
+        return None;
+    }
+
+    // The partitions and the color endpoint mode of each partition
+    let partition_count = bits(11, 2) as usize + 1;
+    if is_dual_plane && partition_count == 4 {
+        return None;
+    }
+    let mut below_weights = 128 - weight_bits;
+    let mut endpoint_modes = [0; 4];
+    let (partition_index, color_start) = if partition_count == 1 {
+        endpoint_modes[0] = bits(13, 4);
+        (0, 17)
+    } else {
+        let selector = bits(23, 2);
+        if selector == 0 {
+            endpoint_modes = [bits(25, 4); 4];
+        } else {
+            // The remaining bits of the endpoint modes are stored below the weights
+            let extra_bits = 3 * partition_count - 4;
+            below_weights -= extra_bits;
+            let encoded = bits(25, 4) | bits(below_weights, extra_bits) << 4;
+            for (i, endpoint_mode) in endpoint_modes.iter_mut().enumerate().take(partition_count) {
+                let class = selector - 1 + ((encoded >> i) & 1);
+                *endpoint_mode = class << 2 | ((encoded >> (partition_count + 2 * i)) & 3);
+            }
+        }
+        (bits(13, 10), 29)
+    };
+    let plane2_channel = if is_dual_plane {
+        below_weights -= 2;
+        Some(bits(below_weights, 2) as usize)
+    } else {
+        None
+    };
+
+    // The color endpoints use the largest range which fits into the remaining bits
+    let color_bits = below_weights.checked_sub(color_start)?;
+    let value_counts = endpoint_modes.map(|endpoint_mode| 2 * (endpoint_mode as usize / 4 + 1));
+    let value_count = value_counts[..partition_count].iter().sum::<usize>();
+    let color_levels = ASTC_COLOR_LEVELS
+        .into_iter()
+        .rev()
+        .find(|levels| astc_sequence_bits(*levels, value_count) <= color_bits)
+        .filter(|levels| value_count <= 18 && *levels >= 6)?;
+    let values = astc_decode_sequence(
+        block >> color_start,
+        color_levels,
+        value_count,
+        astc_sequence_bits(color_levels, value_count),
+    )
+    .into_iter()
+    .map(|value| astc_unquantize_color(value, color_levels))
+    .collect::<Vec<_>>();
+    let mut endpoints = [[[0; 4]; 2]; 4];
+    let mut offset = 0;
+    for partition in 0..partition_count {
+        endpoints[partition] = astc_endpoints(endpoint_modes[partition], &values[offset..])?;
+        offset += value_counts[partition];
+    }
+
+    // The weights are stored in reverse order from the end of the block
+    let weights = astc_decode_sequence(
+        block.reverse_bits(),
+        weight_levels,
+        weight_count,
+        weight_bits,
+    )
+    .into_iter()
+    .map(|value| astc_unquantize_weight(value, weight_levels))
+    .collect::<Vec<_>>();
+    let scale_x = (1024 + block_width / 2) / (block_width - 1);
+    let scale_y = (1024 + block_height / 2) / (block_height - 1);
+    for y in 0..block_height {
+        for x in 0..block_width {
+            // Bilinear interpolation of the weight grid
+            let grid_x = (scale_x * x * (grid_width - 1) + 32) >> 6;
+            let grid_y = (scale_y * y * (grid_height - 1) + 32) >> 6;
+            let (fx, fy) = (grid_x & 15, grid_y & 15);
+            let w11 = (fx * fy + 8) >> 4;
+            let factors = [16 + w11 - fx - fy, fx - w11, fy - w11, w11];
+            let index = (grid_y >> 4) * grid_width + (grid_x >> 4);
+            let texel_weight = |plane: usize| {
+                let mut weight = 8;
+                for (factor, i) in factors.iter().zip([
+                    index,
+                    index + 1,
+                    index + grid_width,
+                    index + grid_width + 1,
+                ]) {
+                    weight +=
+                        factor * weights.get(i * planes + plane).copied().unwrap_or(0) as usize;
+                }
+                (weight >> 4) as u32
+            };
+            let plane_weights = [texel_weight(0), texel_weight(planes - 1)];
+
+            let partition = if partition_count > 1 {
+                astc_partition(
+                    partition_index,
+                    x as u32,
+                    y as u32,
+                    partition_count,
+                    block_width * block_height < 31,
+                )
+            } else {
+                0
+            };
+            let [e0, e1] = endpoints[partition];
+            for (channel, value) in out[y * block_width + x].iter_mut().enumerate() {
+                let weight = plane_weights[(plane2_channel == Some(channel)) as usize];
+                // The endpoints are expanded to 16 bits before the interpolation
+                let color =
+                    (e0[channel] * 257 * (64 - weight) + e1[channel] * 257 * weight + 32) >> 6;
+                *value = ((color * 255 + 32767) / 65536) as u8;
+            }
+        }
+    }
+    Some(())
+}
+
+const ASTC_COLOR_LEVELS: [u32; 21] = [
+    2, 3, 4, 5, 6, 8, 10, 12, 16, 20, 24, 32, 40, 48, 64, 80, 96, 128, 160, 192, 256,
+];
+
+fn astc_bits(data: u128, offset: usize, count: usize) -> u32 {
+    if offset >= 128 {
+        0
+    } else {
+        ((data >> offset) & ((1 << count) - 1)) as u32
+    }
+}
+
+// Returns the number of bits, trits and quints used for storing a value with the given number of levels
+fn astc_encoding(levels: u32) -> (usize, usize, usize) {
+    if levels.is_multiple_of(3) {
+        ((levels / 3).trailing_zeros() as usize, 1, 0)
+    } else if levels.is_multiple_of(5) {
+        ((levels / 5).trailing_zeros() as usize, 0, 1)
+    } else {
+        (levels.trailing_zeros() as usize, 0, 0)
+    }
+}
+
+fn astc_sequence_bits(levels: u32, count: usize) -> usize {
+    let (bits, trits, quints) = astc_encoding(levels);
+    bits * count + trits * (8 * count).div_ceil(5) + quints * (7 * count).div_ceil(3)
+}
+
+// Decodes a sequence of values which is stored with the bounded integer sequence encoding,
+// where five values share the bits of their trits or three values share the bits of their quints.
+fn astc_decode_sequence(data: u128, levels: u32, count: usize, size: usize) -> Vec<u32> {
+    let data = data & ((1 << size) - 1);
+    let (bits, trits, quints) = astc_encoding(levels);
+    let mut offset = 0;
+    let mut read = |count: usize| {
+        let value = astc_bits(data, offset, count);
+        offset += count;
+        value
+    };
+    let mut values = Vec::with_capacity(count + 4);
+    while values.len() < count {
+        if trits == 1 {
+            let mut low_bits = [0; 5];
+            let mut packed = 0;
+            for (i, (shift, size)) in [(0, 2), (2, 2), (4, 1), (5, 2), (7, 1)]
+                .into_iter()
+                .enumerate()
+            {
+                low_bits[i] = read(bits);
+                packed |= read(size) << shift;
+            }
+            let trits = astc_trits(packed);
+            values.extend((0..5).map(|i| trits[i] << bits | low_bits[i]));
+        } else if quints == 1 {
+            let mut low_bits = [0; 3];
+            let mut packed = 0;
+            for (i, (shift, size)) in [(0, 3), (3, 2), (5, 2)].into_iter().enumerate() {
+                low_bits[i] = read(bits);
+                packed |= read(size) << shift;
+            }
+            let quints = astc_quints(packed);
+            values.extend((0..3).map(|i| quints[i] << bits | low_bits[i]));
+        } else {
+            values.push(read(bits));
+        }
+    }
+    values.truncate(count);
+    values
+}
+
+fn astc_trits(t: u32) -> [u32; 5] {
+    let bit = |value: u32, i: u32| (value >> i) & 1;
+    let (c, t3, t4) = if (t >> 2) & 7 == 7 {
+        ((t >> 5) << 2 | (t & 3), 2, 2)
+    } else if (t >> 5) & 3 == 3 {
+        (t & 0x1F, bit(t, 7), 2)
+    } else {
+        (t & 0x1F, (t >> 5) & 3, bit(t, 7))
+    };
+    let (t0, t1, t2) = if c & 3 == 3 {
+        (bit(c, 3) << 1 | (bit(c, 2) & !bit(c, 3) & 1), bit(c, 4), 2)
+    } else if (c >> 2) & 3 == 3 {
+        (c & 3, 2, 2)
+    } else {
+        (
+            bit(c, 1) << 1 | (bit(c, 0) & !bit(c, 1) & 1),
+            (c >> 2) & 3,
+            bit(c, 4),
+        )
+    };
+    [t0, t1, t2, t3, t4]
+}
+
+fn astc_quints(q: u32) -> [u32; 3] {
+    let bit = |value: u32, i: u32| (value >> i) & 1;
+    if (q >> 1) & 3 == 3 && (q >> 5) & 3 == 0 {
+        let q2 = bit(q, 0) << 2 | (bit(q, 4) & !bit(q, 0) & 1) << 1 | (bit(q, 3) & !bit(q, 0) & 1);
+        return [4, 4, q2];
+    }
+    let (c, q2) = if (q >> 1) & 3 == 3 {
+        (((q >> 3) & 3) << 3 | ((!q >> 5) & 3) << 1 | bit(q, 0), 4)
+    } else {
+        (q & 0x1F, (q >> 5) & 3)
+    };
+    if c & 7 == 5 {
+        [(c >> 3) & 3, 4, q2]
+    } else {
+        [c & 7, (c >> 3) & 3, q2]
+    }
+}
+
+// Repeats the bits of the value until it has the given number of bits
+fn astc_replicate(value: u32, bits: usize, to: usize) -> u32 {
+    if bits == 0 {
+        return 0;
+    }
+    let mut result = value << (to - bits);
+    let mut filled = bits;
+    while filled < to {
+        result |= result >> filled;
+        filled *= 2;
+    }
+    result
+}
+
+fn astc_unquantize_color(value: u32, levels: u32) -> u32 {
+    let (bits, trits, quints) = astc_encoding(levels);
+    if trits == 0 && quints == 0 {
+        return astc_replicate(value, bits, 8);
+    }
+    let bit = |i: u32| (value >> i) & 1;
+    let (b, c) = match (trits, bits) {
+        (1, 1) => (0, 204),
+        (1, 2) => (bit(1) * 0x116, 93),
+        (1, 3) => (bit(2) * 0x10A + bit(1) * 0x85, 44),
+        (1, 4) => (bit(3) * 0x104 + bit(2) * 0x82 + bit(1) * 0x41, 22),
+        (1, 5) => (
+            bit(4) * 0x102 + bit(3) * 0x81 + bit(2) * 0x40 + bit(1) * 0x20,
+            11,
+        ),
+        (1, _) => (
+            bit(5) * 0x101 + bit(4) * 0x80 + bit(3) * 0x40 + bit(2) * 0x20 + bit(1) * 0x10,
+            5,
+        ),
+        (_, 1) => (0, 113),
+        (_, 2) => (bit(1) * 0x10C, 54),
+        (_, 3) => (bit(2) * 0x105 + bit(1) * 0x82, 26),
+        (_, 4) => (bit(3) * 0x102 + bit(2) * 0x81 + bit(1) * 0x40, 13),
+        _ => (
+            bit(4) * 0x101 + bit(3) * 0x80 + bit(2) * 0x40 + bit(1) * 0x20,
+            6,
+        ),
+    };
+    let a = bit(0) * 0x1FF;
+    let t = ((value >> bits) * c + b) ^ a;
+    (a & 0x80) | (t >> 2)
+}
+
+fn astc_unquantize_weight(value: u32, levels: u32) -> u32 {
+    let (bits, trits, quints) = astc_encoding(levels);
+    let bit = |i: u32| (value >> i) & 1;
+    let weight = if trits == 0 && quints == 0 {
+        astc_replicate(value, bits, 6)
+    } else if bits == 0 {
+        if trits == 1 {
+            [0, 32, 63][value as usize]
+        } else {
+            [0, 16, 32, 47, 63][value as usize]
+        }
+    } else {
+        let (b, c) = match (trits, bits) {
+            (1, 1) => (0, 50),
+            (1, 2) => (bit(1) * 0x45, 23),
+            (1, _) => (bit(2) * 0x42 + bit(1) * 0x21, 11),
+            (_, 1) => (0, 28),
+            _ => (bit(1) * 0x42, 13),
+        };
+        let a = bit(0) * 0x7F;
+        let t = ((value >> bits) * c + b) ^ a;
+        (a & 0x20) | (t >> 2)
+    };
+    if weight > 32 {
+        weight + 1
+    } else {
+        weight
+    }
+}
+
+// Returns the two endpoints of the given color endpoint mode, or None for the HDR modes
+fn astc_endpoints(mode: u32, v: &[u32]) -> Option<[[u32; 4]; 2]> {
+    let v = |i: usize| v[i] as i32;
+    // Moves the most significant bit of b to a, which becomes a signed offset
+    let transfer = |a: i32, b: i32| {
+        let offset = (a >> 1) & 0x3F;
+        (
+            if offset & 0x20 != 0 {
+                offset - 0x40
+            } else {
+                offset
+            },
+            (b >> 1) | (a & 0x80),
+        )
+    };
+    let blue_contract = |c: [i32; 4]| [(c[0] + c[2]) >> 1, (c[1] + c[2]) >> 1, c[2], c[3]];
+    let endpoints = match mode {
+        0 => [[v(0), v(0), v(0), 255], [v(1), v(1), v(1), 255]],
+        1 => {
+            let l0 = (v(0) >> 2) | (v(1) & 0xC0);
+            let l1 = (l0 + (v(1) & 0x3F)).min(255);
+            [[l0, l0, l0, 255], [l1, l1, l1, 255]]
+        }
+        4 => [[v(0), v(0), v(0), v(2)], [v(1), v(1), v(1), v(3)]],
+        5 => {
+            let (d0, l) = transfer(v(1), v(0));
+            let (d1, a) = transfer(v(3), v(2));
+            [[l, l, l, a], [l + d0, l + d0, l + d0, a + d1]]
+        }
+        6 | 10 => {
+            let alpha = if mode == 6 { [255, 255] } else { [v(4), v(5)] };
+            [
+                [
+                    (v(0) * v(3)) >> 8,
+                    (v(1) * v(3)) >> 8,
+                    (v(2) * v(3)) >> 8,
+                    alpha[0],
+                ],
+                [v(0), v(1), v(2), alpha[1]],
+            ]
+        }
+        8 | 12 => {
+            let alpha = if mode == 8 { [255, 255] } else { [v(6), v(7)] };
+            let e0 = [v(0), v(2), v(4), alpha[0]];
+            let e1 = [v(1), v(3), v(5), alpha[1]];
+            if v(1) + v(3) + v(5) >= v(0) + v(2) + v(4) {
+                [e0, e1]
+            } else {
+                [blue_contract(e1), blue_contract(e0)]
+            }
+        }
+        9 | 13 => {
+            let (d_r, r) = transfer(v(1), v(0));
+            let (d_g, g) = transfer(v(3), v(2));
+            let (d_b, b) = transfer(v(5), v(4));
+            let (d_a, a) = if mode == 9 {
+                (0, 255)
+            } else {
+                transfer(v(7), v(6))
+            };
+            let e0 = [r, g, b, a];
+            let e1 = [r + d_r, g + d_g, b + d_b, a + d_a];
+            if d_r + d_g + d_b >= 0 {
+                [e0, e1]
+            } else {
+                [blue_contract(e1), blue_contract(e0)]
+            }
+        }
+        _ => return None,
+    };
+    Some(endpoints.map(|e| e.map(|c| c.clamp(0, 255) as u32)))
+}
+
+// Returns the partition of the texel at the given position, see the ASTC specification
+fn astc_partition(
+    seed: u32,
+    x: u32,
+    y: u32,
+    partition_count: usize,
+    is_small_block: bool,
+) -> usize {
+    let (x, y) = if is_small_block {
+        (x << 1, y << 1)
+    } else {
+        (x, y)
+    };
+    let seed = seed + (partition_count as u32 - 1) * 1024;
+    let mut random = seed;
+    random ^= random >> 15;
+    random = random.wrapping_sub(random << 17);
+    random = random.wrapping_add(random << 7);
+    random = random.wrapping_add(random << 4);
+    random ^= random >> 5;
+    random = random.wrapping_add(random << 16);
+    random ^= random >> 7;
+    random ^= random >> 3;
+    random ^= random << 6;
+    random ^= random >> 17;
+
+    let (shift1, shift2) = match (seed & 1 != 0, seed & 2 != 0) {
+        (true, true) => (4, if partition_count == 3 { 6 } else { 5 }),
+        (true, false) => (5, if partition_count == 3 { 6 } else { 5 }),
+        (false, true) => (if partition_count == 3 { 6 } else { 5 }, 4),
+        (false, false) => (if partition_count == 3 { 6 } else { 5 }, 5),
+    };
+    let seeds = [0, 4, 8, 12, 16, 20, 24, 28].map(|shift| (random >> shift) & 0xF);
+    let scale =
+        |i: usize| (seeds[i] * seeds[i]) >> if i.is_multiple_of(2) { shift1 } else { shift2 };
+    let mut values = [
+        (scale(0) * x + scale(1) * y + (random >> 14)) & 0x3F,
+        (scale(2) * x + scale(3) * y + (random >> 10)) & 0x3F,
+        (scale(4) * x + scale(5) * y + (random >> 6)) & 0x3F,
+        (scale(6) * x + scale(7) * y + (random >> 2)) & 0x3F,
+    ];
+    for value in values.iter_mut().skip(partition_count) {
+        *value = 0;
+    }
+    // The first partition with the largest value
+    let max = values.iter().max().unwrap();
+    values.iter().position(|value| value == max).unwrap()
+}
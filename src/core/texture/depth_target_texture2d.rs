@@ -12,6 +12,10 @@ pub enum DepthFormat {
     Depth24,
     /// 32 bit per pixel.
     Depth32F,
+    /// 24 bit depth and 8 bit stencil per pixel.
+    Depth24Stencil8,
+    /// 32 bit depth and 8 bit stencil per pixel.
+    Depth32FStencil8,
 }
 
 ///
@@ -22,6 +26,7 @@ pub struct DepthTargetTexture2D {
     id: crate::context::Texture,
     width: u32,
     height: u32,
+    format: DepthFormat,
 }
 
 impl DepthTargetTexture2D {
@@ -42,6 +47,7 @@ impl DepthTargetTexture2D {
             id,
             width,
             height,
+            format,
         };
         texture.bind();
         set_parameters(
@@ -108,7 +114,7 @@ impl DepthTargetTexture2D {
         unsafe {
             self.context.framebuffer_texture_2d(
                 crate::context::FRAMEBUFFER,
-                crate::context::DEPTH_ATTACHMENT,
+                depth_attachment_from_format(self.format),
                 crate::context::TEXTURE_2D,
                 Some(self.id),
                 0,
@@ -116,6 +122,19 @@ impl DepthTargetTexture2D {
         }
     }
 
+    ///
+    /// Sets the label of this texture, which is shown in graphics debuggers and debug messages, see [Context::set_debug_output].
+    /// Requires the `KHR_debug` extension, otherwise this does nothing.
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.context.set_object_label(
+            crate::context::TEXTURE,
+            crate::context::TEXTURE_BINDING_2D,
+            || self.bind(),
+            label,
+        );
+    }
+
     pub(in crate::core) fn bind(&self) {
         unsafe {
             self.context
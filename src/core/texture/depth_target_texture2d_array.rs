@@ -9,6 +9,7 @@ pub struct DepthTargetTexture2DArray {
     width: u32,
     height: u32,
     depth: u32,
+    format: DepthFormat,
 }
 
 impl DepthTargetTexture2DArray {
@@ -31,6 +32,7 @@ impl DepthTargetTexture2DArray {
             width,
             height,
             depth,
+            format,
         };
         texture.bind();
         set_parameters(
@@ -104,7 +106,7 @@ impl DepthTargetTexture2DArray {
         unsafe {
             self.context.framebuffer_texture_layer(
                 crate::context::DRAW_FRAMEBUFFER,
-                crate::context::DEPTH_ATTACHMENT,
+                depth_attachment_from_format(self.format),
                 Some(self.id),
                 0,
                 layer as i32,
@@ -112,6 +114,19 @@ impl DepthTargetTexture2DArray {
         }
     }
 
+    ///
+    /// Sets the label of this texture, which is shown in graphics debuggers and debug messages, see [Context::set_debug_output].
+    /// Requires the `KHR_debug` extension, otherwise this does nothing.
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.context.set_object_label(
+            crate::context::TEXTURE,
+            crate::context::TEXTURE_BINDING_2D_ARRAY,
+            || self.bind(),
+            label,
+        );
+    }
+
     pub(in crate::core) fn bind(&self) {
         unsafe {
             self.context
@@ -8,6 +8,7 @@ pub struct DepthTargetTextureCubeMap {
     id: crate::context::Texture,
     width: u32,
     height: u32,
+    format: DepthFormat,
 }
 
 impl DepthTargetTextureCubeMap {
@@ -29,6 +30,7 @@ impl DepthTargetTextureCubeMap {
             id,
             width,
             height,
+            format,
         };
         texture.bind();
         set_parameters(
@@ -96,7 +98,7 @@ impl DepthTargetTextureCubeMap {
         unsafe {
             self.context.framebuffer_texture_2d(
                 crate::context::DRAW_FRAMEBUFFER,
-                crate::context::DEPTH_ATTACHMENT,
+                depth_attachment_from_format(self.format),
                 side.to_const(),
                 Some(self.id),
                 0,
@@ -104,6 +106,19 @@ impl DepthTargetTextureCubeMap {
         }
     }
 
+    ///
+    /// Sets the label of this texture, which is shown in graphics debuggers and debug messages, see [Context::set_debug_output].
+    /// Requires the `KHR_debug` extension, otherwise this does nothing.
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.context.set_object_label(
+            crate::context::TEXTURE,
+            crate::context::TEXTURE_BINDING_CUBE_MAP,
+            || self.bind(),
+            label,
+        );
+    }
+
     pub(in crate::core) fn bind(&self) {
         unsafe {
             self.context
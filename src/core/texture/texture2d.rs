@@ -30,6 +30,10 @@ impl Texture2D {
             TextureData::RgF32(ref data) => Self::new_with_data(context, cpu_texture, data),
             TextureData::RgbF32(ref data) => Self::new_with_data(context, cpu_texture, data),
             TextureData::RgbaF32(ref data) => Self::new_with_data(context, cpu_texture, data),
+            TextureData::Compressed {
+                format,
+                ref mip_levels,
+            } => Self::new_with_compressed_data(context, cpu_texture, format, mip_levels),
         }
     }
 
@@ -52,6 +56,90 @@ impl Texture2D {
         Ok(texture)
     }
 
+    fn new_with_compressed_data(
+        context: &Context,
+        cpu_texture: &CpuTexture,
+        format: CompressedFormat,
+        mip_levels: &[Vec<u8>],
+    ) -> ThreeDResult<Self> {
+        if !format.is_supported(context) {
+            let mut decompressed = cpu_texture.decompress()?;
+            // Flipped here, since it is flipped again when uploaded, to keep the orientation of the compressed data
+            let (width, height) = (cpu_texture.width as usize, cpu_texture.height as usize);
+            match decompressed.data {
+                TextureData::RU8(ref mut data) => flip_y(data, width, height),
+                TextureData::RgU8(ref mut data) => flip_y(data, width, height),
+                TextureData::RgbaU8(ref mut data) => flip_y(data, width, height),
+                _ => unreachable!(),
+            }
+            return Self::new(context, &decompressed);
+        }
+        if mip_levels.is_empty() {
+            Err(CoreError::InvalidTextureLength(
+                0,
+                format.byte_size(cpu_texture.width, cpu_texture.height),
+            ))?;
+        }
+        for (level, bytes) in mip_levels.iter().enumerate() {
+            let expected_bytes = format.byte_size(
+                (cpu_texture.width >> level).max(1),
+                (cpu_texture.height >> level).max(1),
+            );
+            if bytes.len() != expected_bytes {
+                Err(CoreError::InvalidTextureLength(bytes.len(), expected_bytes))?;
+            }
+        }
+        let id = generate(context)?;
+        let number_of_mip_maps = mip_levels.len() as u32;
+        let texture = Self {
+            context: context.clone(),
+            id,
+            width: cpu_texture.width,
+            height: cpu_texture.height,
+            // Mip maps are not generated for compressed data, instead the given mip levels are used
+            number_of_mip_maps: 1,
+            data_byte_size: format.block_byte_size(),
+        };
+        texture.bind();
+        set_parameters(
+            context,
+            crate::context::TEXTURE_2D,
+            cpu_texture.min_filter,
+            cpu_texture.mag_filter,
+            if number_of_mip_maps == 1 {
+                None
+            } else {
+                cpu_texture.mip_map_filter
+            },
+            cpu_texture.wrap_s,
+            cpu_texture.wrap_t,
+            None,
+        )?;
+        unsafe {
+            context.tex_storage_2d(
+                crate::context::TEXTURE_2D,
+                number_of_mip_maps as i32,
+                format.internal_format()?,
+                cpu_texture.width as i32,
+                cpu_texture.height as i32,
+            );
+            for (level, bytes) in mip_levels.iter().enumerate() {
+                context.compressed_tex_sub_image_2d(
+                    crate::context::TEXTURE_2D,
+                    level as i32,
+                    0,
+                    0,
+                    (cpu_texture.width >> level).max(1) as i32,
+                    (cpu_texture.height >> level).max(1) as i32,
+                    format.internal_format()?,
+                    crate::context::CompressedPixelUnpackData::Slice(bytes),
+                );
+            }
+        }
+        context.error_check()?;
+        Ok(texture)
+    }
+
     ///
     /// Constructs a new empty 2D texture with the given parameters.
     /// The format is determined by the generic [TextureDataType] parameter
@@ -208,6 +296,19 @@ impl Texture2D {
             );
         }
     }
+    ///
+    /// Sets the label of this texture, which is shown in graphics debuggers and debug messages, see [Context::set_debug_output].
+    /// Requires the `KHR_debug` extension, otherwise this does nothing.
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.context.set_object_label(
+            crate::context::TEXTURE,
+            crate::context::TEXTURE_BINDING_2D,
+            || self.bind(),
+            label,
+        );
+    }
+
     pub(in crate::core) fn bind(&self) {
         unsafe {
             self.context
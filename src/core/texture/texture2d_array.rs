@@ -145,6 +145,19 @@ impl Texture2DArray {
         }
     }
 
+    ///
+    /// Sets the label of this texture, which is shown in graphics debuggers and debug messages, see [Context::set_debug_output].
+    /// Requires the `KHR_debug` extension, otherwise this does nothing.
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.context.set_object_label(
+            crate::context::TEXTURE,
+            crate::context::TEXTURE_BINDING_2D_ARRAY,
+            || self.bind(),
+            label,
+        );
+    }
+
     pub(in crate::core) fn bind(&self) {
         unsafe {
             self.context
@@ -30,6 +30,9 @@ impl Texture3D {
             TextureData::RgF32(ref data) => Self::new_with_data(context, cpu_texture, data),
             TextureData::RgbF32(ref data) => Self::new_with_data(context, cpu_texture, data),
             TextureData::RgbaF32(ref data) => Self::new_with_data(context, cpu_texture, data),
+            TextureData::Compressed { .. } => Err(CoreError::TextureCreation(
+                "compressed data is not supported for 3D textures".to_string(),
+            ))?,
         }
     }
 
@@ -169,6 +172,19 @@ impl Texture3D {
             }
         }
     }
+    ///
+    /// Sets the label of this texture, which is shown in graphics debuggers and debug messages, see [Context::set_debug_output].
+    /// Requires the `KHR_debug` extension, otherwise this does nothing.
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.context.set_object_label(
+            crate::context::TEXTURE,
+            crate::context::TEXTURE_BINDING_3D,
+            || self.bind(),
+            label,
+        );
+    }
+
     pub(in crate::core) fn bind(&self) {
         unsafe {
             self.context
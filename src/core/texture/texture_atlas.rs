@@ -0,0 +1,270 @@
+use crate::core::texture::*;
+use std::rc::Rc;
+
+///
+/// A rectangle in texture coordinates, for example the area of an image in a [TextureAtlas].
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UvRect {
+    /// The minimum uv coordinates, ie. the bottom left corner of the image.
+    pub min: Vec2,
+    /// The maximum uv coordinates, ie. the top right corner of the image.
+    pub max: Vec2,
+}
+
+impl UvRect {
+    ///
+    /// Creates a new rectangle with the given minimum and maximum uv coordinates.
+    ///
+    pub fn new(min: Vec2, max: Vec2) -> Self {
+        Self { min, max }
+    }
+}
+
+impl Default for UvRect {
+    fn default() -> Self {
+        Self::new(vec2(0.0, 0.0), vec2(1.0, 1.0))
+    }
+}
+
+///
+/// A [Texture2D] containing many images packed together, which makes it possible to render objects with different images in the same draw call.
+/// The area of each image in the atlas is given as a [UvRect].
+///
+pub struct TextureAtlas {
+    texture: Rc<Texture2D>,
+    uv_rects: Vec<UvRect>,
+}
+
+impl TextureAtlas {
+    ///
+    /// Packs the given textures into one texture with the given number of pixels between the images.
+    /// The edge pixels of each image are repeated into the padding to avoid bleeding between the images when interpolating.
+    /// The filtering is taken from the first texture.
+    /// Textures with [TextureData::Compressed] data are decompressed before they are packed, see [CpuTexture::decompress].
+    ///
+    /// # Errors
+    /// Returns an error if no textures are given or if the textures do not have the same format.
+    ///
+    pub fn new(context: &Context, cpu_textures: &[CpuTexture], padding: u32) -> ThreeDResult<Self> {
+        let decompressed;
+        let cpu_textures = if cpu_textures
+            .iter()
+            .any(|t| matches!(t.data, TextureData::Compressed { .. }))
+        {
+            decompressed = cpu_textures
+                .iter()
+                .map(|t| t.decompress())
+                .collect::<ThreeDResult<Vec<_>>>()?;
+            &decompressed
+        } else {
+            cpu_textures
+        };
+        let first = cpu_textures.first().ok_or_else(|| {
+            CoreError::TextureCreation("a texture atlas needs at least one texture".to_string())
+        })?;
+        let layout = Layout::new(cpu_textures, padding);
+        let data = match first.data {
+            TextureData::RU8(_) => TextureData::RU8(layout.compose(cpu_textures, |d| match d {
+                TextureData::RU8(d) => Some(d),
+                _ => None,
+            })?),
+            TextureData::RgU8(_) => {
+                TextureData::RgU8(layout.compose(cpu_textures, |d| match d {
+                    TextureData::RgU8(d) => Some(d),
+                    _ => None,
+                })?)
+            }
+            TextureData::RgbU8(_) => {
+                TextureData::RgbU8(layout.compose(cpu_textures, |d| match d {
+                    TextureData::RgbU8(d) => Some(d),
+                    _ => None,
+                })?)
+            }
+            TextureData::RgbaU8(_) => {
+                TextureData::RgbaU8(layout.compose(cpu_textures, |d| match d {
+                    TextureData::RgbaU8(d) => Some(d),
+                    _ => None,
+                })?)
+            }
+            TextureData::RF16(_) => {
+                TextureData::RF16(layout.compose(cpu_textures, |d| match d {
+                    TextureData::RF16(d) => Some(d),
+                    _ => None,
+                })?)
+            }
+            TextureData::RgF16(_) => {
+                TextureData::RgF16(layout.compose(cpu_textures, |d| match d {
+                    TextureData::RgF16(d) => Some(d),
+                    _ => None,
+                })?)
+            }
+            TextureData::RgbF16(_) => {
+                TextureData::RgbF16(layout.compose(cpu_textures, |d| match d {
+                    TextureData::RgbF16(d) => Some(d),
+                    _ => None,
+                })?)
+            }
+            TextureData::RgbaF16(_) => {
+                TextureData::RgbaF16(layout.compose(cpu_textures, |d| match d {
+                    TextureData::RgbaF16(d) => Some(d),
+                    _ => None,
+                })?)
+            }
+            TextureData::RF32(_) => {
+                TextureData::RF32(layout.compose(cpu_textures, |d| match d {
+                    TextureData::RF32(d) => Some(d),
+                    _ => None,
+                })?)
+            }
+            TextureData::RgF32(_) => {
+                TextureData::RgF32(layout.compose(cpu_textures, |d| match d {
+                    TextureData::RgF32(d) => Some(d),
+                    _ => None,
+                })?)
+            }
+            TextureData::RgbF32(_) => {
+                TextureData::RgbF32(layout.compose(cpu_textures, |d| match d {
+                    TextureData::RgbF32(d) => Some(d),
+                    _ => None,
+                })?)
+            }
+            TextureData::RgbaF32(_) => {
+                TextureData::RgbaF32(layout.compose(cpu_textures, |d| match d {
+                    TextureData::RgbaF32(d) => Some(d),
+                    _ => None,
+                })?)
+            }
+            TextureData::Compressed { .. } => unreachable!(),
+        };
+        let texture = Texture2D::new(
+            context,
+            &CpuTexture {
+                data,
+                width: layout.width,
+                height: layout.height,
+                min_filter: first.min_filter,
+                mag_filter: first.mag_filter,
+                mip_map_filter: first.mip_map_filter,
+                wrap_s: Wrapping::ClampToEdge,
+                wrap_t: Wrapping::ClampToEdge,
+            },
+        )?;
+        let (width, height) = (layout.width as f32, layout.height as f32);
+        // The first row of the data is the top of the texture, ie. at v = 1
+        let uv_rects = cpu_textures
+            .iter()
+            .zip(layout.positions.iter())
+            .map(|(t, (x, y))| {
+                UvRect::new(
+                    vec2(*x as f32 / width, 1.0 - (y + t.height) as f32 / height),
+                    vec2((x + t.width) as f32 / width, 1.0 - *y as f32 / height),
+                )
+            })
+            .collect();
+        Ok(Self {
+            texture: Rc::new(texture),
+            uv_rects,
+        })
+    }
+
+    ///
+    /// Returns the texture containing all of the images.
+    ///
+    pub fn texture(&self) -> &Rc<Texture2D> {
+        &self.texture
+    }
+
+    ///
+    /// Returns the area in the atlas of each of the images in the same order as the textures given at construction.
+    ///
+    pub fn uv_rects(&self) -> &[UvRect] {
+        &self.uv_rects
+    }
+
+    ///
+    /// Returns the area in the atlas of the image at the given index in the textures given at construction.
+    ///
+    pub fn uv_rect(&self, index: usize) -> UvRect {
+        self.uv_rects[index]
+    }
+}
+
+struct Layout {
+    width: u32,
+    height: u32,
+    padding: u32,
+    // The position in pixels of the top left corner of each image, excluding the padding
+    positions: Vec<(u32, u32)>,
+}
+
+impl Layout {
+    // Packs the images in rows sorted by height
+    fn new(cpu_textures: &[CpuTexture], padding: u32) -> Self {
+        let sizes: Vec<(u32, u32)> = cpu_textures
+            .iter()
+            .map(|t| (t.width + 2 * padding, t.height + 2 * padding))
+            .collect();
+        let area: u32 = sizes.iter().map(|(w, h)| w * h).sum();
+        let max_width = sizes.iter().map(|(w, _)| *w).max().unwrap_or(1);
+        let width = max_width.max(((area as f32).sqrt().ceil() as u32).next_power_of_two());
+
+        let mut order: Vec<usize> = (0..sizes.len()).collect();
+        order.sort_by_key(|i| std::cmp::Reverse(sizes[*i].1));
+        let mut positions = vec![(0, 0); sizes.len()];
+        let (mut x, mut y, mut row_height) = (0, 0, 0);
+        for i in order {
+            let (w, h) = sizes[i];
+            if x + w > width {
+                x = 0;
+                y += row_height;
+                row_height = 0;
+            }
+            positions[i] = (x + padding, y + padding);
+            x += w;
+            row_height = row_height.max(h);
+        }
+        Self {
+            width,
+            height: (y + row_height).max(1),
+            padding,
+            positions,
+        }
+    }
+
+    fn compose<T: TextureDataType + Copy + Default>(
+        &self,
+        cpu_textures: &[CpuTexture],
+        select: impl Fn(&TextureData) -> Option<&Vec<T>>,
+    ) -> ThreeDResult<Vec<T>> {
+        let mut result = vec![T::default(); (self.width * self.height) as usize];
+        let padding = self.padding as i64;
+        for (cpu_texture, (x, y)) in cpu_textures.iter().zip(self.positions.iter()) {
+            let data = select(&cpu_texture.data).ok_or_else(|| {
+                CoreError::TextureCreation(
+                    "all textures in a texture atlas must have the same format".to_string(),
+                )
+            })?;
+            check_data_length(
+                cpu_texture.width,
+                cpu_texture.height,
+                1,
+                std::mem::size_of::<T>(),
+                data,
+            )?;
+            let (w, h) = (cpu_texture.width as i64, cpu_texture.height as i64);
+            if w == 0 || h == 0 {
+                continue;
+            }
+            // Copy the image including the padding, where the edge pixels are repeated
+            for row in -padding..h + padding {
+                for column in -padding..w + padding {
+                    let source = (row.clamp(0, h - 1) * w + column.clamp(0, w - 1)) as usize;
+                    let target = (*y as i64 + row) * self.width as i64 + *x as i64 + column;
+                    result[target as usize] = data[source];
+                }
+            }
+        }
+        Ok(result)
+    }
+}
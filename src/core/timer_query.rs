@@ -0,0 +1,180 @@
+use crate::core::*;
+
+///
+/// Measures the time the GPU spends on the render calls made in the callback given to [TimerQuery::measure].
+/// Use [Context::supports_timer_queries] to check whether timer queries are supported, which requires the `EXT_disjoint_timer_query_webgl2` extension on web.
+///
+/// The GPU executes the render calls asynchronously, so the result is not available until a later frame, see [TimerQuery::elapsed].
+/// Only one timer query can measure at a time, so calls to [TimerQuery::measure] must not be nested, see [Profiler] for nested measurements.
+///
+pub struct TimerQuery {
+    context: Context,
+    #[cfg(not(target_arch = "wasm32"))]
+    id: crate::context::Query,
+    #[cfg(target_arch = "wasm32")]
+    id: web_sys::WebGlQuery,
+    measured: std::cell::Cell<bool>,
+}
+
+// Set when the timer results are invalid, for example because the GPU changed frequency, and cleared when read
+const GPU_DISJOINT_EXT: u32 = 0x8FBB;
+
+impl TimerQuery {
+    ///
+    /// Creates a new timer query.
+    ///
+    /// # Errors
+    /// Will return an error if timer queries are not supported, see [Context::supports_timer_queries].
+    ///
+    pub fn new(context: &Context) -> ThreeDResult<Self> {
+        if !context.supports_timer_queries() {
+            Err(CoreError::TimerQueriesNotSupported)?;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        let id = unsafe { context.create_query() }.map_err(CoreError::QueryCreation)?;
+        #[cfg(target_arch = "wasm32")]
+        let id = context
+            .webgl_context
+            .as_ref()
+            .and_then(|gl| gl.create_query())
+            .ok_or_else(|| CoreError::QueryCreation("Unable to create query".to_string()))?;
+        Ok(Self {
+            context: context.clone(),
+            id,
+            measured: std::cell::Cell::new(false),
+        })
+    }
+
+    ///
+    /// Measures the GPU time spent on the render calls made in the callback.
+    /// Discards the result of any previous measurement that has not yet been read.
+    ///
+    pub fn measure<T>(&mut self, callback: impl FnOnce() -> ThreeDResult<T>) -> ThreeDResult<T> {
+        self.begin();
+        let result = callback();
+        self.end();
+        result
+    }
+
+    pub(in crate::core) fn begin(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        unsafe {
+            self.context
+                .begin_query(crate::context::TIME_ELAPSED, self.id);
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Some(gl) = self.context.webgl_context.as_ref() {
+            gl.begin_query(crate::context::TIME_ELAPSED, &self.id);
+        }
+        self.measured.set(true);
+    }
+
+    pub(in crate::core) fn end(&self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        unsafe {
+            self.context.end_query(crate::context::TIME_ELAPSED);
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Some(gl) = self.context.webgl_context.as_ref() {
+            gl.end_query(crate::context::TIME_ELAPSED);
+        }
+    }
+
+    ///
+    /// Returns the GPU time in milliseconds spent on the render calls in the last measurement,
+    /// or `None` if nothing has been measured or the result is not yet available, which is usually the case until one or two frames later.
+    /// The result is discarded, and `None` is returned, if a disjoint event, for example a change of the GPU frequency, has invalidated the measurement.
+    ///
+    pub fn elapsed(&self) -> Option<f64> {
+        self.poll().flatten()
+    }
+
+    ///
+    /// Returns `None` if the result of the last measurement is not yet available, otherwise the GPU time in milliseconds
+    /// or `None` if nothing has been measured or the measurement is discarded because of a disjoint event.
+    ///
+    pub(in crate::core) fn poll(&self) -> Option<Option<f64>> {
+        if !self.measured.get() {
+            return Some(None);
+        }
+        let (available, disjoint) = self.status();
+        if !available {
+            return None;
+        }
+        if disjoint {
+            self.measured.set(false);
+            return Some(None);
+        }
+        Some(Some(self.nanoseconds() as f64 * 1e-6))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn status(&self) -> (bool, bool) {
+        unsafe {
+            let available = self
+                .context
+                .get_query_parameter_u32(self.id, crate::context::QUERY_RESULT_AVAILABLE)
+                != 0;
+            // Disjoint events are only reported by the extension on OpenGL ES
+            let disjoint = available
+                && self.context.version().is_embedded
+                && self.context.get_parameter_i32(GPU_DISJOINT_EXT) != 0;
+            (available, disjoint)
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn nanoseconds(&self) -> u64 {
+        unsafe {
+            self.context
+                .get_query_parameter_u32(self.id, crate::context::QUERY_RESULT) as u64
+        }
+    }
+
+    // On web, the availability and the disjoint flag are booleans, which cannot be read through the graphics context
+    #[cfg(target_arch = "wasm32")]
+    fn status(&self) -> (bool, bool) {
+        self.context
+            .webgl_context
+            .as_ref()
+            .map(|gl| {
+                let available = gl
+                    .get_query_parameter(&self.id, crate::context::QUERY_RESULT_AVAILABLE)
+                    .as_bool()
+                    .unwrap_or(false);
+                let disjoint = available
+                    && gl
+                        .get_parameter(GPU_DISJOINT_EXT)
+                        .ok()
+                        .and_then(|d| d.as_bool())
+                        .unwrap_or(false);
+                (available, disjoint)
+            })
+            .unwrap_or((false, false))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn nanoseconds(&self) -> u64 {
+        self.context
+            .webgl_context
+            .as_ref()
+            .and_then(|gl| {
+                gl.get_query_parameter(&self.id, crate::context::QUERY_RESULT)
+                    .as_f64()
+            })
+            .unwrap_or(0.0) as u64
+    }
+}
+
+impl Drop for TimerQuery {
+    fn drop(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        unsafe {
+            self.context.delete_query(self.id);
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Some(gl) = self.context.webgl_context.as_ref() {
+            gl.delete_query(Some(&self.id));
+        }
+    }
+}
@@ -216,6 +216,41 @@ impl GUI {
     }
 }
 
+///
+/// Shows the measurements of a frame from a [Profiler] in the given egui [Ui](egui::Ui), for example inside a window or side panel created in the callback given to [GUI::update].
+/// Nested passes are indented below the pass they are nested inside.
+///
+pub fn profiler_ui(ui: &mut egui::Ui, frame: &ProfiledFrame) {
+    let time = |time: Option<f64>| {
+        time.map(|t| format!("{:.3} ms", t))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    ui.label(format!(
+        "GPU: {}  Draw calls: {}  Triangles: {}",
+        time(frame.gpu_time),
+        frame.statistics.draw_calls,
+        frame.statistics.triangles
+    ));
+    egui::Grid::new("profiler").striped(true).show(ui, |ui| {
+        ui.label("Pass");
+        ui.label("GPU");
+        ui.label("Draw calls");
+        ui.label("Triangles");
+        ui.label("Program changes");
+        ui.label("State changes");
+        ui.end_row();
+        for pass in frame.passes.iter() {
+            ui.label(format!("{}{}", "  ".repeat(pass.depth), pass.name));
+            ui.monospace(time(pass.gpu_time));
+            ui.monospace(pass.statistics.draw_calls.to_string());
+            ui.monospace(pass.statistics.triangles.to_string());
+            ui.monospace(pass.statistics.program_changes.to_string());
+            ui.monospace(pass.statistics.render_state_changes.to_string());
+            ui.end_row();
+        }
+    });
+}
+
 fn construct_input_state(frame_input: &mut FrameInput) -> egui::RawInput {
     let mut scroll_delta = egui::Vec2::ZERO;
     let mut egui_modifiers = egui::Modifiers::default();
@@ -305,6 +340,9 @@ fn construct_input_state(frame_input: &mut FrameInput) -> egui::RawInput {
             Event::Text(text) => {
                 egui_events.push(egui::Event::Text(text.clone()));
             }
+            Event::Clipboard(text) => {
+                egui_events.push(egui::Event::Text(text.replace("\r\n", "\n")));
+            }
             Event::MouseLeave => {
                 egui_events.push(egui::Event::PointerGone);
             }
@@ -30,6 +30,12 @@ pub enum IOError {
     #[cfg(feature = "3d-io")]
     #[error("error while parsing a .3d file")]
     ThreeD(#[from] bincode::Error),
+    #[cfg(feature = "3d-io")]
+    #[error("the file is not a .3d file")]
+    ThreeDCorruptData,
+    #[cfg(feature = "3d-io")]
+    #[error("the .3d file has version {0} but only version {1} is supported")]
+    ThreeDVersion(u32, u32),
     #[cfg(feature = "obj-io")]
     #[error("error while parsing an .obj file")]
     Obj(#[from] wavefront_obj::ParseError),
@@ -44,6 +50,40 @@ pub enum IOError {
     GltfMissingData,
     #[error("the .vol file contain wrong data size")]
     VolCorruptData,
+    #[cfg(feature = "ktx2-io")]
+    #[error("error while parsing a .ktx2 file")]
+    Ktx2(#[from] ktx2::ParseError),
+    #[cfg(feature = "ktx2-io")]
+    #[error("the .ktx2 file is not supported: {0}")]
+    Ktx2NotSupported(String),
+    #[error("the .dds file contain corrupt data")]
+    DdsCorruptData,
+    #[error("the .dds file is not supported: {0}")]
+    DdsNotSupported(String),
+    #[error("the .stl file contain corrupt data")]
+    StlCorruptData,
+    #[error("the .ply file contain corrupt data: {0}")]
+    PlyCorruptData(String),
+    #[error("the .xyz file contain corrupt data")]
+    XyzCorruptData,
+    #[cfg(feature = "volume-io")]
+    #[error("the .nrrd file contain corrupt data: {0}")]
+    NrrdCorruptData(String),
+    #[cfg(feature = "volume-io")]
+    #[error("the .nrrd file is not supported: {0}")]
+    NrrdNotSupported(String),
+    #[cfg(feature = "volume-io")]
+    #[error("the .mhd file contain corrupt data: {0}")]
+    MhdCorruptData(String),
+    #[cfg(feature = "volume-io")]
+    #[error("the .mhd file is not supported: {0}")]
+    MhdNotSupported(String),
+    #[cfg(feature = "volume-io")]
+    #[error("the DICOM file contain corrupt data: {0}")]
+    DicomCorruptData(String),
+    #[cfg(feature = "volume-io")]
+    #[error("the DICOM file is not supported: {0}")]
+    DicomNotSupported(String),
     #[cfg(not(target_arch = "wasm32"))]
     #[error("error while loading the file {0}: {1}")]
     FailedLoading(String, std::io::Error),
@@ -127,6 +127,13 @@ impl Loaded {
     pub fn insert_bytes(&mut self, path: impl AsRef<Path>, bytes: Vec<u8>) {
         self.loaded.insert(path.as_ref().to_path_buf(), bytes);
     }
+
+    ///
+    /// Returns the paths of all the loaded files.
+    ///
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.loaded.keys().map(|p| p.as_path())
+    }
 }
 
 impl std::fmt::Debug for Loaded {
@@ -171,6 +178,7 @@ impl Loader {
     /// Parallel loads all of the resources in the given paths from disk and returns the [Loaded] resources.
     ///
     /// This only loads resources from disk, if downloading resources from URLs is also needed, use the [Loader::load_async] method instead.
+    /// A path to a directory loads all the files in the directory, for example a DICOM series.
     ///
     #[cfg_attr(docsrs, doc(not(target_arch = "wasm32")))]
     #[cfg(not(target_arch = "wasm32"))]
@@ -236,8 +244,24 @@ impl Loader {
 
 #[cfg(not(target_arch = "wasm32"))]
 fn load_from_disk(mut paths: Vec<PathBuf>, loaded: &mut Loaded) -> ThreeDResult<()> {
-    let mut handles = Vec::new();
+    // A directory is loaded as all the files in the directory
+    let mut files = Vec::new();
     for path in paths.drain(..) {
+        if path.is_dir() {
+            let error = |e| IOError::FailedLoading(path.to_str().unwrap().to_string(), e);
+            for entry in std::fs::read_dir(&path).map_err(error)? {
+                let file = entry.map_err(error)?.path();
+                if file.is_file() {
+                    files.push(file);
+                }
+            }
+        } else {
+            files.push(path);
+        }
+    }
+
+    let mut handles = Vec::new();
+    for path in files.drain(..) {
         handles.push((
             path.clone(),
             std::thread::spawn(move || std::fs::read(path)),
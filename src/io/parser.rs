@@ -22,3 +22,61 @@ pub use img::*;
 mod vol;
 #[doc(inline)]
 pub use vol::*;
+
+#[cfg(feature = "volume-io")]
+mod voxels;
+
+#[cfg(feature = "volume-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "volume-io")))]
+mod nrrd;
+#[cfg(feature = "volume-io")]
+#[doc(inline)]
+pub use nrrd::*;
+
+#[cfg(feature = "volume-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "volume-io")))]
+mod mhd;
+#[cfg(feature = "volume-io")]
+#[doc(inline)]
+pub use mhd::*;
+
+#[cfg(feature = "volume-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "volume-io")))]
+mod dicom;
+#[cfg(feature = "volume-io")]
+#[doc(inline)]
+pub use dicom::*;
+
+#[cfg(feature = "ktx2-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ktx2-io")))]
+mod ktx2;
+#[cfg(feature = "ktx2-io")]
+#[doc(inline)]
+pub use self::ktx2::*;
+
+mod dds;
+#[doc(inline)]
+pub use dds::*;
+
+mod stl;
+#[doc(inline)]
+pub use stl::*;
+
+mod ply;
+#[doc(inline)]
+pub use ply::*;
+
+mod xyz;
+#[doc(inline)]
+pub use xyz::*;
+
+#[cfg(feature = "3d-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "3d-io")))]
+mod three_d;
+#[cfg(feature = "3d-io")]
+#[doc(inline)]
+pub use three_d::*;
+
+#[cfg(feature = "text")]
+#[cfg_attr(docsrs, doc(cfg(feature = "text")))]
+mod font;
@@ -0,0 +1,113 @@
+use crate::core::*;
+use crate::io::*;
+use std::path::Path;
+
+///
+/// Deserialize the given bytes representing a DDS file with block compressed data into a [CpuTexture]
+/// with [TextureData::Compressed] data including all mip levels.
+/// Supported formats: BC1 (DXT1), BC2 (DXT2 and DXT3), BC3 (DXT4 and DXT5), BC4, BC5 and BC7 (only with the DX10 header).
+///
+/// **Note:** Only 2D textures are supported, ie. not cube maps, arrays or volume textures.
+/// Uncompressed DDS files can be loaded using `image_from_bytes` instead, which requires the `image-io` feature.
+///
+pub fn dds_from_bytes(bytes: &[u8]) -> ThreeDResult<CpuTexture> {
+    let read = |offset: usize| -> ThreeDResult<u32> {
+        Ok(u32::from_le_bytes(
+            bytes
+                .get(offset..offset + 4)
+                .ok_or(IOError::DdsCorruptData)?
+                .try_into()
+                .unwrap(),
+        ))
+    };
+    if bytes.get(0..4) != Some(b"DDS ") || read(4)? != 124 {
+        Err(IOError::DdsCorruptData)?;
+    }
+    let flags = read(8)?;
+    let height = read(12)?;
+    let width = read(16)?;
+    let mip_map_count = if flags & 0x20000 != 0 {
+        read(28)?.max(1)
+    } else {
+        1
+    };
+    if mip_map_count > 32 - width.max(height).leading_zeros() {
+        Err(IOError::DdsCorruptData)?;
+    }
+    let pixel_format_flags = read(80)?;
+    let caps2 = read(112)?;
+    let four_cc = bytes[84..88].to_vec();
+    if caps2 & 0x200 != 0 || caps2 & 0x200000 != 0 {
+        Err(IOError::DdsNotSupported(
+            "only 2D textures are supported".to_string(),
+        ))?;
+    }
+    if pixel_format_flags & 0x4 == 0 {
+        Err(IOError::DdsNotSupported(
+            "only block compressed data is supported".to_string(),
+        ))?;
+    }
+
+    let mut offset = 128;
+    let format = match &four_cc[..] {
+        b"DXT1" => CompressedFormat::Bc1Rgba,
+        b"DXT2" | b"DXT3" => CompressedFormat::Bc2,
+        b"DXT4" | b"DXT5" => CompressedFormat::Bc3,
+        b"ATI1" | b"BC4U" => CompressedFormat::Bc4,
+        b"ATI2" | b"BC5U" => CompressedFormat::Bc5,
+        b"DX10" => {
+            let dxgi_format = read(128)?;
+            // Texture 2D resource dimension, not a cube map and a single image
+            if read(132)? != 3 || read(136)? & 0x4 != 0 || read(140)? > 1 {
+                Err(IOError::DdsNotSupported(
+                    "only 2D textures are supported".to_string(),
+                ))?;
+            }
+            offset += 20;
+            match dxgi_format {
+                70..=72 => CompressedFormat::Bc1Rgba,
+                73..=75 => CompressedFormat::Bc2,
+                76..=78 => CompressedFormat::Bc3,
+                79 | 80 => CompressedFormat::Bc4,
+                82 | 83 => CompressedFormat::Bc5,
+                97..=99 => CompressedFormat::Bc7,
+                _ => Err(IOError::DdsNotSupported(format!(
+                    "the DXGI format {} is not supported",
+                    dxgi_format
+                )))?,
+            }
+        }
+        _ => Err(IOError::DdsNotSupported(format!(
+            "the format {} is not supported",
+            String::from_utf8_lossy(&four_cc)
+        )))?,
+    };
+
+    let mut mip_levels = Vec::new();
+    for level in 0..mip_map_count {
+        let size = format.byte_size((width >> level).max(1), (height >> level).max(1));
+        mip_levels.push(
+            bytes
+                .get(offset..offset + size)
+                .ok_or(IOError::DdsCorruptData)?
+                .to_vec(),
+        );
+        offset += size;
+    }
+    Ok(CpuTexture {
+        data: TextureData::Compressed { format, mip_levels },
+        width,
+        height,
+        ..Default::default()
+    })
+}
+
+impl Loaded {
+    ///
+    /// Deserialize the loaded .dds file at the given path into a [CpuTexture], see [dds_from_bytes] for the supported formats.
+    /// The CpuTexture can then be used to create a [Texture2D].
+    ///
+    pub fn dds(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuTexture> {
+        dds_from_bytes(self.get_bytes(path)?)
+    }
+}
@@ -0,0 +1,426 @@
+use super::voxels::*;
+use crate::core::*;
+use crate::io::*;
+use std::path::Path;
+
+impl Loaded {
+    ///
+    /// Deserialize a DICOM series, ie. all the loaded DICOM files in the given directory where each file contains one slice of the volume, into a [CpuVolume].
+    /// On desktop, all the files in a directory can be loaded by giving the path to the directory to the [Loader].
+    /// A single multi-frame DICOM file, where each frame is a slice, is also supported. Loaded files in the directory that are not DICOM files or do not contain an image are ignored.
+    ///
+    /// The slices are sorted by their position along the normal of the slices (or by the instance number if the position is not given),
+    /// and the pixel spacing and the distance between the slices are used to compute the [CpuVolume::size].
+    /// The rescale slope and intercept are applied to the pixel values, for example to get Hounsfield units for CT data,
+    /// after which the values are scaled to the range `[0..1]` using the smallest and largest value in the volume, unless all slices contain unscaled 8-bit values.
+    /// The original range of the values, for example in Hounsfield units, is stored in the [CpuVolume::value_range].
+    ///
+    /// **Note:** Only uncompressed (implicit or explicit VR little endian and deflated explicit VR little endian) grayscale images are supported.
+    ///
+    pub fn dicom_series(&mut self, directory: impl AsRef<Path>) -> ThreeDResult<CpuVolume> {
+        let mut paths: Vec<_> = self
+            .paths()
+            .filter(|p| p.starts_with(directory.as_ref()))
+            .map(|p| p.to_path_buf())
+            .collect();
+        paths.sort();
+        let mut slices = Vec::new();
+        for path in paths {
+            if let Some(slice) = dicom_slice(self.get_bytes(path)?)? {
+                slices.push(slice);
+            }
+        }
+        if slices.is_empty() {
+            Err(IOError::DicomCorruptData(
+                "no DICOM images found".to_string(),
+            ))?;
+        }
+        let (rows, columns) = (slices[0].rows, slices[0].columns);
+        if slices
+            .iter()
+            .any(|s| s.rows != rows || s.columns != columns)
+        {
+            Err(IOError::DicomCorruptData(
+                "the slices have different sizes".to_string(),
+            ))?;
+        }
+
+        // Sort the slices by their position along the slice normal
+        let normal = slices[0].orientation.map(|o| {
+            vec3(o[0], o[1], o[2])
+                .cross(vec3(o[3], o[4], o[5]))
+                .normalize()
+        });
+        let distances: Option<Vec<f32>> = normal.and_then(|normal| {
+            slices
+                .iter()
+                .map(|s| s.position.map(|p| p.dot(normal)))
+                .collect()
+        });
+        let mut slice_spacing = None;
+        if let Some(distances) = distances {
+            let mut order: Vec<usize> = (0..slices.len()).collect();
+            order.sort_by(|a, b| distances[*a].total_cmp(&distances[*b]));
+            if slices.len() > 1 {
+                let range = distances[order[order.len() - 1]] - distances[order[0]];
+                slice_spacing = Some(range / (slices.len() - 1) as f32).filter(|s| *s > 0.0);
+            }
+            let mut sorted: Vec<Option<DicomSlice>> = slices.into_iter().map(Some).collect();
+            slices = order.iter().map(|i| sorted[*i].take().unwrap()).collect();
+        } else {
+            slices.sort_by_key(|s| s.instance_number);
+        }
+        let first = &slices[0];
+        let slice_spacing = slice_spacing
+            .or(first.spacing_between_slices)
+            .or(first.slice_thickness)
+            .unwrap_or(1.0);
+        let spacing = vec3(first.pixel_spacing.1, first.pixel_spacing.0, slice_spacing);
+        let is_u8 = slices
+            .iter()
+            .all(|s| s.voxel_type == VoxelType::U8 && s.slope == 1.0 && s.intercept == 0.0);
+        let corrupt = || IOError::DicomCorruptData("too many slices".to_string());
+        let depth = slices
+            .iter()
+            .try_fold(0u32, |depth, s| depth.checked_add(s.frames))
+            .ok_or_else(corrupt)?;
+        let mut values =
+            Vec::with_capacity(voxel_count(&[columns, rows, depth]).ok_or_else(corrupt)?);
+        for slice in slices {
+            values.extend(
+                slice
+                    .pixels
+                    .iter()
+                    .map(|v| v * slice.slope + slice.intercept),
+            );
+        }
+
+        Ok(cpu_volume(
+            directory
+                .as_ref()
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            values,
+            is_u8,
+            [columns, rows, depth],
+            spacing,
+        ))
+    }
+}
+
+struct DicomSlice {
+    rows: u32,
+    columns: u32,
+    frames: u32,
+    voxel_type: VoxelType,
+    // The spacing between the rows and between the columns
+    pixel_spacing: (f32, f32),
+    slice_thickness: Option<f32>,
+    spacing_between_slices: Option<f32>,
+    position: Option<Vec3>,
+    orientation: Option<[f32; 6]>,
+    instance_number: i32,
+    slope: f32,
+    intercept: f32,
+    pixels: Vec<f32>,
+}
+
+const UNDEFINED_LENGTH: u32 = 0xFFFFFFFF;
+const ITEM: (u16, u16) = (0xFFFE, 0xE000);
+const ITEM_DELIMITATION: (u16, u16) = (0xFFFE, 0xE00D);
+const SEQUENCE_DELIMITATION: (u16, u16) = (0xFFFE, 0xE0DD);
+
+///
+/// Parses a single DICOM file. Returns `None` if the bytes do not represent a DICOM file or if the file does not contain an image.
+///
+fn dicom_slice(bytes: &[u8]) -> ThreeDResult<Option<DicomSlice>> {
+    if bytes.len() < 132 || &bytes[128..132] != b"DICM" {
+        return Ok(None);
+    }
+    // The file meta information is always explicit VR little endian
+    let mut reader = DicomReader {
+        bytes,
+        position: 132,
+        explicit: true,
+    };
+    let mut transfer_syntax = String::new();
+    while reader.peek_group() == Some(0x0002) {
+        let (tag, value) = reader.read_element()?;
+        if tag == (0x0002, 0x0010) {
+            transfer_syntax = string(value.unwrap_or(&[]));
+        }
+    }
+    let inflated;
+    reader = match transfer_syntax.as_str() {
+        "1.2.840.10008.1.2" => DicomReader {
+            explicit: false,
+            ..reader
+        },
+        "1.2.840.10008.1.2.1" => reader,
+        "1.2.840.10008.1.2.1.99" => {
+            use std::io::Read;
+            let mut data = Vec::new();
+            flate2::read::DeflateDecoder::new(&bytes[reader.position..])
+                .read_to_end(&mut data)
+                .map_err(|e| IOError::DicomCorruptData(e.to_string()))?;
+            inflated = data;
+            DicomReader {
+                bytes: &inflated,
+                position: 0,
+                explicit: true,
+            }
+        }
+        _ => Err(IOError::DicomNotSupported(format!(
+            "the transfer syntax {}",
+            transfer_syntax
+        )))?,
+    };
+
+    let mut rows = 0;
+    let mut columns = 0;
+    let mut frames = 1;
+    let mut samples_per_pixel = 1;
+    let mut bits_allocated = 16;
+    let mut pixel_representation = 0;
+    let mut pixel_spacing = (1.0, 1.0);
+    let mut slice_thickness = None;
+    let mut spacing_between_slices = None;
+    let mut position = None;
+    let mut orientation = None;
+    let mut instance_number = 0;
+    let mut slope = 1.0;
+    let mut intercept = 0.0;
+    let mut pixel_data = None;
+    while reader.position < reader.bytes.len() {
+        let (tag, value) = reader.read_element()?;
+        let value = match value {
+            Some(value) => value,
+            None if tag == (0x7FE0, 0x0010) => Err(IOError::DicomNotSupported(
+                "compressed pixel data".to_string(),
+            ))?,
+            None => continue,
+        };
+        let numbers = || -> Vec<f32> {
+            string(value)
+                .split('\\')
+                .filter_map(|v| v.trim().parse().ok())
+                .collect()
+        };
+        let number = || numbers().first().copied();
+        match tag {
+            (0x0028, 0x0010) => rows = reader.u16(value) as u32,
+            (0x0028, 0x0011) => columns = reader.u16(value) as u32,
+            (0x0028, 0x0008) => frames = number().unwrap_or(1.0) as u32,
+            (0x0028, 0x0002) => samples_per_pixel = reader.u16(value),
+            (0x0028, 0x0100) => bits_allocated = reader.u16(value),
+            (0x0028, 0x0103) => pixel_representation = reader.u16(value),
+            (0x0028, 0x0030) => {
+                if let [row, column] = numbers()[..] {
+                    pixel_spacing = (row, column);
+                }
+            }
+            (0x0018, 0x0050) => slice_thickness = number(),
+            (0x0018, 0x0088) => spacing_between_slices = number(),
+            (0x0020, 0x0032) => {
+                if let [x, y, z] = numbers()[..] {
+                    position = Some(vec3(x, y, z));
+                }
+            }
+            (0x0020, 0x0037) => {
+                if let [a, b, c, d, e, f] = numbers()[..] {
+                    orientation = Some([a, b, c, d, e, f]);
+                }
+            }
+            (0x0020, 0x0013) => instance_number = number().unwrap_or(0.0) as i32,
+            (0x0028, 0x1053) => slope = number().unwrap_or(1.0),
+            (0x0028, 0x1052) => intercept = number().unwrap_or(0.0),
+            (0x7FE0, 0x0010) => pixel_data = Some(value),
+            _ => {}
+        }
+    }
+    let pixel_data = match pixel_data {
+        Some(pixel_data) => pixel_data,
+        None => return Ok(None),
+    };
+    if samples_per_pixel != 1 {
+        Err(IOError::DicomNotSupported(
+            "only grayscale images are supported".to_string(),
+        ))?;
+    }
+    let voxel_type = match (bits_allocated, pixel_representation) {
+        (8, 0) => VoxelType::U8,
+        (8, _) => VoxelType::I8,
+        (16, 0) => VoxelType::U16,
+        (16, _) => VoxelType::I16,
+        (32, 0) => VoxelType::U32,
+        (32, _) => VoxelType::I32,
+        _ => Err(IOError::DicomNotSupported(format!(
+            "{} bits allocated",
+            bits_allocated
+        )))?,
+    };
+    let count = voxel_count(&[columns, rows, frames])
+        .ok_or_else(|| IOError::DicomCorruptData("image size".to_string()))?;
+    let pixels = decode_voxels(pixel_data, voxel_type, false, count)
+        .ok_or_else(|| IOError::DicomCorruptData("not enough pixel data".to_string()))?;
+    Ok(Some(DicomSlice {
+        rows,
+        columns,
+        frames,
+        voxel_type,
+        pixel_spacing,
+        slice_thickness,
+        spacing_between_slices,
+        position,
+        orientation,
+        instance_number,
+        slope,
+        intercept,
+        pixels,
+    }))
+}
+
+fn string(value: &[u8]) -> String {
+    String::from_utf8_lossy(value)
+        .trim_end_matches(['\0', ' '])
+        .trim()
+        .to_string()
+}
+
+// The tag, ie. the group and element number, and the value of a data element
+type DicomElement<'a> = ((u16, u16), Option<&'a [u8]>);
+
+struct DicomReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    explicit: bool,
+}
+
+impl<'a> DicomReader<'a> {
+    fn u16(&self, value: &[u8]) -> u16 {
+        value
+            .get(0..2)
+            .map(|v| u16::from_le_bytes([v[0], v[1]]))
+            .unwrap_or(0)
+    }
+
+    fn u32_at(&self, position: usize) -> ThreeDResult<u32> {
+        let v = self
+            .bytes
+            .get(position..position + 4)
+            .ok_or_else(|| IOError::DicomCorruptData("unexpected end of file".to_string()))?;
+        Ok(u32::from_le_bytes([v[0], v[1], v[2], v[3]]))
+    }
+
+    fn peek_group(&self) -> Option<u16> {
+        self.bytes
+            .get(self.position..self.position + 2)
+            .map(|v| u16::from_le_bytes([v[0], v[1]]))
+    }
+
+    fn read_tag(&mut self) -> ThreeDResult<(u16, u16)> {
+        let tag = self.u32_at(self.position)?;
+        self.position += 4;
+        Ok(((tag & 0xFFFF) as u16, (tag >> 16) as u16))
+    }
+
+    ///
+    /// Reads the next data element and returns the tag and the value. The value is `None` if the element has undefined length,
+    /// in which case the content is skipped unless it is the pixel data.
+    ///
+    fn read_element(&mut self) -> ThreeDResult<DicomElement<'a>> {
+        let tag = self.read_tag()?;
+        let length = if tag.0 == 0xFFFE {
+            let length = self.u32_at(self.position)?;
+            self.position += 4;
+            length
+        } else if self.explicit {
+            let vr = self
+                .bytes
+                .get(self.position..self.position + 2)
+                .unwrap_or(&[]);
+            if matches!(
+                vr,
+                b"OB"
+                    | b"OD"
+                    | b"OF"
+                    | b"OL"
+                    | b"OV"
+                    | b"OW"
+                    | b"SQ"
+                    | b"SV"
+                    | b"UC"
+                    | b"UN"
+                    | b"UR"
+                    | b"UT"
+                    | b"UV"
+            ) {
+                let length = self.u32_at(self.position + 4)?;
+                self.position += 8;
+                length
+            } else {
+                let v = self
+                    .bytes
+                    .get(self.position + 2..self.position + 4)
+                    .ok_or_else(|| {
+                        IOError::DicomCorruptData("unexpected end of file".to_string())
+                    })?;
+                self.position += 4;
+                u16::from_le_bytes([v[0], v[1]]) as u32
+            }
+        } else {
+            let length = self.u32_at(self.position)?;
+            self.position += 4;
+            length
+        };
+        if length == UNDEFINED_LENGTH {
+            if tag != (0x7FE0, 0x0010) {
+                self.skip_sequence()?;
+            }
+            return Ok((tag, None));
+        }
+        let value = self
+            .position
+            .checked_add(length as usize)
+            .and_then(|end| self.bytes.get(self.position..end))
+            .ok_or_else(|| IOError::DicomCorruptData("unexpected end of file".to_string()))?;
+        let end = self.position + value.len();
+        self.position = end;
+        Ok((tag, Some(value)))
+    }
+
+    ///
+    /// Skips the content of a sequence with undefined length, including any nested sequences.
+    ///
+    fn skip_sequence(&mut self) -> ThreeDResult<()> {
+        loop {
+            let tag = self.read_tag()?;
+            let length = self.u32_at(self.position)?;
+            self.position += 4;
+            match tag {
+                SEQUENCE_DELIMITATION => return Ok(()),
+                ITEM if length == UNDEFINED_LENGTH => loop {
+                    if self.bytes.get(self.position..self.position + 4)
+                        == Some(&[0xFE, 0xFF, 0x0D, 0xE0])
+                    {
+                        self.read_tag()?;
+                        self.position += 4;
+                        break;
+                    }
+                    self.read_element()?;
+                },
+                ITEM | ITEM_DELIMITATION => {
+                    self.position = self
+                        .position
+                        .checked_add(length as usize)
+                        .filter(|end| *end <= self.bytes.len())
+                        .ok_or_else(|| {
+                            IOError::DicomCorruptData("unexpected end of file".to_string())
+                        })?
+                }
+                _ => Err(IOError::DicomCorruptData("invalid sequence".to_string()))?,
+            }
+        }
+    }
+}
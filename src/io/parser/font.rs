@@ -0,0 +1,12 @@
+use crate::core::*;
+use crate::io::*;
+use std::path::Path;
+
+impl Loaded {
+    ///
+    /// Deserialize a loaded TrueType (.ttf) or OpenType (.otf) font file into a [CpuFont].
+    ///
+    pub fn font(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuFont> {
+        CpuFont::new(self.remove_bytes(path.as_ref())?)
+    }
+}
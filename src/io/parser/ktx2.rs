@@ -0,0 +1,358 @@
+use crate::core::*;
+use crate::io::*;
+use ::ktx2::{Format, SupercompressionScheme};
+use std::path::Path;
+
+///
+/// Deserialize the given bytes representing a KTX2 file into a [CpuTexture] using the [ktx2](https://crates.io/crates/ktx2) crate.
+/// Block compressed formats (BCn, ETC2, EAC and ASTC) are returned as [TextureData::Compressed] including all mip levels,
+/// while 8-bit, 16-bit float and 32-bit float uncompressed formats are returned as the corresponding uncompressed texture data.
+/// Zstandard supercompression is supported.
+///
+/// Basis Universal textures (ETC1S and UASTC) are transcoded to uncompressed RGBA data with 8 bits per channel
+/// using the [basis-universal](https://crates.io/crates/basis-universal) crate.
+///
+/// **Note:** Transcoding Basis Universal textures is not supported on web.
+/// Only 2D textures are supported, ie. not cube maps, arrays or 3D textures.
+///
+pub fn ktx2_from_bytes(bytes: &[u8]) -> ThreeDResult<CpuTexture> {
+    let reader = ::ktx2::Reader::new(bytes).map_err(IOError::from)?;
+    let header = reader.header();
+    if header.pixel_depth > 1 || header.layer_count > 1 || header.face_count > 1 {
+        Err(IOError::Ktx2NotSupported(
+            "only 2D textures are supported".to_string(),
+        ))?;
+    }
+    let format = match header.format {
+        Some(format) => format,
+        None => return transcode_basis_universal(&reader),
+    };
+    let levels = decompress_levels(&reader)?;
+
+    let compressed_format = match format {
+        Format::BC1_RGB_UNORM_BLOCK | Format::BC1_RGB_SRGB_BLOCK => Some(CompressedFormat::Bc1Rgb),
+        Format::BC1_RGBA_UNORM_BLOCK | Format::BC1_RGBA_SRGB_BLOCK => {
+            Some(CompressedFormat::Bc1Rgba)
+        }
+        Format::BC2_UNORM_BLOCK | Format::BC2_SRGB_BLOCK => Some(CompressedFormat::Bc2),
+        Format::BC3_UNORM_BLOCK | Format::BC3_SRGB_BLOCK => Some(CompressedFormat::Bc3),
+        Format::BC4_UNORM_BLOCK => Some(CompressedFormat::Bc4),
+        Format::BC5_UNORM_BLOCK => Some(CompressedFormat::Bc5),
+        Format::BC7_UNORM_BLOCK | Format::BC7_SRGB_BLOCK => Some(CompressedFormat::Bc7),
+        Format::ETC2_R8G8B8_UNORM_BLOCK | Format::ETC2_R8G8B8_SRGB_BLOCK => {
+            Some(CompressedFormat::Etc2Rgb)
+        }
+        Format::ETC2_R8G8B8A1_UNORM_BLOCK | Format::ETC2_R8G8B8A1_SRGB_BLOCK => {
+            Some(CompressedFormat::Etc2RgbA1)
+        }
+        Format::ETC2_R8G8B8A8_UNORM_BLOCK | Format::ETC2_R8G8B8A8_SRGB_BLOCK => {
+            Some(CompressedFormat::Etc2Rgba)
+        }
+        Format::EAC_R11_UNORM_BLOCK => Some(CompressedFormat::EacR11),
+        Format::EAC_R11G11_UNORM_BLOCK => Some(CompressedFormat::EacRg11),
+        _ => {
+            // The ASTC formats are ordered by block size with a linear and an sRGB variant of each
+            let value = format.0.get();
+            if (157..=184).contains(&value) {
+                let (block_width, block_height) = [
+                    (4, 4),
+                    (5, 4),
+                    (5, 5),
+                    (6, 5),
+                    (6, 6),
+                    (8, 5),
+                    (8, 6),
+                    (8, 8),
+                    (10, 5),
+                    (10, 6),
+                    (10, 8),
+                    (10, 10),
+                    (12, 10),
+                    (12, 12),
+                ][(value as usize - 157) / 2];
+                Some(CompressedFormat::Astc {
+                    block_width,
+                    block_height,
+                })
+            } else {
+                None
+            }
+        }
+    };
+
+    let data = if let Some(format) = compressed_format {
+        TextureData::Compressed {
+            format,
+            mip_levels: levels,
+        }
+    } else {
+        let bytes = levels.into_iter().next().unwrap_or_default();
+        match format {
+            Format::R8_UNORM | Format::R8_SRGB => TextureData::RU8(bytes),
+            Format::R8G8_UNORM | Format::R8G8_SRGB => {
+                TextureData::RgU8(bytes.chunks_exact(2).map(|c| [c[0], c[1]]).collect())
+            }
+            Format::R8G8B8_UNORM | Format::R8G8B8_SRGB => {
+                TextureData::RgbU8(bytes.chunks_exact(3).map(|c| [c[0], c[1], c[2]]).collect())
+            }
+            Format::R8G8B8A8_UNORM | Format::R8G8B8A8_SRGB => TextureData::RgbaU8(
+                bytes
+                    .chunks_exact(4)
+                    .map(|c| [c[0], c[1], c[2], c[3]])
+                    .collect(),
+            ),
+            Format::R16G16B16A16_SFLOAT => TextureData::RgbaF16(
+                bytes
+                    .chunks_exact(8)
+                    .map(|c| {
+                        [0, 2, 4, 6].map(|i| f16::from_bits(u16::from_le_bytes([c[i], c[i + 1]])))
+                    })
+                    .collect(),
+            ),
+            Format::R32G32B32A32_SFLOAT => TextureData::RgbaF32(
+                bytes
+                    .chunks_exact(16)
+                    .map(|c| {
+                        [0, 4, 8, 12]
+                            .map(|i| f32::from_le_bytes([c[i], c[i + 1], c[i + 2], c[i + 3]]))
+                    })
+                    .collect(),
+            ),
+            _ => Err(IOError::Ktx2NotSupported(format!(
+                "the format {:?} is not supported",
+                format
+            )))?,
+        }
+    };
+    Ok(CpuTexture {
+        data,
+        width: header.pixel_width,
+        height: header.pixel_height.max(1),
+        ..Default::default()
+    })
+}
+
+fn decompress_levels(reader: &::ktx2::Reader<&[u8]>) -> ThreeDResult<Vec<Vec<u8>>> {
+    let mut levels = Vec::new();
+    for level in reader.levels() {
+        levels.push(match reader.header().supercompression_scheme {
+            None => level.to_vec(),
+            Some(SupercompressionScheme::Zstandard) => {
+                use std::io::Read;
+                let mut decoder = ruzstd::StreamingDecoder::new(level).map_err(|e| {
+                    IOError::Ktx2NotSupported(format!("invalid Zstandard data: {}", e))
+                })?;
+                let mut data = Vec::new();
+                decoder.read_to_end(&mut data).map_err(|e| {
+                    IOError::Ktx2NotSupported(format!("invalid Zstandard data: {}", e))
+                })?;
+                data
+            }
+            Some(scheme) => Err(IOError::Ktx2NotSupported(format!(
+                "the supercompression scheme {:?} is not supported",
+                scheme
+            )))?,
+        });
+    }
+    Ok(levels)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn transcode_basis_universal(_reader: &::ktx2::Reader<&[u8]>) -> ThreeDResult<CpuTexture> {
+    Err(IOError::Ktx2NotSupported(
+        "transcoding Basis Universal textures is not supported on web".to_string(),
+    ))?
+}
+
+///
+/// Transcodes the first mip level of a Basis Universal texture, which is either ETC1S with BasisLZ supercompression or UASTC, to RGBA data.
+///
+#[cfg(not(target_arch = "wasm32"))]
+fn transcode_basis_universal(reader: &::ktx2::Reader<&[u8]>) -> ThreeDResult<CpuTexture> {
+    use basis_universal::*;
+    let header = reader.header();
+    let (width, height) = (header.pixel_width, header.pixel_height.max(1));
+    let basis = basis_from_ktx2(reader)?;
+    let mut transcoder = Transcoder::new();
+    transcoder
+        .prepare_transcoding(&basis)
+        .map_err(|_| IOError::Ktx2NotSupported("invalid Basis Universal data".to_string()))?;
+    let rgba = transcoder
+        .transcode_image_level(
+            &basis,
+            TranscoderTextureFormat::RGBA32,
+            TranscodeParameters::default(),
+        )
+        .map_err(|e| IOError::Ktx2NotSupported(format!("failed transcoding: {:?}", e)))?;
+    Ok(CpuTexture {
+        data: TextureData::RgbaU8(
+            rgba.chunks_exact(4)
+                .take((width * height) as usize)
+                .map(|c| [c[0], c[1], c[2], c[3]])
+                .collect(),
+        ),
+        width,
+        height,
+        ..Default::default()
+    })
+}
+
+///
+/// Converts a KTX2 texture with either ETC1S data and BasisLZ supercompression or UASTC data into a .basis file,
+/// since the codebooks, Huffman tables and slices are stored in the same way in a .basis file.
+///
+#[cfg(not(target_arch = "wasm32"))]
+fn basis_from_ktx2(reader: &::ktx2::Reader<&[u8]>) -> ThreeDResult<Vec<u8>> {
+    const HEADER_SIZE: usize = 77;
+    const SLICE_DESC_SIZE: usize = 23;
+    let header = reader.header();
+    let (width, height) = (header.pixel_width, header.pixel_height.max(1));
+    let level_size = |level_index: usize| {
+        (
+            (width >> level_index).max(1),
+            (height >> level_index).max(1),
+        )
+    };
+    let is_etc1s = header.supercompression_scheme == Some(SupercompressionScheme::BasisLZ);
+    let mut slices = Vec::new();
+    let mut codebook_counts = [0, 0];
+    let mut codebook_lengths = [0, 0, 0];
+    let mut codebooks = &[][..];
+    let has_alpha = if is_etc1s {
+        let global = reader.supercompression_global_data();
+        let invalid = || IOError::Ktx2NotSupported("invalid BasisLZ global data".to_string());
+        let read = |offset: usize, size: usize| -> ThreeDResult<u32> {
+            let bytes = global.get(offset..offset + size).ok_or_else(invalid)?;
+            Ok(bytes
+                .iter()
+                .rev()
+                .fold(0, |value, byte| (value << 8) | *byte as u32))
+        };
+        codebook_counts = [read(0, 2)?, read(2, 2)?];
+        codebook_lengths = [read(4, 4)?, read(8, 4)?, read(12, 4)?].map(|l| l as usize);
+        let codebooks_start = 20 + 20 * reader.levels().len();
+        codebooks = global
+            .get(codebooks_start..codebooks_start + codebook_lengths.iter().sum::<usize>())
+            .ok_or_else(invalid)?;
+        for (level_index, level) in reader.levels().enumerate() {
+            let desc = 20 + 20 * level_index;
+            let (level_width, level_height) = level_size(level_index);
+            for (offset, length, flags) in [(4, 8, 0), (12, 16, 1)] {
+                let (offset, length) = (
+                    read(desc + offset, 4)? as usize,
+                    read(desc + length, 4)? as usize,
+                );
+                if flags == 1 && length == 0 {
+                    continue;
+                }
+                let data = level.get(offset..offset + length).ok_or_else(invalid)?;
+                slices.push((level_index, level_width, level_height, flags, data.to_vec()));
+            }
+        }
+        slices.iter().any(|slice| slice.3 == 1)
+    } else {
+        let descriptor = reader
+            .data_format_descriptors()
+            .find_map(|descriptor| ::ktx2::BasicDataFormatDescriptor::parse(descriptor.data).ok())
+            .filter(|descriptor| descriptor.color_model == Some(::ktx2::ColorModel::UASTC))
+            .ok_or_else(|| {
+                IOError::Ktx2NotSupported(
+                    "the texture has no format and is not a Basis Universal texture".to_string(),
+                )
+            })?;
+        for (level_index, level) in decompress_levels(reader)?.into_iter().enumerate() {
+            let (level_width, level_height) = level_size(level_index);
+            slices.push((level_index, level_width, level_height, 0, level));
+        }
+        // The channel type of UASTC textures is either RGB (0), RGBA (3), RRR (4) or RRRG (5)
+        descriptor
+            .sample_information()
+            .next()
+            .is_some_and(|sample| sample.channel_type == 3 || sample.channel_type == 5)
+    };
+
+    let endpoints_offset = HEADER_SIZE + SLICE_DESC_SIZE * slices.len();
+    let selectors_offset = endpoints_offset + codebook_lengths[0];
+    let tables_offset = selectors_offset + codebook_lengths[1];
+    let mut data = Vec::new();
+    let mut slice_offset = tables_offset + codebook_lengths[2];
+    for (level_index, level_width, level_height, flags, slice) in slices.iter() {
+        push(&mut data, 0, 3);
+        push(&mut data, *level_index as u32, 1);
+        push(&mut data, *flags, 1);
+        push(&mut data, *level_width, 2);
+        push(&mut data, *level_height, 2);
+        push(&mut data, level_width.div_ceil(4), 2);
+        push(&mut data, level_height.div_ceil(4), 2);
+        push(&mut data, slice_offset as u32, 4);
+        push(&mut data, slice.len() as u32, 4);
+        push(&mut data, crc16(slice) as u32, 2);
+        slice_offset += slice.len();
+    }
+    data.extend_from_slice(codebooks);
+    for slice in slices.iter() {
+        data.extend_from_slice(&slice.4);
+    }
+
+    let mut basis = Vec::with_capacity(HEADER_SIZE + data.len());
+    push(&mut basis, u16::from_le_bytes(*b"sB") as u32, 2);
+    push(&mut basis, 0x13, 2);
+    push(&mut basis, HEADER_SIZE as u32, 2);
+    push(&mut basis, 0, 2); // The header CRC16 is written below
+    push(&mut basis, data.len() as u32, 4);
+    push(&mut basis, crc16(&data) as u32, 2);
+    push(&mut basis, slices.len() as u32, 3);
+    push(&mut basis, 1, 3);
+    push(&mut basis, if is_etc1s { 0 } else { 1 }, 1);
+    push(
+        &mut basis,
+        if is_etc1s { 1 } else { 0 } | if has_alpha { 4 } else { 0 },
+        2,
+    );
+    push(&mut basis, 0, 1); // 2D
+    push(&mut basis, 0, 3);
+    push(&mut basis, 0, 4);
+    push(&mut basis, 0, 4);
+    push(&mut basis, 0, 4);
+    push(&mut basis, codebook_counts[0], 2);
+    push(&mut basis, endpoints_offset as u32, 4);
+    push(&mut basis, codebook_lengths[0] as u32, 3);
+    push(&mut basis, codebook_counts[1], 2);
+    push(&mut basis, selectors_offset as u32, 4);
+    push(&mut basis, codebook_lengths[1] as u32, 3);
+    push(&mut basis, tables_offset as u32, 4);
+    push(&mut basis, codebook_lengths[2] as u32, 4);
+    push(&mut basis, HEADER_SIZE as u32, 4);
+    push(&mut basis, 0, 4);
+    push(&mut basis, 0, 4);
+    let header_crc = crc16(&basis[8..]);
+    basis[6..8].copy_from_slice(&header_crc.to_le_bytes());
+    basis.extend_from_slice(&data);
+    Ok(basis)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn push(data: &mut Vec<u8>, value: u32, size: usize) {
+    data.extend_from_slice(&value.to_le_bytes()[..size]);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc = !0u16;
+    for byte in data {
+        let q = *byte as u16 ^ (crc >> 8);
+        let k = (q >> 4) ^ q;
+        crc = (crc << 8) ^ k ^ (k << 5) ^ (k << 12);
+    }
+    !crc
+}
+
+impl Loaded {
+    ///
+    /// Deserialize the loaded .ktx2 file at the given path into a [CpuTexture], see [ktx2_from_bytes] for the supported formats.
+    /// The CpuTexture can then be used to create a [Texture2D].
+    ///
+    pub fn ktx2(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuTexture> {
+        ktx2_from_bytes(self.get_bytes(path)?)
+    }
+}
@@ -0,0 +1,135 @@
+use super::voxels::*;
+use crate::core::*;
+use crate::io::*;
+use std::path::Path;
+
+impl Loaded {
+    ///
+    /// Deserialize the loaded MetaImage file (.mhd or .mha) at the given path into a [CpuVolume].
+    /// The voxel data can either be in the same file (`ElementDataFile = LOCAL`) or in a separate file, usually a .raw file, which then also has to be loaded.
+    /// Supports uncompressed and zlib compressed data, both byte orders and all scalar element types.
+    /// The `ElementSpacing` field is used to compute the [CpuVolume::size].
+    ///
+    /// Unsigned 8-bit voxel data is stored as it is, while all other types are scaled to the range `[0..1]` using the smallest and largest value in the data.
+    /// The original range of the values is stored in the [CpuVolume::value_range].
+    ///
+    /// **Note:** Only three dimensional volumes with one value per voxel are supported.
+    ///
+    pub fn mhd(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuVolume> {
+        let corrupt = |message: &str| IOError::MhdCorruptData(message.to_string());
+        let bytes = self.get_bytes(path.as_ref())?;
+
+        let mut dimensions = Vec::new();
+        let mut voxel_type = None;
+        let mut spacing = vec3(1.0, 1.0, 1.0);
+        let mut big_endian = false;
+        let mut compressed = false;
+        let mut header_size = 0i64;
+        let mut channels = 1;
+        let mut data = None;
+        // The header consists of lines of the form `Key = Value` and ends with the ElementDataFile field
+        let mut position = 0;
+        while position < bytes.len() {
+            let end = bytes[position..]
+                .iter()
+                .position(|b| *b == b'\n')
+                .map(|i| position + i + 1)
+                .unwrap_or(bytes.len());
+            let line = std::str::from_utf8(&bytes[position..end])
+                .map_err(|_| corrupt("invalid header"))?;
+            position = end;
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+            let numbers = || {
+                value
+                    .split_whitespace()
+                    .map(|v| v.parse::<f32>().map_err(|_| corrupt(key)))
+                    .collect::<Result<Vec<_>, _>>()
+            };
+            let is_true = value.eq_ignore_ascii_case("true");
+            match key {
+                "NDims" if value != "3" => Err(IOError::MhdNotSupported(
+                    "only three dimensional volumes are supported".to_string(),
+                ))?,
+                "DimSize" => dimensions = numbers()?.iter().map(|v| *v as u32).collect(),
+                "ElementType" => {
+                    voxel_type = Some(match value {
+                        "MET_UCHAR" => VoxelType::U8,
+                        "MET_CHAR" => VoxelType::I8,
+                        "MET_USHORT" => VoxelType::U16,
+                        "MET_SHORT" => VoxelType::I16,
+                        "MET_UINT" | "MET_ULONG" => VoxelType::U32,
+                        "MET_INT" | "MET_LONG" => VoxelType::I32,
+                        "MET_FLOAT" => VoxelType::F32,
+                        "MET_DOUBLE" => VoxelType::F64,
+                        _ => Err(IOError::MhdNotSupported(format!(
+                            "the element type {}",
+                            value
+                        )))?,
+                    })
+                }
+                "ElementSpacing" | "ElementSize" => {
+                    let values = numbers()?;
+                    if values.len() == 3 {
+                        spacing = vec3(values[0], values[1], values[2]);
+                    }
+                }
+                "BinaryDataByteOrderMSB" | "ElementByteOrderMSB" => big_endian = is_true,
+                "CompressedData" => compressed = is_true,
+                "HeaderSize" => header_size = value.parse().map_err(|_| corrupt(key))?,
+                "ElementNumberOfChannels" => channels = value.parse().map_err(|_| corrupt(key))?,
+                "ElementDataFile" => {
+                    data = Some(if value == "LOCAL" {
+                        &bytes[position..]
+                    } else if value.starts_with("LIST") || value.contains(' ') {
+                        Err(IOError::MhdNotSupported("multiple data files".to_string()))?
+                    } else {
+                        data_file(self, path.as_ref(), value)?
+                    });
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let voxel_type = voxel_type.ok_or_else(|| corrupt("missing ElementType"))?;
+        let data = data.ok_or_else(|| corrupt("missing ElementDataFile"))?;
+        if dimensions.len() != 3 {
+            Err(corrupt("missing DimSize"))?;
+        }
+        if channels != 1 {
+            Err(IOError::MhdNotSupported(
+                "only one value per voxel is supported".to_string(),
+            ))?;
+        }
+        let count = voxel_count(&dimensions).ok_or_else(|| corrupt("DimSize"))?;
+        let size = count
+            .checked_mul(voxel_type.size())
+            .ok_or_else(|| corrupt("DimSize"))?;
+
+        let decompressed;
+        let data = if compressed {
+            decompressed = decompress(data, false).map_err(|e| corrupt(&e.to_string()))?;
+            &decompressed[..]
+        } else if header_size < 0 {
+            // A header size of -1 means that the data is at the end of the file
+            &data[data.len().saturating_sub(size)..]
+        } else {
+            data.get(header_size as usize..).unwrap_or(&[])
+        };
+        let values = decode_voxels(data, voxel_type, big_endian, count)
+            .ok_or_else(|| corrupt("not enough voxel data"))?;
+
+        Ok(cpu_volume(
+            path.as_ref()
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            values,
+            voxel_type == VoxelType::U8,
+            [dimensions[0], dimensions[1], dimensions[2]],
+            spacing,
+        ))
+    }
+}
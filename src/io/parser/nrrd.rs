@@ -0,0 +1,187 @@
+use super::voxels::*;
+use crate::core::*;
+use crate::io::*;
+use std::path::Path;
+
+impl Loaded {
+    ///
+    /// Deserialize the loaded .nrrd or .nhdr file at the given path into a [CpuVolume].
+    /// The voxel data can either be attached in the same file or detached in a separate file given by the `data file` field, which then also has to be loaded.
+    /// Supports the raw, gzip and ASCII encodings, both endians and all scalar types.
+    /// The voxel spacing is given by the `spacings` or `space directions` field and is used to compute the [CpuVolume::size].
+    ///
+    /// Unsigned 8-bit voxel data is stored as it is, while all other types are scaled to the range `[0..1]` using the smallest and largest value in the data.
+    /// The original range of the values is stored in the [CpuVolume::value_range].
+    ///
+    /// **Note:** Only three dimensional volumes with one value per voxel are supported.
+    ///
+    pub fn nrrd(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuVolume> {
+        let corrupt = |message: &str| IOError::NrrdCorruptData(message.to_string());
+        let bytes = self.get_bytes(path.as_ref())?;
+        if !bytes.starts_with(b"NRRD") {
+            Err(corrupt("not a .nrrd file"))?;
+        }
+        // The header ends with an empty line if the data is attached
+        let header_end = bytes
+            .windows(2)
+            .position(|w| w == b"\n\n")
+            .map(|i| (i, i + 2))
+            .or_else(|| {
+                bytes
+                    .windows(4)
+                    .position(|w| w == b"\r\n\r\n")
+                    .map(|i| (i, i + 4))
+            })
+            .unwrap_or((bytes.len(), bytes.len()));
+        let header =
+            std::str::from_utf8(&bytes[..header_end.0]).map_err(|_| corrupt("invalid header"))?;
+
+        let mut voxel_type = None;
+        let mut dimension = 0;
+        let mut sizes = Vec::new();
+        let mut encoding = "raw".to_string();
+        let mut big_endian = false;
+        let mut spacing = vec3(1.0, 1.0, 1.0);
+        let mut data_file_name = None;
+        let mut byte_skip = 0i64;
+        let mut line_skip = 0usize;
+        for line in header.lines().skip(1) {
+            let line = line.trim();
+            if line.starts_with('#') || line.contains(":=") {
+                continue;
+            }
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field.trim(), value.trim()),
+                None => continue,
+            };
+            let numbers = || {
+                value
+                    .split_whitespace()
+                    .map(|v| v.parse::<f64>().map_err(|_| corrupt(field)))
+                    .collect::<Result<Vec<_>, _>>()
+            };
+            match field {
+                "type" => {
+                    voxel_type = Some(match value {
+                        "signed char" | "int8" | "int8_t" => VoxelType::I8,
+                        "uchar" | "unsigned char" | "uint8" | "uint8_t" => VoxelType::U8,
+                        "short" | "short int" | "signed short" | "signed short int" | "int16"
+                        | "int16_t" => VoxelType::I16,
+                        "ushort" | "unsigned short" | "unsigned short int" | "uint16"
+                        | "uint16_t" => VoxelType::U16,
+                        "int" | "signed int" | "int32" | "int32_t" => VoxelType::I32,
+                        "uint" | "unsigned int" | "uint32" | "uint32_t" => VoxelType::U32,
+                        "float" => VoxelType::F32,
+                        "double" => VoxelType::F64,
+                        _ => Err(IOError::NrrdNotSupported(format!("the type {}", value)))?,
+                    })
+                }
+                "dimension" => dimension = value.parse().map_err(|_| corrupt(field))?,
+                "sizes" => sizes = numbers()?.iter().map(|v| *v as u32).collect(),
+                "encoding" => encoding = value.to_string(),
+                "endian" => big_endian = value == "big",
+                "spacings" => {
+                    let values = numbers()?;
+                    if values.len() == 3 {
+                        spacing = vec3(values[0] as f32, values[1] as f32, values[2] as f32);
+                    }
+                }
+                "space directions" => {
+                    // For example (1,0,0) (0,1,0) (0,0,2.5) where the length of each vector is the spacing
+                    let lengths = value
+                        .split(')')
+                        .filter_map(|v| v.split_once('(').map(|(_, v)| v))
+                        .map(|v| {
+                            v.split(',')
+                                .map(|c| c.trim().parse::<f32>().map_err(|_| corrupt(field)))
+                                .collect::<Result<Vec<_>, _>>()
+                                .map(|c| c.iter().map(|c| c * c).sum::<f32>().sqrt())
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if lengths.len() == 3 {
+                        spacing = vec3(lengths[0], lengths[1], lengths[2]);
+                    }
+                }
+                "data file" | "datafile" => data_file_name = Some(value.to_string()),
+                "byte skip" | "byteskip" => {
+                    byte_skip = value.parse().map_err(|_| corrupt(field))?
+                }
+                "line skip" | "lineskip" => {
+                    line_skip = value.parse().map_err(|_| corrupt(field))?
+                }
+                _ => {}
+            }
+        }
+        let voxel_type = voxel_type.ok_or_else(|| corrupt("missing type"))?;
+        if dimension != 3 || sizes.len() != 3 {
+            Err(IOError::NrrdNotSupported(
+                "only three dimensional volumes are supported".to_string(),
+            ))?;
+        }
+        let count = voxel_count(&sizes).ok_or_else(|| corrupt("sizes"))?;
+        let size = count
+            .checked_mul(voxel_type.size())
+            .ok_or_else(|| corrupt("sizes"))?;
+
+        let mut data = match data_file_name {
+            Some(ref name) if name.starts_with("LIST") || name.contains(' ') => {
+                Err(IOError::NrrdNotSupported("multiple data files".to_string()))?
+            }
+            Some(ref name) => data_file(self, path.as_ref(), name)?,
+            None => &bytes[header_end.1..],
+        };
+        for _ in 0..line_skip {
+            let end = data.iter().position(|b| *b == b'\n').unwrap_or(data.len());
+            data = &data[(end + 1).min(data.len())..];
+        }
+        let values = match encoding.as_str() {
+            "raw" => {
+                let skip = if byte_skip < 0 {
+                    data.len().saturating_sub(size)
+                } else {
+                    byte_skip as usize
+                };
+                decode_voxels(
+                    data.get(skip..).unwrap_or(&[]),
+                    voxel_type,
+                    big_endian,
+                    count,
+                )
+            }
+            "gzip" | "gz" => {
+                let data = decompress(data, true).map_err(|e| corrupt(&e.to_string()))?;
+                decode_voxels(
+                    data.get(byte_skip.max(0) as usize..).unwrap_or(&[]),
+                    voxel_type,
+                    big_endian,
+                    count,
+                )
+            }
+            "ascii" | "text" | "txt" => std::str::from_utf8(data)
+                .ok()
+                .and_then(|text| {
+                    text.split_whitespace()
+                        .take(count)
+                        .map(|v| v.parse::<f32>().ok())
+                        .collect::<Option<Vec<_>>>()
+                })
+                .filter(|values| values.len() == count),
+            _ => Err(IOError::NrrdNotSupported(format!(
+                "the {} encoding",
+                encoding
+            )))?,
+        }
+        .ok_or_else(|| corrupt("not enough voxel data"))?;
+
+        Ok(cpu_volume(
+            path.as_ref()
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            values,
+            voxel_type == VoxelType::U8,
+            [sizes[0], sizes[1], sizes[2]],
+            spacing,
+        ))
+    }
+}
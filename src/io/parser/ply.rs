@@ -0,0 +1,507 @@
+use crate::core::*;
+use crate::io::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+///
+/// The values of the properties in a PLY file which are not used when constructing a [CpuMesh], for example the confidence of each vertex in a scan.
+/// The key is the name of the element and the name of the property separated by a dot, for example `vertex.confidence`,
+/// and the value contains the value of the property for each element in the order they appear in the file.
+///
+pub type PlyProperties = HashMap<String, PlyValues>;
+
+///
+/// The values of a property for each element in a PLY file, see [PlyProperties].
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlyValues {
+    /// One value for each element.
+    Scalar(Vec<f64>),
+    /// A list of values for each element, for example the texture coordinates of the corners of each face.
+    List(Vec<Vec<f64>>),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PlyType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl PlyType {
+    fn parse(name: &str) -> ThreeDResult<Self> {
+        Ok(match name {
+            "char" | "int8" => Self::I8,
+            "uchar" | "uint8" => Self::U8,
+            "short" | "int16" => Self::I16,
+            "ushort" | "uint16" => Self::U16,
+            "int" | "int32" => Self::I32,
+            "uint" | "uint32" => Self::U32,
+            "float" | "float32" => Self::F32,
+            "double" | "float64" => Self::F64,
+            _ => Err(IOError::PlyCorruptData(format!(
+                "unknown property type {}",
+                name
+            )))?,
+        })
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+
+    fn is_float(&self) -> bool {
+        *self == Self::F32 || *self == Self::F64
+    }
+}
+
+struct PlyProperty {
+    name: String,
+    data_type: PlyType,
+    // The data type of the number of values, if this is a list property
+    count_type: Option<PlyType>,
+}
+
+struct PlyElement {
+    name: String,
+    count: usize,
+    properties: Vec<PlyProperty>,
+}
+
+enum PlyBody<'a> {
+    Ascii(&'a str),
+    Binary { bytes: &'a [u8], big_endian: bool },
+}
+
+impl PlyBody<'_> {
+    // An upper bound of the number of values of the given type left in the body
+    fn max_values(&self, data_type: PlyType) -> usize {
+        match self {
+            // Each value is followed by at least one whitespace
+            Self::Ascii(text) => text.len().div_ceil(2),
+            Self::Binary { bytes, .. } => bytes.len() / data_type.size(),
+        }
+    }
+
+    // An upper bound of the number of instances of the given element left in the body, every property reads at least one value
+    fn max_elements(&self, element: &PlyElement) -> usize {
+        if element.properties.is_empty() {
+            return 0;
+        }
+        match self {
+            Self::Ascii(text) => text.len().div_ceil(2) / element.properties.len(),
+            Self::Binary { bytes, .. } => {
+                bytes.len()
+                    / element
+                        .properties
+                        .iter()
+                        .map(|p| p.count_type.unwrap_or(p.data_type).size())
+                        .sum::<usize>()
+            }
+        }
+    }
+
+    fn read(&mut self, data_type: PlyType) -> ThreeDResult<f64> {
+        match self {
+            Self::Ascii(text) => {
+                let remaining = text.trim_start_matches(|c: char| c.is_ascii_whitespace());
+                let end = remaining
+                    .find(|c: char| c.is_ascii_whitespace())
+                    .unwrap_or(remaining.len());
+                *text = &remaining[end..];
+                Ok(remaining[..end]
+                    .parse()
+                    .map_err(|_| IOError::PlyCorruptData("missing or invalid value".to_string()))?)
+            }
+            Self::Binary { bytes, big_endian } => {
+                let size = data_type.size();
+                if bytes.len() < size {
+                    Err(IOError::PlyCorruptData(
+                        "unexpected end of file".to_string(),
+                    ))?;
+                }
+                // Converted to little endian, so the same conversion can be used for both
+                let mut b = [0u8; 8];
+                b[..size].copy_from_slice(&bytes[..size]);
+                if *big_endian {
+                    b[..size].reverse();
+                }
+                *bytes = &bytes[size..];
+                Ok(match data_type {
+                    PlyType::I8 => b[0] as i8 as f64,
+                    PlyType::U8 => b[0] as f64,
+                    PlyType::I16 => i16::from_le_bytes([b[0], b[1]]) as f64,
+                    PlyType::U16 => u16::from_le_bytes([b[0], b[1]]) as f64,
+                    PlyType::I32 => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    PlyType::U32 => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    PlyType::F32 => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    PlyType::F64 => f64::from_le_bytes(b),
+                })
+            }
+        }
+    }
+}
+
+///
+/// Deserialize the given bytes representing a PLY file (ASCII, binary little endian or binary big endian) into a [CpuMesh].
+/// The positions, normals, uv coordinates and colors of the vertices are used for the mesh and the faces are triangulated.
+/// The values of all other properties, including list properties other than the vertex indices of the faces, are returned as [PlyProperties].
+///
+pub fn ply_from_bytes(bytes: &[u8]) -> ThreeDResult<(CpuMesh, PlyProperties)> {
+    let corrupt = |message: &str| IOError::PlyCorruptData(message.to_string());
+    let header_end = bytes
+        .windows(10)
+        .position(|w| w == b"end_header")
+        .ok_or_else(|| corrupt("missing end_header"))?;
+    let body_start = bytes[header_end..]
+        .iter()
+        .position(|b| *b == b'\n')
+        .map(|i| header_end + i + 1)
+        .unwrap_or(bytes.len());
+    let header =
+        std::str::from_utf8(&bytes[..header_end]).map_err(|_| corrupt("invalid header"))?;
+
+    let mut lines = header.lines().map(|l| l.trim());
+    if lines.next() != Some("ply") {
+        Err(corrupt("not a .ply file"))?;
+    }
+    let mut format = None;
+    let mut elements: Vec<PlyElement> = Vec::new();
+    for line in lines {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["format", name, ..] => format = Some(name.to_string()),
+            ["element", name, count] => elements.push(PlyElement {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| corrupt("invalid element count"))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_type, data_type, name] => elements
+                .last_mut()
+                .ok_or_else(|| corrupt("property without element"))?
+                .properties
+                .push(PlyProperty {
+                    name: name.to_string(),
+                    data_type: PlyType::parse(data_type)?,
+                    count_type: Some(PlyType::parse(count_type)?),
+                }),
+            ["property", data_type, name] => elements
+                .last_mut()
+                .ok_or_else(|| corrupt("property without element"))?
+                .properties
+                .push(PlyProperty {
+                    name: name.to_string(),
+                    data_type: PlyType::parse(data_type)?,
+                    count_type: None,
+                }),
+            _ => {}
+        }
+    }
+    let mut body = match format.as_deref() {
+        Some("ascii") => PlyBody::Ascii(
+            std::str::from_utf8(&bytes[body_start..]).map_err(|_| corrupt("invalid ASCII data"))?,
+        ),
+        Some("binary_little_endian") => PlyBody::Binary {
+            bytes: &bytes[body_start..],
+            big_endian: false,
+        },
+        Some("binary_big_endian") => PlyBody::Binary {
+            bytes: &bytes[body_start..],
+            big_endian: true,
+        },
+        _ => Err(corrupt("unknown format"))?,
+    };
+
+    let mut positions: Vec<Vector3<f64>> = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut colors = Vec::new();
+    let mut indices = Vec::new();
+    let mut properties = PlyProperties::new();
+    let mut is_f64 = false;
+    for element in elements.iter() {
+        let is_vertex = element.name == "vertex";
+        let is_face = element.name == "face";
+        if element.count > body.max_elements(element) {
+            Err(corrupt("element count larger than the data"))?;
+        }
+        for _ in 0..element.count {
+            let mut position = vec3(0.0, 0.0, 0.0);
+            let mut normal = vec3(0.0, 0.0, 0.0);
+            let mut uv = vec2(0.0, 0.0);
+            let mut color = Color::WHITE;
+            let (mut has_normal, mut has_uv, mut has_color) = (false, false, false);
+            for property in element.properties.iter() {
+                if let Some(count_type) = property.count_type {
+                    let count = body.read(count_type)? as usize;
+                    let mut values =
+                        Vec::with_capacity(count.min(body.max_values(property.data_type)));
+                    for _ in 0..count {
+                        values.push(body.read(property.data_type)?);
+                    }
+                    if is_face
+                        && (property.name == "vertex_indices" || property.name == "vertex_index")
+                    {
+                        let values = values
+                            .iter()
+                            .map(|v| {
+                                if *v >= 0.0 && *v <= u32::MAX as f64 {
+                                    Ok(*v as u32)
+                                } else {
+                                    Err(corrupt("face index out of range"))
+                                }
+                            })
+                            .collect::<Result<Vec<_>, _>>()?;
+                        // Triangulated as a fan, which works for convex polygons
+                        for i in 2..values.len() {
+                            indices.extend_from_slice(&[values[0], values[i - 1], values[i]]);
+                        }
+                    } else if let PlyValues::List(lists) = properties
+                        .entry(format!("{}.{}", element.name, property.name))
+                        .or_insert_with(|| PlyValues::List(Vec::new()))
+                    {
+                        lists.push(values);
+                    }
+                    continue;
+                }
+                let value = body.read(property.data_type)?;
+                // Colors are stored either as bytes or as floats between 0 and 1
+                let color_value = || {
+                    if property.data_type.is_float() {
+                        (value * 255.0).round().clamp(0.0, 255.0) as u8
+                    } else {
+                        value as u8
+                    }
+                };
+                match property.name.as_str() {
+                    "x" if is_vertex => position.x = value,
+                    "y" if is_vertex => position.y = value,
+                    "z" if is_vertex => position.z = value,
+                    "nx" if is_vertex => (normal.x, has_normal) = (value as f32, true),
+                    "ny" if is_vertex => (normal.y, has_normal) = (value as f32, true),
+                    "nz" if is_vertex => (normal.z, has_normal) = (value as f32, true),
+                    "u" | "s" | "texture_u" | "texture_s" if is_vertex => {
+                        (uv.x, has_uv) = (value as f32, true)
+                    }
+                    "v" | "t" | "texture_v" | "texture_t" if is_vertex => {
+                        (uv.y, has_uv) = (1.0 - value as f32, true)
+                    }
+                    "red" | "diffuse_red" if is_vertex => {
+                        (color.r, has_color) = (color_value(), true)
+                    }
+                    "green" | "diffuse_green" if is_vertex => {
+                        (color.g, has_color) = (color_value(), true)
+                    }
+                    "blue" | "diffuse_blue" if is_vertex => {
+                        (color.b, has_color) = (color_value(), true)
+                    }
+                    "alpha" if is_vertex => (color.a, has_color) = (color_value(), true),
+                    name => {
+                        if let PlyValues::Scalar(values) = properties
+                            .entry(format!("{}.{}", element.name, name))
+                            .or_insert_with(|| PlyValues::Scalar(Vec::new()))
+                        {
+                            values.push(value);
+                        }
+                    }
+                }
+                if is_vertex
+                    && property.data_type == PlyType::F64
+                    && ["x", "y", "z"].contains(&property.name.as_str())
+                {
+                    is_f64 = true;
+                }
+            }
+            if is_vertex {
+                positions.push(position);
+                if has_normal {
+                    normals.push(normal);
+                }
+                if has_uv {
+                    uvs.push(uv);
+                }
+                if has_color {
+                    colors.push(color);
+                }
+            }
+        }
+    }
+
+    let vertex_count = positions.len();
+    if indices.iter().any(|i| *i as usize >= vertex_count) {
+        Err(corrupt("face index out of range"))?;
+    }
+    let cpu_mesh = CpuMesh {
+        positions: if is_f64 {
+            Positions::F64(positions)
+        } else {
+            Positions::F32(
+                positions
+                    .iter()
+                    .map(|p| vec3(p.x as f32, p.y as f32, p.z as f32))
+                    .collect(),
+            )
+        },
+        indices: if indices.is_empty() {
+            None
+        } else {
+            Some(Indices::U32(indices))
+        },
+        normals: Some(normals).filter(|v| v.len() == vertex_count && vertex_count > 0),
+        uvs: Some(uvs).filter(|v| v.len() == vertex_count && vertex_count > 0),
+        colors: Some(colors).filter(|v| v.len() == vertex_count && vertex_count > 0),
+        ..Default::default()
+    };
+    Ok((cpu_mesh, properties))
+}
+
+///
+/// Serialize the given [CpuMesh] into bytes representing a binary little endian PLY file including the positions, normals, uv coordinates, colors and triangles of the mesh.
+/// The given vertex properties, ie. the scalar properties in [PlyProperties] starting with `vertex.` and with one value for each vertex, are stored as well.
+///
+pub fn ply_to_bytes(cpu_mesh: &CpuMesh, properties: &PlyProperties) -> Vec<u8> {
+    let vertex_count = cpu_mesh.positions.len();
+    let mut extra_properties: Vec<(&str, &Vec<f64>)> = properties
+        .iter()
+        .filter_map(|(key, values)| match values {
+            PlyValues::Scalar(values) if values.len() == vertex_count => Some((key, values)),
+            _ => None,
+        })
+        .filter_map(|(key, values)| key.strip_prefix("vertex.").map(|name| (name, values)))
+        .collect();
+    extra_properties.sort_by(|a, b| a.0.cmp(b.0));
+    let mut triangle_count = 0;
+    cpu_mesh.for_each_triangle(|_, _, _| triangle_count += 1);
+
+    let mut header = String::new();
+    header.push_str("ply\nformat binary_little_endian 1.0\ncomment Created by three-d\n");
+    header.push_str(&format!("element vertex {}\n", vertex_count));
+    let position_type = match cpu_mesh.positions {
+        Positions::F32(_) => "float",
+        Positions::F64(_) => "double",
+    };
+    for name in ["x", "y", "z"] {
+        header.push_str(&format!("property {} {}\n", position_type, name));
+    }
+    if cpu_mesh.normals.is_some() {
+        header.push_str("property float nx\nproperty float ny\nproperty float nz\n");
+    }
+    if cpu_mesh.uvs.is_some() {
+        header.push_str("property float s\nproperty float t\n");
+    }
+    if cpu_mesh.colors.is_some() {
+        header.push_str(
+            "property uchar red\nproperty uchar green\nproperty uchar blue\nproperty uchar alpha\n",
+        );
+    }
+    for (name, _) in extra_properties.iter() {
+        header.push_str(&format!("property double {}\n", name));
+    }
+    header.push_str(&format!("element face {}\n", triangle_count));
+    header.push_str("property list uchar uint vertex_indices\nend_header\n");
+
+    let mut bytes = header.into_bytes();
+    for i in 0..vertex_count {
+        match cpu_mesh.positions {
+            Positions::F32(ref positions) => {
+                for c in [positions[i].x, positions[i].y, positions[i].z] {
+                    bytes.extend_from_slice(&c.to_le_bytes());
+                }
+            }
+            Positions::F64(ref positions) => {
+                for c in [positions[i].x, positions[i].y, positions[i].z] {
+                    bytes.extend_from_slice(&c.to_le_bytes());
+                }
+            }
+        }
+        if let Some(ref normals) = cpu_mesh.normals {
+            for c in [normals[i].x, normals[i].y, normals[i].z] {
+                bytes.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+        if let Some(ref uvs) = cpu_mesh.uvs {
+            for c in [uvs[i].x, 1.0 - uvs[i].y] {
+                bytes.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+        if let Some(ref colors) = cpu_mesh.colors {
+            bytes.extend_from_slice(&[colors[i].r, colors[i].g, colors[i].b, colors[i].a]);
+        }
+        for (_, values) in extra_properties.iter() {
+            bytes.extend_from_slice(&values[i].to_le_bytes());
+        }
+    }
+    cpu_mesh.for_each_triangle(|i0, i1, i2| {
+        bytes.push(3);
+        for index in [i0, i1, i2] {
+            bytes.extend_from_slice(&(index as u32).to_le_bytes());
+        }
+    });
+    bytes
+}
+
+///
+/// Deserialize the given bytes representing a PLY file into a [CpuPointCloud] containing the vertices, see [ply_from_bytes].
+/// The intensities are read from the `intensity` or `scalar_intensity` vertex property, if present,
+/// and are divided by the largest intensity if any intensity is larger than one.
+///
+pub fn ply_point_cloud_from_bytes(bytes: &[u8]) -> ThreeDResult<CpuPointCloud> {
+    let (cpu_mesh, mut properties) = ply_from_bytes(bytes)?;
+    let mut cpu_point_cloud = CpuPointCloud::from(&cpu_mesh);
+    cpu_point_cloud.intensities = ["vertex.intensity", "vertex.scalar_intensity"]
+        .iter()
+        .find_map(|key| match properties.remove(*key) {
+            Some(PlyValues::Scalar(values)) => Some(values),
+            _ => None,
+        })
+        .filter(|values| values.len() == cpu_point_cloud.len())
+        .map(|values| {
+            let max = values.iter().fold(0.0f64, |a, b| a.max(*b));
+            let scale = if max > 1.0 { 1.0 / max } else { 1.0 };
+            values.iter().map(|v| (v * scale) as f32).collect()
+        });
+    Ok(cpu_point_cloud)
+}
+
+impl Loaded {
+    ///
+    /// Deserialize the loaded .ply file at the given path into a [CpuMesh] and the values of the properties not used by the mesh, see [ply_from_bytes].
+    ///
+    pub fn ply(&mut self, path: impl AsRef<Path>) -> ThreeDResult<(CpuMesh, PlyProperties)> {
+        ply_from_bytes(self.get_bytes(path)?)
+    }
+
+    ///
+    /// Deserialize the loaded .ply file at the given path into a [CpuPointCloud], see [ply_point_cloud_from_bytes].
+    /// The CpuPointCloud can then be used to create a [PointCloud](crate::renderer::PointCloud).
+    ///
+    pub fn ply_point_cloud(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuPointCloud> {
+        ply_point_cloud_from_bytes(self.get_bytes(path)?)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Saver {
+    ///
+    /// Saves the given [CpuMesh] and vertex properties as a binary .ply file, see [ply_to_bytes].
+    ///
+    pub fn save_ply(
+        path: impl AsRef<Path>,
+        cpu_mesh: &CpuMesh,
+        properties: &PlyProperties,
+    ) -> ThreeDResult<()> {
+        Self::save_file(path, &ply_to_bytes(cpu_mesh, properties))
+    }
+}
@@ -0,0 +1,150 @@
+use crate::core::*;
+use crate::io::*;
+use std::path::Path;
+
+///
+/// Deserialize the given bytes representing an ASCII or binary STL file into a [CpuMesh].
+/// Each triangle gets its own three vertices with the normal of the triangle, so the mesh is not indexed.
+///
+pub fn stl_from_bytes(bytes: &[u8]) -> ThreeDResult<CpuMesh> {
+    let is_binary = bytes.len() >= 84 && {
+        let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+        50usize
+            .checked_mul(count)
+            .and_then(|size| size.checked_add(84))
+            == Some(bytes.len())
+    };
+    let mut name = String::new();
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    if is_binary {
+        name = String::from_utf8_lossy(&bytes[0..80])
+            .trim_end_matches('\0')
+            .trim()
+            .to_string();
+        let read = |offset: usize| {
+            vec3(
+                f32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()),
+                f32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().unwrap()),
+                f32::from_le_bytes(bytes[offset + 8..offset + 12].try_into().unwrap()),
+            )
+        };
+        for triangle in 0..(bytes.len() - 84) / 50 {
+            let offset = 84 + 50 * triangle;
+            normals.push(read(offset));
+            for i in 0..3 {
+                positions.push(read(offset + 12 + 12 * i));
+            }
+        }
+    } else {
+        let text = std::str::from_utf8(bytes).map_err(|_| IOError::StlCorruptData)?;
+        let mut tokens = text.split_whitespace();
+        let read = |tokens: &mut std::str::SplitWhitespace| -> ThreeDResult<Vec3> {
+            let mut value = || -> ThreeDResult<f32> {
+                Ok(tokens
+                    .next()
+                    .and_then(|t| t.parse().ok())
+                    .ok_or(IOError::StlCorruptData)?)
+            };
+            Ok(vec3(value()?, value()?, value()?))
+        };
+        if tokens.next() != Some("solid") {
+            Err(IOError::StlCorruptData)?;
+        }
+        if let Some(line) = text.lines().next() {
+            name = line
+                .trim_start()
+                .trim_start_matches("solid")
+                .trim()
+                .to_string();
+        }
+        while let Some(token) = tokens.next() {
+            match token {
+                "normal" => normals.push(read(&mut tokens)?),
+                "vertex" => positions.push(read(&mut tokens)?),
+                _ => {}
+            }
+        }
+        if positions.len() != 3 * normals.len() {
+            Err(IOError::StlCorruptData)?;
+        }
+    }
+
+    let normals = normals
+        .iter()
+        .enumerate()
+        .flat_map(|(i, normal)| {
+            // Some files leave the normals at zero, in which case it is computed from the vertices,
+            // and degenerate triangles without area get a zero normal
+            let normal = if normal.magnitude2() > 0.0 {
+                normal.normalize()
+            } else {
+                let (p0, p1, p2) = (positions[3 * i], positions[3 * i + 1], positions[3 * i + 2]);
+                let normal = (p1 - p0).cross(p2 - p0);
+                if normal.magnitude2() > 0.0 {
+                    normal.normalize()
+                } else {
+                    vec3(0.0, 0.0, 0.0)
+                }
+            };
+            [normal; 3]
+        })
+        .collect();
+    Ok(CpuMesh {
+        name,
+        positions: Positions::F32(positions),
+        normals: Some(normals),
+        ..Default::default()
+    })
+}
+
+///
+/// Serialize the given [CpuMesh] into bytes representing a binary STL file.
+/// Only the positions are stored, the normal of each triangle is computed from the positions.
+///
+pub fn stl_to_bytes(cpu_mesh: &CpuMesh) -> Vec<u8> {
+    let positions = cpu_mesh.positions.to_f32();
+    let mut bytes = vec![0; 80];
+    for (i, byte) in cpu_mesh.name.bytes().take(80).enumerate() {
+        bytes[i] = byte;
+    }
+    bytes.extend_from_slice(&[0; 4]);
+    let mut count = 0u32;
+    cpu_mesh.for_each_triangle(|i0, i1, i2| {
+        let (p0, p1, p2) = (positions[i0], positions[i1], positions[i2]);
+        let normal = (p1 - p0).cross(p2 - p0);
+        let normal = if normal.magnitude2() > 0.0 {
+            normal.normalize()
+        } else {
+            normal
+        };
+        for v in [normal, p0, p1, p2] {
+            for c in [v.x, v.y, v.z] {
+                bytes.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+        bytes.extend_from_slice(&[0; 2]);
+        count += 1;
+    });
+    bytes[80..84].copy_from_slice(&count.to_le_bytes());
+    bytes
+}
+
+impl Loaded {
+    ///
+    /// Deserialize the loaded .stl file (ASCII or binary) at the given path into a [CpuMesh], see [stl_from_bytes].
+    ///
+    pub fn stl(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuMesh> {
+        stl_from_bytes(self.get_bytes(path)?)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Saver {
+    ///
+    /// Saves the given [CpuMesh] as a binary .stl file, see [stl_to_bytes].
+    ///
+    pub fn save_stl(path: impl AsRef<Path>, cpu_mesh: &CpuMesh) -> ThreeDResult<()> {
+        Self::save_file(path, &stl_to_bytes(cpu_mesh))
+    }
+}
@@ -0,0 +1,80 @@
+use crate::core::*;
+use crate::io::*;
+use std::path::Path;
+
+const MAGIC: &[u8; 8] = b"three-d\0";
+const VERSION: u32 = 1;
+
+///
+/// Deserialize the given bytes in the native binary .3d format, see [Loaded::three_d].
+///
+pub fn three_d_from_bytes<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> ThreeDResult<T> {
+    if bytes.len() < 12 || &bytes[0..8] != MAGIC {
+        Err(IOError::ThreeDCorruptData)?;
+    }
+    let version = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+    if version != VERSION {
+        Err(IOError::ThreeDVersion(version, VERSION))?;
+    }
+    Ok(bincode::deserialize(&bytes[12..]).map_err(IOError::from)?)
+}
+
+///
+/// Serialize the given data into bytes in the native binary .3d format, see [Saver::save_3d].
+///
+pub fn three_d_to_bytes<T: serde::Serialize>(data: &T) -> ThreeDResult<Vec<u8>> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bincode::serialize_into(&mut bytes, data).map_err(IOError::from)?;
+    Ok(bytes)
+}
+
+impl Loaded {
+    ///
+    /// Deserialize the loaded .3d file at the given path, which is a binary format containing any data that was saved using [Saver::save_3d],
+    /// for example meshes and materials
+    /// ```no_run
+    /// # use three_d::*;
+    /// # fn main() -> ThreeDResult<()> {
+    /// # let mut loaded = Loader::load_blocking(&["model.3d"])?;
+    /// let (cpu_meshes, cpu_materials): (Vec<CpuMesh>, Vec<CpuMaterial>) = loaded.three_d("model.3d")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// The data is stored exactly as it was saved, including for example tangents and compressed textures with their mip levels,
+    /// so loading a .3d file avoids the parsing and processing done when loading for example a .gltf file or an image.
+    ///
+    /// # Errors
+    /// Returns an error if the file is not a .3d file, if it was saved with a different version of the format or if it does not contain the requested type of data.
+    ///
+    pub fn three_d<T: serde::de::DeserializeOwned>(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> ThreeDResult<T> {
+        three_d_from_bytes(self.get_bytes(path)?)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Saver {
+    ///
+    /// Saves the given data in the native binary .3d format, which can be loaded again using [Loaded::three_d], for example meshes and materials
+    /// ```no_run
+    /// # use three_d::*;
+    /// # fn main() -> ThreeDResult<()> {
+    /// # let mut loaded = Loader::load_blocking(&["model.gltf"])?;
+    /// let (mut cpu_meshes, cpu_materials) = loaded.gltf("model.gltf")?;
+    /// for cpu_mesh in cpu_meshes.iter_mut() {
+    ///     cpu_mesh.compute_tangents()?;
+    /// }
+    /// Saver::save_3d("model.3d", &(cpu_meshes, cpu_materials))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// Any data that can be serialized using [serde](https://crates.io/crates/serde) can be saved,
+    /// including [CpuMesh], [CpuMaterial], [CpuTexture], [CpuTextureCube] and [CpuVolume].
+    ///
+    pub fn save_3d<T: serde::Serialize>(path: impl AsRef<Path>, data: &T) -> ThreeDResult<()> {
+        Self::save_file(path, &three_d_to_bytes(data)?)
+    }
+}
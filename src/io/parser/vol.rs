@@ -61,6 +61,7 @@ impl Loaded {
                 ..Default::default()
             },
             size: vec3(size.z, size.x, size.y),
+            value_range: (0.0, 255.0),
             ..Default::default()
         })
     }
@@ -0,0 +1,148 @@
+use crate::core::*;
+use crate::io::*;
+
+///
+/// The data type of a single voxel value.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum VoxelType {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    F32,
+    F64,
+}
+
+impl VoxelType {
+    pub fn size(&self) -> usize {
+        match self {
+            Self::U8 | Self::I8 => 1,
+            Self::U16 | Self::I16 => 2,
+            Self::U32 | Self::I32 | Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+}
+
+///
+/// Returns the number of voxels in a volume with the given size in each dimension or `None` if it overflows.
+///
+pub(super) fn voxel_count(sizes: &[u32]) -> Option<usize> {
+    sizes
+        .iter()
+        .try_fold(1usize, |count, size| count.checked_mul(*size as usize))
+}
+
+///
+/// Decodes `count` values of the given type from the bytes.
+///
+pub(super) fn decode_voxels(
+    bytes: &[u8],
+    voxel_type: VoxelType,
+    big_endian: bool,
+    count: usize,
+) -> Option<Vec<f32>> {
+    let size = voxel_type.size();
+    let bytes = bytes.get(..count.checked_mul(size)?)?;
+    Some(
+        bytes
+            .chunks_exact(size)
+            .map(|c| {
+                // Converted to little endian, so the same conversion can be used for both
+                let mut b = [0u8; 8];
+                b[..size].copy_from_slice(c);
+                if big_endian {
+                    b[..size].reverse();
+                }
+                match voxel_type {
+                    VoxelType::U8 => b[0] as f32,
+                    VoxelType::I8 => b[0] as i8 as f32,
+                    VoxelType::U16 => u16::from_le_bytes([b[0], b[1]]) as f32,
+                    VoxelType::I16 => i16::from_le_bytes([b[0], b[1]]) as f32,
+                    VoxelType::U32 => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32,
+                    VoxelType::I32 => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32,
+                    VoxelType::F32 => f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+                    VoxelType::F64 => f64::from_le_bytes(b) as f32,
+                }
+            })
+            .collect(),
+    )
+}
+
+///
+/// Decompresses zlib or gzip compressed data.
+///
+pub(super) fn decompress(bytes: &[u8], gzip: bool) -> std::io::Result<Vec<u8>> {
+    use std::io::Read;
+    let mut data = Vec::new();
+    if gzip {
+        flate2::read::MultiGzDecoder::new(bytes).read_to_end(&mut data)?;
+    } else {
+        flate2::read::ZlibDecoder::new(bytes).read_to_end(&mut data)?;
+    }
+    Ok(data)
+}
+
+///
+/// Constructs a [CpuVolume] from the voxel values, where the x index changes fastest and the z index slowest, and the spacing between the voxels.
+/// Unsigned 8-bit values are stored as they are, all other values are scaled to the range `[0..1]` using the smallest and largest value,
+/// which are stored in the [CpuVolume::value_range].
+///
+pub(super) fn cpu_volume(
+    name: String,
+    values: Vec<f32>,
+    is_u8: bool,
+    dimensions: [u32; 3],
+    spacing: Vec3,
+) -> CpuVolume {
+    let (data, value_range) = if is_u8 {
+        (
+            TextureData::RU8(values.iter().map(|v| *v as u8).collect()),
+            (0.0, 255.0),
+        )
+    } else {
+        let (min, max) = values.iter().fold((f32::MAX, f32::MIN), |(min, max), v| {
+            (min.min(*v), max.max(*v))
+        });
+        let scale = if max > min { 1.0 / (max - min) } else { 0.0 };
+        (
+            TextureData::RF32(values.iter().map(|v| (v - min) * scale).collect()),
+            (min, max),
+        )
+    };
+    CpuVolume {
+        name,
+        voxels: CpuTexture3D {
+            data,
+            width: dimensions[0],
+            height: dimensions[1],
+            depth: dimensions[2],
+            ..Default::default()
+        },
+        size: vec3(
+            dimensions[0] as f32 * spacing.x,
+            dimensions[1] as f32 * spacing.y,
+            dimensions[2] as f32 * spacing.z,
+        ),
+        value_range,
+    }
+}
+
+///
+/// Returns the bytes of the data file referenced by a header file, where the path of the data file is relative to the header file.
+///
+pub(super) fn data_file<'a>(
+    loaded: &'a Loaded,
+    header_path: &std::path::Path,
+    data_file: &str,
+) -> ThreeDResult<&'a [u8]> {
+    loaded.get_bytes(
+        header_path
+            .parent()
+            .map(|p| p.join(data_file))
+            .unwrap_or_else(|| data_file.into()),
+    )
+}
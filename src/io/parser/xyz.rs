@@ -0,0 +1,94 @@
+use crate::core::*;
+use crate::io::*;
+use std::path::Path;
+
+///
+/// Deserialize the given bytes representing an ASCII XYZ file into a [CpuPointCloud].
+/// Each line contains the values of one point separated by spaces, tabs or commas, and empty lines and lines starting with `#` or `//` are skipped.
+/// The meaning of the values is determined by the number of values on each line:
+/// - 3 values: `x y z`
+/// - 4 values: `x y z intensity`
+/// - 6 values: `x y z red green blue`
+/// - 7 values: `x y z red green blue intensity`
+/// - 9 values: `x y z red green blue nx ny nz`
+///
+/// The colors are assumed to be in the range `[0..255]`, unless all colors are in the range `[0..1]`.
+/// The intensities are divided by the largest intensity if any intensity is larger than one.
+///
+pub fn xyz_from_bytes(bytes: &[u8]) -> ThreeDResult<CpuPointCloud> {
+    let text = std::str::from_utf8(bytes).map_err(|_| IOError::XyzCorruptData)?;
+    let mut column_count = None;
+    let mut positions = Vec::new();
+    let mut colors = Vec::new();
+    let mut normals = Vec::new();
+    let mut intensities = Vec::new();
+    for line in text.lines().map(|l| l.trim()) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        let values = line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|v| !v.is_empty())
+            .map(|v| v.parse::<f32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| IOError::XyzCorruptData)?;
+        // Some files start with a line containing the number of points
+        if column_count.is_none() && values.len() < 3 {
+            continue;
+        }
+        if *column_count.get_or_insert(values.len()) != values.len() {
+            Err(IOError::XyzCorruptData)?;
+        }
+        positions.push(vec3(values[0], values[1], values[2]));
+        match values.len() {
+            3 => {}
+            4 => intensities.push(values[3]),
+            6 | 7 | 9 => {
+                colors.push([values[3], values[4], values[5]]);
+                if values.len() == 7 {
+                    intensities.push(values[6]);
+                }
+                if values.len() == 9 {
+                    normals.push(vec3(values[6], values[7], values[8]));
+                }
+            }
+            _ => Err(IOError::XyzCorruptData)?,
+        }
+    }
+
+    let color_scale = if colors.iter().flatten().all(|c| *c <= 1.0) {
+        255.0
+    } else {
+        1.0
+    };
+    let max_intensity = intensities.iter().fold(0.0f32, |a, b| a.max(*b));
+    if max_intensity > 1.0 {
+        intensities.iter_mut().for_each(|i| *i /= max_intensity);
+    }
+    Ok(CpuPointCloud {
+        positions,
+        colors: Some(
+            colors
+                .iter()
+                .map(|c| {
+                    let v = |c: f32| (c * color_scale).round().clamp(0.0, 255.0) as u8;
+                    Color::new_opaque(v(c[0]), v(c[1]), v(c[2]))
+                })
+                .collect::<Vec<_>>(),
+        )
+        .filter(|c| !c.is_empty()),
+        normals: Some(normals).filter(|n| !n.is_empty()),
+        intensities: Some(intensities).filter(|i| !i.is_empty()),
+        ..Default::default()
+    })
+}
+
+impl Loaded {
+    ///
+    /// Deserialize the loaded .xyz file at the given path into a [CpuPointCloud], see [xyz_from_bytes] for the supported columns.
+    /// The CpuPointCloud can then be used to create a [PointCloud](crate::renderer::PointCloud).
+    ///
+    pub fn xyz(&mut self, path: impl AsRef<Path>) -> ThreeDResult<CpuPointCloud> {
+        xyz_from_bytes(self.get_bytes(path)?)
+    }
+}
@@ -5,11 +5,14 @@
 
 pub use crate::core::{
     math::*, render_states::*, render_target::*, texture::*, AxisAlignedBoundingBox, Camera,
-    Context, CpuMaterial, CpuMesh, CpuTexture, CpuTexture3D, CpuTextureCube, CpuVolume,
-    GeometryFunction, Indices, LightingModel, NormalDistributionFunction, Positions, ScissorBox,
-    Viewport,
+    Camera2D, ClipOperation, ClipPlanes, Context, CpuMaterial, CpuMesh, CpuTexture, CpuTexture3D,
+    CpuTextureCube, CpuVolume, DebugSeverity, GeometryFunction, Indices, LightingModel,
+    NormalDistributionFunction, Positions, ScissorBox, Viewport, MAX_CLIP_PLANES,
 };
 
+#[cfg(feature = "text")]
+pub use crate::core::CpuFont;
+
 pub mod material;
 pub use material::*;
 
@@ -33,6 +36,18 @@ pub use geometry::*;
 pub mod object;
 pub use object::*;
 
+mod clip_caps;
+#[doc(inline)]
+pub use clip_caps::*;
+
+mod dashes;
+
+#[cfg(feature = "text")]
+#[cfg_attr(docsrs, doc(cfg(feature = "text")))]
+pub mod text;
+#[cfg(feature = "text")]
+pub use text::*;
+
 pub use crate::ThreeDResult;
 use thiserror::Error;
 ///
@@ -148,15 +163,17 @@ pub fn render_pass(
     objects: &[&dyn Object],
     lights: &[&dyn Light],
 ) -> ThreeDResult<()> {
-    let mut culled_objects = objects
-        .iter()
-        .filter(|o| camera.in_frustum(&o.aabb()))
-        .collect::<Vec<_>>();
-    culled_objects.sort_by(|a, b| cmp_render_order(camera, a, b));
-    for object in culled_objects {
-        object.render(camera, lights)?;
-    }
-    Ok(())
+    camera.context().debug_group("render_pass", || {
+        let mut culled_objects = objects
+            .iter()
+            .filter(|o| camera.in_frustum(&o.aabb()))
+            .collect::<Vec<_>>();
+        culled_objects.sort_by(|a, b| cmp_render_order(camera, a, b));
+        for object in culled_objects {
+            object.render(camera, lights)?;
+        }
+        Ok(())
+    })
 }
 
 ///
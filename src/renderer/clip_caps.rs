@@ -0,0 +1,167 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// The appearance of the caps that close the cut surfaces of the clipped geometries, see [render_clip_caps].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClipCapStyle {
+    /// The caps are filled with the given color.
+    Color(Color),
+    /// The caps are filled with a hatch pattern of parallel diagonal lines, as is common in technical drawings.
+    Hatch {
+        /// The color of the lines.
+        color: Color,
+        /// The color between the lines.
+        background: Color,
+        /// The distance between the lines in world units.
+        spacing: f32,
+        /// The width of the lines in world units.
+        width: f32,
+    },
+}
+
+///
+/// Closes the cut surfaces of the given geometries, which are cut by the clip planes set on the context (see [Context::set_clip_planes]), with caps in the given style.
+/// The geometries must already be rendered and this must be called in the same callback given as input to a [RenderTarget] or [DepthTarget] write method,
+/// since the caps are depth tested against the rendered geometries.
+///
+/// The caps are found using the stencil buffer, so the render target must have a stencil buffer (for example the screen or a depth target with the [DepthFormat::Depth24Stencil8] format)
+/// and the stencil buffer is cleared for each clip plane.
+/// For each clip plane, the cap is drawn where a ray from the camera through the cap crosses the remaining surface of the geometries an odd number of times.
+///
+/// **Note:** The geometries must be closed, ie. without holes, for the caps to be correct.
+/// The caps are exact for a single clip plane and for several clip planes as long as two cut surfaces are not seen through each other.
+///
+pub fn render_clip_caps(
+    context: &Context,
+    camera: &Camera,
+    geometries: &[&dyn Geometry],
+    style: ClipCapStyle,
+) -> ThreeDResult<()> {
+    let clip_planes = context.clip_planes();
+    let mut aabb = AxisAlignedBoundingBox::EMPTY;
+    for geometry in geometries {
+        aabb.expand_with_aabb(&geometry.aabb());
+    }
+    if aabb.is_empty() || aabb.is_infinite() {
+        return Ok(());
+    }
+
+    // Inverts the lowest bit of the stencil value each time a remaining surface is rendered
+    let stencil_material = ColorMaterial {
+        render_states: RenderStates {
+            write_mask: WriteMask::NONE,
+            depth_test: DepthTest::Always,
+            cull: Cull::None,
+            stencil: Stencil::both(StencilFace {
+                test: StencilTest::Always,
+                reference: 0,
+                read_mask: 0xFF,
+                write_mask: 0x01,
+                fail: StencilOperation::Keep,
+                depth_fail: StencilOperation::Invert,
+                pass: StencilOperation::Invert,
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let cap_material = ClipCapMaterial { style };
+
+    let planes = clip_planes.planes.iter().take(MAX_CLIP_PLANES);
+    let result = planes.clone().enumerate().try_for_each(|(i, plane)| {
+        let length = plane.truncate().magnitude();
+        if length <= 0.0 {
+            return Ok(());
+        }
+        let normal = plane.truncate() / length;
+        let distance = plane.w / length;
+
+        ClearState::stencil(0).apply(context);
+        context.set_clip_planes(clip_planes.clone());
+        for geometry in geometries {
+            geometry.render_with_material(&stencil_material, camera, &[])?;
+        }
+
+        // The cap on this plane is clipped by the other planes, so it only covers the cut surface
+        let others = planes.clone().enumerate().filter(|(j, _)| *j != i);
+        context.set_clip_planes(match clip_planes.operation {
+            ClipOperation::Union => ClipPlanes::new(others.map(|(_, p)| *p).collect()),
+            // The cap is only visible where all the other planes also clip away
+            ClipOperation::Intersection => ClipPlanes::new(others.map(|(_, p)| -*p).collect()),
+        });
+        let rotation = Mat4::from(Quat::from_arc(
+            vec3(0.0, 0.0, 1.0),
+            normal,
+            Some(vec3(1.0, 0.0, 0.0)),
+        ));
+        let center = aabb.center();
+        let center = center - (normal.dot(center) + distance) * normal;
+        let size = aabb.size().magnitude();
+        context.object(
+            |context| Ok(ClipCap(Mesh::new(context, &CpuMesh::square())?)),
+            |cap: &mut ClipCap| {
+                cap.0.set_transformation(
+                    Mat4::from_translation(center) * rotation * Mat4::from_scale(size),
+                );
+                cap.0.render_with_material(&cap_material, camera, &[])
+            },
+        )
+    });
+    context.set_clip_planes(clip_planes);
+    result
+}
+
+// The square which is transformed to each clip plane, stored in the context so it is only created once
+struct ClipCap(Mesh);
+
+struct ClipCapMaterial {
+    style: ClipCapStyle,
+}
+
+impl Material for ClipCapMaterial {
+    fn fragment_shader_source(&self, _use_vertex_colors: bool, _lights: &[&dyn Light]) -> String {
+        let mut shader = String::new();
+        if let ClipCapStyle::Hatch { .. } = self.style {
+            shader.push_str("#define USE_HATCH\n");
+        }
+        shader.push_str(include_str!("../core/shared.frag"));
+        shader.push_str(include_str!("material/shaders/clip_cap.frag"));
+        shader
+    }
+
+    fn use_uniforms(
+        &self,
+        program: &Program,
+        _camera: &Camera,
+        _lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        match self.style {
+            ClipCapStyle::Color(color) => program.use_uniform("surfaceColor", color),
+            ClipCapStyle::Hatch {
+                color,
+                background,
+                spacing,
+                width,
+            } => {
+                program.use_uniform("surfaceColor", background)?;
+                program.use_uniform("hatchColor", color)?;
+                program.use_uniform("hatchSpacing", spacing)?;
+                program.use_uniform("hatchWidth", width)
+            }
+        }
+    }
+
+    fn render_states(&self) -> RenderStates {
+        RenderStates {
+            cull: Cull::None,
+            stencil: Stencil::both(StencilFace::test(StencilTest::NotEqual, 0)),
+            ..Default::default()
+        }
+    }
+
+    fn is_transparent(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,135 @@
+use crate::core::*;
+
+///
+/// A point on a [Polyline](crate::renderer::Polyline) or a [Path2D](crate::renderer::Path2D), which can be split into dashes.
+///
+pub(crate) trait PathPoint: Copy {
+    /// The squared distance below which two consecutive points are considered to be the same point.
+    const TOLERANCE: f32;
+
+    /// The squared distance between the positions of the two points.
+    fn distance2(&self, other: &Self) -> f32;
+
+    /// The point at the given fraction between this and the other point.
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+impl PathPoint for Vec2 {
+    const TOLERANCE: f32 = 0.000001;
+
+    fn distance2(&self, other: &Self) -> f32 {
+        MetricSpace::distance2(*self, *other)
+    }
+
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl PathPoint for (Vec3, Color) {
+    const TOLERANCE: f32 = 0.0;
+
+    fn distance2(&self, other: &Self) -> f32 {
+        self.0.distance2(other.0)
+    }
+
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        let (a, b) = (self.1, other.1);
+        (
+            self.0 + (other.0 - self.0) * t,
+            Color::new(mix(a.r, b.r), mix(a.g, b.g), mix(a.b, b.b), mix(a.a, b.a)),
+        )
+    }
+}
+
+///
+/// Returns the points without consecutive duplicates and, if the line is closed, without a last point equal to the first point.
+///
+pub(crate) fn remove_duplicates<P: PathPoint>(points: &[P], closed: bool) -> Vec<P> {
+    let mut result: Vec<P> = Vec::with_capacity(points.len());
+    for point in points {
+        if result
+            .last()
+            .map(|last| last.distance2(point) > P::TOLERANCE)
+            .unwrap_or(true)
+        {
+            result.push(*point);
+        }
+    }
+    if closed && result.len() > 1 && result[0].distance2(result.last().unwrap()) <= P::TOLERANCE {
+        result.pop();
+    }
+    result
+}
+
+///
+/// Splits the line through the given points into dashes, where the pattern contains the alternating lengths of the dashes and the gaps
+/// and the offset is the distance into the pattern at the start of the line.
+///
+pub(crate) fn dashes<P: PathPoint>(
+    points: &[P],
+    closed: bool,
+    pattern: &[f32],
+    offset: f32,
+) -> Vec<Vec<P>> {
+    let pattern: Vec<f32> = pattern.iter().map(|d| d.max(0.0)).collect();
+    let pattern_length: f32 = pattern.iter().sum();
+    // Find the position in the pattern at the start of the line
+    let mut index = 0;
+    let mut remaining = pattern[0];
+    let mut skip = offset.rem_euclid(pattern_length);
+    while skip > 0.0 {
+        if skip < remaining {
+            remaining -= skip;
+            skip = 0.0;
+        } else {
+            skip -= remaining;
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+    }
+
+    let mut dashes = Vec::new();
+    let mut dash = vec![points[0]];
+    let segment_count = if closed {
+        points.len()
+    } else {
+        points.len() - 1
+    };
+    for i in 0..segment_count {
+        let start = points[i];
+        let end = points[(i + 1) % points.len()];
+        let length = start.distance2(&end).sqrt();
+        let mut travelled = 0.0;
+        while travelled < length {
+            let step = remaining.min(length - travelled);
+            travelled = if step < length - travelled {
+                travelled + step
+            } else {
+                length
+            };
+            let point = start.lerp(&end, travelled / length);
+            if index % 2 == 0 {
+                dash.push(point);
+            }
+            remaining -= step;
+            if remaining <= 0.0 {
+                if index % 2 == 0 && dash.len() > 1 {
+                    dashes.push(std::mem::take(&mut dash));
+                }
+                index = (index + 1) % pattern.len();
+                remaining = pattern[index];
+                dash = vec![point];
+            }
+        }
+    }
+    if index % 2 == 0 && dash.len() > 1 {
+        dashes.push(dash);
+    }
+    dashes
+        .into_iter()
+        .map(|dash| remove_duplicates(&dash, false))
+        .filter(|dash| dash.len() > 1)
+        .collect()
+}
@@ -1,5 +1,5 @@
 //!
-//! Effects applied to each pixel, for example fog or anti-aliasing.
+//! Effects applied to each pixel, for example fog, anti-aliasing, outlines or eye-dome lighting.
 //!
 
 mod fog;
@@ -9,3 +9,11 @@ pub use fog::*;
 mod fxaa;
 #[doc(inline)]
 pub use fxaa::*;
+
+mod outline;
+#[doc(inline)]
+pub use outline::*;
+
+mod eye_dome_lighting;
+#[doc(inline)]
+pub use eye_dome_lighting::*;
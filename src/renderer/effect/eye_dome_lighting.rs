@@ -0,0 +1,65 @@
+use crate::core::*;
+
+///
+/// An effect that improves the perception of depth by darkening pixels that are behind their neighbouring pixels, which outlines the silhouettes and edges in the scene.
+/// This is especially useful for a [PointCloud](crate::renderer::PointCloud) without normals, since it only requires the depth of each pixel and no lighting.
+///
+pub struct EyeDomeLightingEffect {
+    /// The strength of the shading. A higher value gives darker edges.
+    pub strength: f32,
+    /// The distance in pixels to the neighbouring pixels that the depth is compared with. A higher value gives thicker edges.
+    pub radius: f32,
+    image_effect: ImageEffect,
+}
+
+impl EyeDomeLightingEffect {
+    ///
+    /// Constructs a new eye-dome lighting effect with the given strength and radius in pixels.
+    ///
+    pub fn new(context: &Context, strength: f32, radius: f32) -> ThreeDResult<Self> {
+        Ok(Self {
+            strength,
+            radius,
+            image_effect: ImageEffect::new(
+                context,
+                &format!(
+                    "{}{}",
+                    include_str!("../../core/shared.frag"),
+                    include_str!("shaders/eye_dome_lighting.frag")
+                ),
+            )?,
+        })
+    }
+
+    ///
+    /// Apply the eye-dome lighting effect on the current render target based on the given depth map, which must be rendered with the given camera.
+    /// Must be called in the callback given as input to a [RenderTarget], [ColorTarget] or [DepthTarget] write method.
+    ///
+    pub fn apply(&self, camera: &Camera, depth_texture: &DepthTargetTexture2D) -> ThreeDResult<()> {
+        let render_states = RenderStates {
+            write_mask: WriteMask::COLOR,
+            depth_test: DepthTest::Always,
+            blend: Blend::TRANSPARENCY,
+            cull: Cull::Back,
+            ..Default::default()
+        };
+
+        self.image_effect
+            .use_depth_texture("depthMap", depth_texture)?;
+        self.image_effect.use_uniform(
+            "viewProjectionInverse",
+            (camera.projection() * camera.view()).invert().unwrap(),
+        )?;
+        self.image_effect
+            .use_uniform("eyePosition", camera.position())?;
+        self.image_effect.use_uniform("strength", self.strength)?;
+        self.image_effect.use_uniform("radius", self.radius)?;
+        self.image_effect.use_uniform(
+            "resolution",
+            vec2(depth_texture.width() as f32, depth_texture.height() as f32),
+        )?;
+
+        self.image_effect.apply(render_states, camera.viewport())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// An effect that draws a colored outline around the silhouette of a set of objects, which is usually used to highlight selected objects.
+/// The outline can also be drawn where the objects are occluded by other objects, which gives a see-through look.
+///
+/// The effect is used in two steps. First, [OutlineEffect::render_mask] renders the silhouette of the objects to a mask.
+/// Then [OutlineEffect::apply] draws the outline on top of the rendered scene.
+///
+pub struct OutlineEffect {
+    /// The color of the outline where the objects are visible.
+    pub color: Color,
+    /// The color of the outline where the objects are occluded by other objects. If None, the outline is not drawn where the objects are occluded.
+    /// Requires a scene depth texture, see [OutlineEffect::apply].
+    pub occluded_color: Option<Color>,
+    /// The color used to fill the parts of the objects that are occluded by other objects. If None, the occluded parts are not filled.
+    /// Requires a scene depth texture, see [OutlineEffect::apply].
+    pub occluded_fill_color: Option<Color>,
+    /// The width of the outline in pixels.
+    pub width: f32,
+    /// The width in pixels of a glow outside the outline where the outline color fades out. Zero means no glow.
+    pub glow: f32,
+    context: Context,
+    camera: Camera,
+    mask: Option<DepthTargetTexture2D>,
+    image_effect: ImageEffect,
+}
+
+impl OutlineEffect {
+    ///
+    /// Constructs a new outline effect with the given color and width in pixels.
+    /// The outline is drawn with a transparent version of the color where the objects are occluded.
+    ///
+    pub fn new(context: &Context, color: Color, width: f32) -> ThreeDResult<Self> {
+        Ok(Self {
+            color,
+            occluded_color: Some(Color::new(color.r, color.g, color.b, color.a / 2)),
+            occluded_fill_color: None,
+            width,
+            glow: 0.0,
+            context: context.clone(),
+            camera: Camera::new_perspective(
+                context,
+                Viewport::new_at_origo(1, 1),
+                vec3(0.0, 0.0, 1.0),
+                vec3(0.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                degrees(75.0),
+                0.01,
+                10.0,
+            )?,
+            mask: None,
+            image_effect: ImageEffect::new(context, include_str!("shaders/outline.frag"))?,
+        })
+    }
+
+    ///
+    /// Renders the silhouette of the given objects, as seen from the given camera, to the mask used by [OutlineEffect::apply].
+    /// This function must not be called in a render target render function.
+    ///
+    pub fn render_mask(&mut self, camera: &Camera, objects: &[&dyn Object]) -> ThreeDResult<()> {
+        let viewport = Viewport::new_at_origo(camera.viewport().width, camera.viewport().height);
+        match camera.projection_type() {
+            ProjectionType::Perspective { field_of_view_y } => {
+                self.camera.set_perspective_projection(
+                    *field_of_view_y,
+                    camera.z_near(),
+                    camera.z_far(),
+                )?;
+            }
+            ProjectionType::Orthographic { height, .. } => {
+                self.camera.set_orthographic_projection(
+                    *height,
+                    camera.z_near(),
+                    camera.z_far(),
+                )?;
+            }
+        };
+        self.camera.set_viewport(viewport)?;
+        self.camera
+            .set_view(*camera.position(), *camera.target(), *camera.up())?;
+        if self
+            .mask
+            .as_ref()
+            .map(|m| m.width() != viewport.width || m.height() != viewport.height)
+            .unwrap_or(true)
+        {
+            self.mask = Some(DepthTargetTexture2D::new(
+                &self.context,
+                viewport.width,
+                viewport.height,
+                Wrapping::ClampToEdge,
+                Wrapping::ClampToEdge,
+                DepthFormat::Depth32F,
+            )?);
+        }
+        let material = DepthMaterial {
+            render_states: RenderStates {
+                write_mask: WriteMask::DEPTH,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let camera = &self.camera;
+        self.mask
+            .as_mut()
+            .unwrap()
+            .as_depth_target()
+            .clear(ClearState::depth(1.0))?
+            .write(|| {
+                for object in objects.iter().filter(|o| camera.in_frustum(&o.aabb())) {
+                    object.render_with_material(&material, camera, &[])?;
+                }
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    ///
+    /// Draws the outline around the objects given in the last [OutlineEffect::render_mask] call on top of the current render target.
+    /// To distinguish between visible and occluded parts of the objects, the depth texture of the rendered scene must be given.
+    /// It must be rendered with the same camera, for example the depth texture of a [RenderTarget] written with [render_pass]
+    /// or [DeferredPipeline::geometry_pass_depth_texture]. If no scene depth texture is given, the objects are considered visible everywhere.
+    /// Must be called in the callback given as input to a [RenderTarget], [ColorTarget] or [DepthTarget] write method.
+    ///
+    pub fn apply(
+        &self,
+        camera: &Camera,
+        scene_depth_texture: Option<&DepthTargetTexture2D>,
+    ) -> ThreeDResult<()> {
+        if let Some(mask) = &self.mask {
+            self.apply_with_mask(camera, mask, scene_depth_texture)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Draws the outline around the silhouette defined by the given mask on top of the current render target.
+    /// The mask is a depth texture containing the depth of the objects to outline as seen from the given camera and a depth of 1 (the far plane) everywhere else.
+    /// See [OutlineEffect::apply] for a description of the scene depth texture.
+    /// Must be called in the callback given as input to a [RenderTarget], [ColorTarget] or [DepthTarget] write method.
+    ///
+    pub fn apply_with_mask(
+        &self,
+        camera: &Camera,
+        mask: &DepthTargetTexture2D,
+        scene_depth_texture: Option<&DepthTargetTexture2D>,
+    ) -> ThreeDResult<()> {
+        let render_states = RenderStates {
+            write_mask: WriteMask::COLOR,
+            depth_test: DepthTest::Always,
+            blend: Blend::TRANSPARENCY,
+            cull: Cull::Back,
+            ..Default::default()
+        };
+        let transparent = Color::new(0, 0, 0, 0);
+
+        self.image_effect.use_depth_texture("maskMap", mask)?;
+        self.image_effect
+            .use_depth_texture("sceneDepthMap", scene_depth_texture.unwrap_or(mask))?;
+        self.image_effect.use_uniform(
+            "useSceneDepth",
+            if scene_depth_texture.is_some() { 1 } else { 0 },
+        )?;
+        self.image_effect.use_uniform("outlineColor", self.color)?;
+        self.image_effect
+            .use_uniform("occludedColor", self.occluded_color.unwrap_or(transparent))?;
+        self.image_effect.use_uniform(
+            "occludedFillColor",
+            self.occluded_fill_color.unwrap_or(transparent),
+        )?;
+        self.image_effect.use_uniform("width", self.width)?;
+        self.image_effect.use_uniform("glow", self.glow)?;
+        self.image_effect.use_uniform(
+            "resolution",
+            vec2(mask.width() as f32, mask.height() as f32),
+        )?;
+
+        self.image_effect.apply(render_states, camera.viewport())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+uniform sampler2D depthMap;
+
+uniform mat4 viewProjectionInverse;
+uniform vec3 eyePosition;
+uniform float strength;
+uniform float radius;
+uniform vec2 resolution;
+
+in vec2 uv;
+
+layout (location = 0) out vec4 color;
+
+float logDistance(vec2 p) {
+    float depth = texture(depthMap, p).x;
+    if(depth >= 1.0) {
+        // The background is treated as being far away, which darkens the silhouettes
+        return 30.0;
+    }
+    return log2(distance(world_pos_from_depth(viewProjectionInverse, depth, p), eyePosition));
+}
+
+void main()
+{
+    float depth = texture(depthMap, uv).x;
+    if(depth >= 1.0) {
+        discard;
+    }
+    float center = logDistance(uv);
+
+    // Sum how much the eight neighbours are in front of this pixel
+    float response = 0.0;
+    for(int i = 0; i < 8; i++) {
+        float angle = 0.785398 * float(i);
+        vec2 offset = radius * vec2(cos(angle), sin(angle)) / resolution;
+        response += max(0.0, center - logDistance(uv + offset));
+    }
+    float shade = exp(-300.0 * strength * response / 8.0);
+
+    color = vec4(0.0, 0.0, 0.0, 1.0 - shade);
+}
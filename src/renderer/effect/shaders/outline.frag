@@ -0,0 +1,61 @@
+
+uniform sampler2D maskMap;
+uniform sampler2D sceneDepthMap;
+uniform int useSceneDepth;
+
+uniform vec4 outlineColor;
+uniform vec4 occludedColor;
+uniform vec4 occludedFillColor;
+uniform float width;
+uniform float glow;
+uniform vec2 resolution;
+
+in vec2 uv;
+
+layout (location = 0) out vec4 color;
+
+bool isOccluded(vec2 p, float maskDepth) {
+    return useSceneDepth == 1 && maskDepth > texture(sceneDepthMap, p).x + 0.00001;
+}
+
+void main()
+{
+    float maskDepth = texture(maskMap, uv).x;
+    if(maskDepth < 1.0) {
+        // Inside the silhouette, only draw the see-through fill where the object is occluded
+        if(isOccluded(uv, maskDepth) && occludedFillColor.a > 0.0) {
+            color = occludedFillColor;
+            return;
+        }
+        discard;
+    }
+
+    // Find the distance to the closest pixel inside the silhouette
+    float radius = width + glow;
+    int r = int(ceil(min(radius, 32.0)));
+    float minDistance = radius + 1.0;
+    bool occluded = false;
+    for(int x = -r; x <= r; x++) {
+        for(int y = -r; y <= r; y++) {
+            float dist = length(vec2(x, y));
+            if(dist < minDistance) {
+                vec2 p = uv + vec2(x, y) / resolution;
+                float d = texture(maskMap, p).x;
+                if(d < 1.0) {
+                    minDistance = dist;
+                    occluded = isOccluded(p, d);
+                }
+            }
+        }
+    }
+    if(minDistance > radius) {
+        discard;
+    }
+
+    float alpha = minDistance <= width ? 1.0 : 1.0 - (minDistance - width) / glow;
+    color = occluded ? occludedColor : outlineColor;
+    color.a *= alpha;
+    if(color.a <= 0.0) {
+        discard;
+    }
+}
@@ -19,6 +19,18 @@ mod particles;
 #[doc(inline)]
 pub use particles::*;
 
+mod particle_system;
+#[doc(inline)]
+pub use particle_system::*;
+
+mod lines;
+#[doc(inline)]
+pub use lines::*;
+
+mod point_cloud;
+#[doc(inline)]
+pub use point_cloud::*;
+
 use crate::core::*;
 use crate::renderer::*;
 
@@ -139,6 +151,21 @@ pub trait Geometry2D {
     ///
     fn render_with_material(&self, material: &dyn Material, viewport: Viewport)
         -> ThreeDResult<()>;
+
+    ///
+    /// Render the object with the given material as seen by the given 2D camera, ie. the coordinates of the object are in world units instead of pixels.
+    /// Must be called in the callback given as input to a [RenderTarget], [ColorTarget] or [DepthTarget] write method.
+    ///
+    /// The default implementation ignores the pan, zoom and rotation of the camera and renders the object in pixels in the viewport of the camera
+    /// using [Geometry2D::render_with_material].
+    ///
+    fn render_with_material_and_camera(
+        &self,
+        material: &dyn Material,
+        camera: &Camera2D,
+    ) -> ThreeDResult<()> {
+        self.render_with_material(material, camera.viewport())
+    }
 }
 
 impl<T: Geometry2D + ?Sized> Geometry2D for &T {
@@ -149,6 +176,14 @@ impl<T: Geometry2D + ?Sized> Geometry2D for &T {
     ) -> ThreeDResult<()> {
         (*self).render_with_material(material, viewport)
     }
+
+    fn render_with_material_and_camera(
+        &self,
+        material: &dyn Material,
+        camera: &Camera2D,
+    ) -> ThreeDResult<()> {
+        (*self).render_with_material_and_camera(material, camera)
+    }
 }
 
 impl<T: Geometry2D + ?Sized> Geometry2D for &mut T {
@@ -159,6 +194,14 @@ impl<T: Geometry2D + ?Sized> Geometry2D for &mut T {
     ) -> ThreeDResult<()> {
         (**self).render_with_material(material, viewport)
     }
+
+    fn render_with_material_and_camera(
+        &self,
+        material: &dyn Material,
+        camera: &Camera2D,
+    ) -> ThreeDResult<()> {
+        (**self).render_with_material_and_camera(material, camera)
+    }
 }
 
 impl<T: Geometry2D> Geometry2D for Box<T> {
@@ -169,6 +212,15 @@ impl<T: Geometry2D> Geometry2D for Box<T> {
     ) -> ThreeDResult<()> {
         self.as_ref().render_with_material(material, viewport)
     }
+
+    fn render_with_material_and_camera(
+        &self,
+        material: &dyn Material,
+        camera: &Camera2D,
+    ) -> ThreeDResult<()> {
+        self.as_ref()
+            .render_with_material_and_camera(material, camera)
+    }
 }
 
 impl<T: Geometry2D> Geometry2D for std::rc::Rc<T> {
@@ -179,6 +231,15 @@ impl<T: Geometry2D> Geometry2D for std::rc::Rc<T> {
     ) -> ThreeDResult<()> {
         self.as_ref().render_with_material(material, viewport)
     }
+
+    fn render_with_material_and_camera(
+        &self,
+        material: &dyn Material,
+        camera: &Camera2D,
+    ) -> ThreeDResult<()> {
+        self.as_ref()
+            .render_with_material_and_camera(material, camera)
+    }
 }
 
 impl<T: Geometry2D> Geometry2D for std::rc::Rc<std::cell::RefCell<T>> {
@@ -189,6 +250,15 @@ impl<T: Geometry2D> Geometry2D for std::rc::Rc<std::cell::RefCell<T>> {
     ) -> ThreeDResult<()> {
         self.borrow().render_with_material(material, viewport)
     }
+
+    fn render_with_material_and_camera(
+        &self,
+        material: &dyn Material,
+        camera: &Camera2D,
+    ) -> ThreeDResult<()> {
+        self.borrow()
+            .render_with_material_and_camera(material, camera)
+    }
 }
 
 use std::collections::HashMap;
@@ -0,0 +1,295 @@
+use crate::core::*;
+use crate::renderer::dashes::*;
+use crate::renderer::*;
+
+///
+/// The width of the lines in [Lines3D].
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineWidth {
+    /// The width is in pixels, ie. the lines have the same width on the screen independent of the distance to the camera.
+    Pixels(f32),
+    /// The width is in world units, ie. the lines become thinner further away from the camera.
+    World(f32),
+}
+
+///
+/// A line through a sequence of points in 3D, see [Lines3D].
+///
+#[derive(Clone, Debug, Default)]
+pub struct Polyline {
+    /// The points of the line.
+    pub positions: Vec<Vec3>,
+    /// The colors at each point of the line. The colors are interpolated between the points and assumed to be in linear color space.
+    pub colors: Option<Vec<Color>>,
+    /// Whether the line continues from the last point back to the first point.
+    pub closed: bool,
+    /// The dash pattern as alternating lengths of dashes and gaps in world units. An empty pattern gives a solid line.
+    pub dashes: Vec<f32>,
+    /// The distance into the dash pattern at which the line starts.
+    pub dash_offset: f32,
+}
+
+impl Polyline {
+    ///
+    /// Creates a new solid, open polyline through the given points.
+    ///
+    pub fn new(positions: Vec<Vec3>) -> Self {
+        Self {
+            positions,
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Creates a polyline consisting of a single line segment between the two given points.
+    ///
+    pub fn segment(start: Vec3, end: Vec3) -> Self {
+        Self::new(vec![start, end])
+    }
+}
+
+///
+/// A set of thick lines in 3D which are expanded into quads facing the camera in the vertex shader.
+/// Connected line segments are joined with a miter and the edges of the lines are anti-aliased by fading out the vertex colors over one pixel,
+/// so it should be combined with a material which uses the vertex colors and blends transparent colors, for example a transparent [ColorMaterial].
+///
+pub struct Lines3D {
+    context: Context,
+    position_buffer: VertexBuffer,
+    start_buffer: InstanceBuffer,
+    end_buffer: InstanceBuffer,
+    previous_buffer: InstanceBuffer,
+    next_buffer: InstanceBuffer,
+    start_color_buffer: InstanceBuffer,
+    end_color_buffer: InstanceBuffer,
+    aabb_local: AxisAlignedBoundingBox,
+    aabb: AxisAlignedBoundingBox,
+    transformation: Mat4,
+    width: LineWidth,
+    anti_alias: bool,
+}
+
+impl Lines3D {
+    ///
+    /// Creates new lines from the given polylines with the given width.
+    ///
+    pub fn new(context: &Context, polylines: &[Polyline], width: LineWidth) -> ThreeDResult<Self> {
+        // Three quads across the line: the anti-aliased edge on each side and the center
+        let mut template = Vec::new();
+        for (side0, outer0, side1, outer1) in [
+            (-1.0, 1.0, -1.0, 0.0),
+            (-1.0, 0.0, 1.0, 0.0),
+            (1.0, 0.0, 1.0, 1.0),
+        ] {
+            template.extend_from_slice(&[
+                vec3(0.0, side0, outer0),
+                vec3(1.0, side0, outer0),
+                vec3(1.0, side1, outer1),
+                vec3(0.0, side0, outer0),
+                vec3(1.0, side1, outer1),
+                vec3(0.0, side1, outer1),
+            ]);
+        }
+        let mut lines = Self {
+            context: context.clone(),
+            position_buffer: VertexBuffer::new_with_data(context, &template)?,
+            start_buffer: InstanceBuffer::new(context)?,
+            end_buffer: InstanceBuffer::new(context)?,
+            previous_buffer: InstanceBuffer::new(context)?,
+            next_buffer: InstanceBuffer::new(context)?,
+            start_color_buffer: InstanceBuffer::new(context)?,
+            end_color_buffer: InstanceBuffer::new(context)?,
+            aabb_local: AxisAlignedBoundingBox::EMPTY,
+            aabb: AxisAlignedBoundingBox::EMPTY,
+            transformation: Mat4::identity(),
+            width,
+            anti_alias: true,
+        };
+        lines.set_polylines(polylines)?;
+        Ok(lines)
+    }
+
+    ///
+    /// Replaces the lines with the given polylines.
+    ///
+    pub fn set_polylines(&mut self, polylines: &[Polyline]) -> ThreeDResult<()> {
+        let mut starts = Vec::new();
+        let mut ends = Vec::new();
+        let mut previous = Vec::new();
+        let mut next = Vec::new();
+        let mut start_colors = Vec::new();
+        let mut end_colors = Vec::new();
+        let mut push = |points: &[(Vec3, Color)], closed: bool| {
+            let count = points.len();
+            let segment_count = if closed { count } else { count - 1 };
+            for i in 0..segment_count {
+                let (start, start_color) = points[i];
+                let (end, end_color) = points[(i + 1) % count];
+                starts.push(start);
+                ends.push(end);
+                start_colors.push(start_color);
+                end_colors.push(end_color);
+                // A neighbour equal to the segment's own point means that there is no join
+                previous.push(if closed || i > 0 {
+                    points[(i + count - 1) % count].0
+                } else {
+                    start
+                });
+                next.push(if closed || i + 2 < count {
+                    points[(i + 2) % count].0
+                } else {
+                    end
+                });
+            }
+        };
+        for polyline in polylines {
+            let points: Vec<(Vec3, Color)> = polyline
+                .positions
+                .iter()
+                .enumerate()
+                .map(|(i, p)| {
+                    (
+                        *p,
+                        polyline
+                            .colors
+                            .as_ref()
+                            .and_then(|c| c.get(i).copied())
+                            .unwrap_or(Color::WHITE),
+                    )
+                })
+                .collect();
+            let points = remove_duplicates(&points, polyline.closed);
+            if points.len() < 2 {
+                continue;
+            }
+            let pattern_length: f32 = polyline.dashes.iter().map(|d| d.max(0.0)).sum();
+            if pattern_length > 0.0 {
+                for dash in dashes(
+                    &points,
+                    polyline.closed,
+                    &polyline.dashes,
+                    polyline.dash_offset,
+                ) {
+                    push(&dash, false);
+                }
+            } else {
+                push(&points, polyline.closed);
+            }
+        }
+
+        self.aabb_local = AxisAlignedBoundingBox::new_with_positions(&starts);
+        self.aabb_local.expand(&ends);
+        self.update_aabb();
+        self.start_buffer.fill(&starts)?;
+        self.end_buffer.fill(&ends)?;
+        self.previous_buffer.fill(&previous)?;
+        self.next_buffer.fill(&next)?;
+        self.start_color_buffer.fill(&start_colors)?;
+        self.end_color_buffer.fill(&end_colors)?;
+        Ok(())
+    }
+
+    ///
+    /// Returns the width of the lines.
+    ///
+    pub fn width(&self) -> LineWidth {
+        self.width
+    }
+
+    ///
+    /// Set the width of the lines.
+    ///
+    pub fn set_width(&mut self, width: LineWidth) {
+        self.width = width;
+    }
+
+    ///
+    /// Returns whether the edges of the lines are anti-aliased.
+    ///
+    pub fn anti_alias(&self) -> bool {
+        self.anti_alias
+    }
+
+    ///
+    /// Set whether the edges of the lines are anti-aliased, which is enabled by default.
+    /// Disable it when using an opaque material.
+    ///
+    pub fn set_anti_alias(&mut self, anti_alias: bool) {
+        self.anti_alias = anti_alias;
+    }
+
+    ///
+    /// Returns the local to world transformation applied to the lines.
+    ///
+    pub fn transformation(&self) -> Mat4 {
+        self.transformation
+    }
+
+    ///
+    /// Set the local to world transformation applied to the lines.
+    ///
+    pub fn set_transformation(&mut self, transformation: Mat4) {
+        self.transformation = transformation;
+        self.update_aabb();
+    }
+
+    fn update_aabb(&mut self) {
+        let mut aabb = self.aabb_local;
+        aabb.transform(&self.transformation);
+        self.aabb = aabb;
+    }
+}
+
+impl Geometry for Lines3D {
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        if self.start_buffer.instance_count() == 0 {
+            return Ok(());
+        }
+        let fragment_shader_source = material.fragment_shader_source(true, lights);
+        self.context.program(
+            include_str!("shaders/lines.vert"),
+            &fragment_shader_source,
+            |program| {
+                material.use_uniforms(program, camera, lights)?;
+                program.use_clip_planes(camera)?;
+                program.use_uniform_block("Camera", camera.uniform_buffer())?;
+                program.use_uniform("transformation", self.transformation)?;
+                let viewport = camera.viewport();
+                program.use_uniform(
+                    "viewportSize",
+                    vec2(viewport.width as f32, viewport.height as f32),
+                )?;
+                let (width, world_width) = match self.width {
+                    LineWidth::Pixels(width) => (width, 0),
+                    LineWidth::World(width) => (width, 1),
+                };
+                program.use_uniform("width", width)?;
+                program.use_uniform("worldWidth", world_width)?;
+                program.use_uniform("feather", if self.anti_alias { 1.0f32 } else { 0.0 })?;
+                program.use_vertex_attribute("position", &self.position_buffer)?;
+                program.use_instance_attribute("start", &self.start_buffer)?;
+                program.use_instance_attribute("end", &self.end_buffer)?;
+                program.use_instance_attribute("previous", &self.previous_buffer)?;
+                program.use_instance_attribute("next", &self.next_buffer)?;
+                program.use_instance_attribute("startColor", &self.start_color_buffer)?;
+                program.use_instance_attribute("endColor", &self.end_color_buffer)?;
+                program.draw_arrays_instanced(
+                    material.render_states(),
+                    viewport,
+                    18,
+                    self.start_buffer.instance_count(),
+                )
+            },
+        )
+    }
+
+    fn aabb(&self) -> AxisAlignedBoundingBox {
+        self.aabb
+    }
+}
@@ -142,6 +142,7 @@ impl Geometry for Mesh {
             &fragment_shader_source,
             |program| {
                 material.use_uniforms(program, camera, lights)?;
+                program.use_clip_planes(camera)?;
                 program.use_uniform("viewProjection", camera.projection() * camera.view())?;
                 program.use_uniform("modelMatrix", &self.transformation)?;
                 program.use_uniform_if_required("textureTransform", &self.texture_transform)?;
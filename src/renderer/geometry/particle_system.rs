@@ -0,0 +1,740 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// The maximum number of forces in a [ParticleSystem].
+///
+pub const MAX_PARTICLE_FORCES: usize = 8;
+
+///
+/// The maximum number of colliders in a [ParticleSystem].
+///
+pub const MAX_PARTICLE_COLLIDERS: usize = 4;
+
+// The number of samples of the color and size curves
+const CURVE_RESOLUTION: usize = 64;
+// The number of spawned particles in each row of the spawn data texture
+const SPAWNS_PER_ROW: usize = 512;
+
+///
+/// A force applied to all living particles in a [ParticleSystem] each time it is updated.
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ParticleForce {
+    /// A constant acceleration, for example gravity.
+    Acceleration(Vec3),
+    /// Slows down the particles proportional to their velocity with the given coefficient.
+    Drag(f32),
+    /// Accelerates the particles towards the velocity of the wind.
+    /// The strength is the fraction of the difference between the wind velocity and the particle velocity which is added to the particle velocity each second.
+    Wind {
+        /// The velocity of the wind.
+        velocity: Vec3,
+        /// The strength of the wind.
+        strength: f32,
+    },
+    /// Swirls the particles around the axis through the center, proportional to the distance to the axis.
+    /// A positive strength swirls the particles counterclockwise when looking in the opposite direction of the axis.
+    Vortex {
+        /// A point on the axis of the vortex.
+        center: Vec3,
+        /// The direction of the axis of the vortex.
+        axis: Vec3,
+        /// The strength of the vortex.
+        strength: f32,
+    },
+}
+
+impl ParticleForce {
+    fn to_vec4s(self) -> [Vec4; 2] {
+        match self {
+            Self::Acceleration(acceleration) => [acceleration.extend(0.0), Vec4::zero()],
+            Self::Drag(coefficient) => [vec4(0.0, 0.0, 0.0, 1.0), vec4(0.0, 0.0, 0.0, coefficient)],
+            Self::Wind { velocity, strength } => {
+                [velocity.extend(2.0), vec4(0.0, 0.0, 0.0, strength)]
+            }
+            Self::Vortex {
+                center,
+                axis,
+                strength,
+            } => [
+                // A zero axis disables the vortex
+                normalize_or_zero(axis).extend(3.0),
+                center.extend(strength),
+            ],
+        }
+    }
+}
+
+///
+/// A plane which the particles in a [ParticleSystem] bounce off.
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ParticleCollider {
+    /// The plane given as the normal in the xyz components and the distance in the w component, see [ParticleCollider::new].
+    /// Particles on the opposite side of the normal are moved to the plane.
+    pub plane: Vec4,
+    /// The fraction of the speed towards the plane which is kept after bouncing off the plane.
+    /// A value of 0 means that the particles stop at the plane and 1 means a perfect bounce.
+    pub restitution: f32,
+}
+
+impl ParticleCollider {
+    ///
+    /// Creates a collider with the plane going through the given point with the given normal.
+    ///
+    pub fn new(point: Vec3, normal: Vec3, restitution: f32) -> Self {
+        Self {
+            plane: ClipPlanes::plane(point, normal),
+            restitution,
+        }
+    }
+}
+
+///
+/// The surface of a mesh that particles are emitted from, see [ParticleEmitterShape::MeshSurface].
+///
+#[derive(Debug, Clone)]
+pub struct ParticleMeshSurface {
+    triangles: Vec<[Vec3; 3]>,
+    cumulative_areas: Vec<f32>,
+}
+
+impl ParticleMeshSurface {
+    ///
+    /// Creates a surface to emit particles from with the triangles of the given mesh.
+    /// The particles are uniformly distributed on the surface. Triangles without area are ignored.
+    ///
+    pub fn new(cpu_mesh: &CpuMesh) -> Self {
+        let positions = cpu_mesh.positions.to_f32();
+        let indices = cpu_mesh
+            .indices
+            .as_ref()
+            .map(|indices| indices.to_u32())
+            .unwrap_or_else(|| (0..positions.len() as u32).collect());
+        let triangles = indices
+            .chunks_exact(3)
+            .map(|t| {
+                [
+                    positions[t[0] as usize],
+                    positions[t[1] as usize],
+                    positions[t[2] as usize],
+                ]
+            })
+            // Degenerate triangles have no normal to emit the particles along
+            .filter(|t| {
+                let area = (t[1] - t[0]).cross(t[2] - t[0]).magnitude();
+                area > 0.0 && area.is_finite()
+            })
+            .collect::<Vec<_>>();
+        let mut area = 0.0;
+        let cumulative_areas = triangles
+            .iter()
+            .map(|t| {
+                area += 0.5 * (t[1] - t[0]).cross(t[2] - t[0]).magnitude();
+                area
+            })
+            .collect();
+        Self {
+            triangles,
+            cumulative_areas,
+        }
+    }
+
+    fn sample(&self, random: &mut Random) -> (Vec3, Vec3) {
+        let area = self.cumulative_areas.last().copied().unwrap_or(0.0);
+        if area <= 0.0 {
+            return (Vec3::zero(), random.direction());
+        }
+        let a = random.next() * area;
+        let i = self
+            .cumulative_areas
+            .partition_point(|c| *c < a)
+            .min(self.triangles.len() - 1);
+        let [p0, p1, p2] = self.triangles[i];
+        let (mut u, mut v) = (random.next(), random.next());
+        if u + v > 1.0 {
+            u = 1.0 - u;
+            v = 1.0 - v;
+        }
+        (
+            p0 + (p1 - p0) * u + (p2 - p0) * v,
+            (p1 - p0).cross(p2 - p0).normalize(),
+        )
+    }
+}
+
+///
+/// The shape that the particles are emitted from, see [ParticleEmitter].
+///
+#[derive(Debug, Clone)]
+pub enum ParticleEmitterShape {
+    /// All particles are emitted from the origin.
+    Point,
+    /// The particles are emitted from the surface of a sphere centered at the origin with the given radius.
+    Sphere {
+        /// The radius of the sphere.
+        radius: f32,
+    },
+    /// The particles are emitted from the surface of a mesh.
+    MeshSurface(ParticleMeshSurface),
+}
+
+///
+/// Emits particles into a [ParticleSystem], either continuously with the given spawn rate each time the particle system is updated
+/// or as a burst using [ParticleSystem::emit].
+///
+#[derive(Debug, Clone)]
+pub struct ParticleEmitter {
+    /// The shape that the particles are emitted from.
+    pub shape: ParticleEmitterShape,
+    /// The transformation applied to the shape and emit direction.
+    pub transformation: Mat4,
+    /// The number of particles emitted per second when the particle system is updated.
+    pub spawn_rate: f32,
+    /// The minimum and maximum lifetime in seconds. The lifetime of each particle is uniformly distributed in between.
+    pub lifetime: (f32, f32),
+    /// The minimum and maximum initial speed. The speed of each particle is uniformly distributed in between.
+    pub speed: (f32, f32),
+    /// The direction the particles are emitted in.
+    /// If `None`, the particles are emitted in the direction of the surface normal of the shape or in a random direction for the [ParticleEmitterShape::Point] shape.
+    /// If zero, for example `Some(Vec3::zero())`, the particles are emitted without any initial velocity.
+    pub direction: Option<Vec3>,
+    /// The angle between the emit direction and the direction of the particles is uniformly distributed between 0 and this angle.
+    pub spread: Radians,
+}
+
+impl ParticleEmitter {
+    ///
+    /// Creates an emitter which emits particles from the given shape with the given spawn rate in particles per second.
+    /// The particles live for a second and are emitted with a speed of 1 in the default directions, see [ParticleEmitter::direction].
+    ///
+    pub fn new(shape: ParticleEmitterShape, spawn_rate: f32) -> Self {
+        Self {
+            shape,
+            transformation: Mat4::identity(),
+            spawn_rate,
+            lifetime: (1.0, 1.0),
+            speed: (1.0, 1.0),
+            direction: None,
+            spread: radians(0.0),
+        }
+    }
+
+    fn spawn(&self, random: &mut Random) -> [Vec4; 2] {
+        let (position, normal) = match &self.shape {
+            ParticleEmitterShape::Point => (Vec3::zero(), random.direction()),
+            ParticleEmitterShape::Sphere { radius } => {
+                let normal = random.direction();
+                (normal * *radius, normal)
+            }
+            ParticleEmitterShape::MeshSurface(surface) => surface.sample(random),
+        };
+        // A zero direction emits the particles without any initial velocity
+        let direction = normalize_or_zero(self.direction.unwrap_or(normal));
+        let direction = if self.spread.0 > 0.0 && direction != Vec3::zero() {
+            let tangent = if direction.x.abs() < 0.9 {
+                direction.cross(Vec3::unit_x())
+            } else {
+                direction.cross(Vec3::unit_y())
+            }
+            .normalize();
+            let bitangent = direction.cross(tangent);
+            let cos_spread = self.spread.0.cos();
+            let cos_angle = 1.0 - random.next() * (1.0 - cos_spread);
+            let sin_angle = (1.0 - cos_angle * cos_angle).max(0.0).sqrt();
+            let phi = 2.0 * std::f32::consts::PI * random.next();
+            direction * cos_angle + (tangent * phi.cos() + bitangent * phi.sin()) * sin_angle
+        } else {
+            direction
+        };
+        let speed = random.between(self.speed);
+        let lifetime = random.between(self.lifetime);
+        [
+            (self.transformation * position.extend(1.0))
+                .truncate()
+                .extend(0.0),
+            (normalize_or_zero(self.transformation.transform_vector(direction)) * speed)
+                .extend(lifetime),
+        ]
+    }
+}
+
+///
+/// A stateful particle system where particles are emitted by [ParticleEmitter]s, move according to [ParticleForce]s,
+/// bounce off [ParticleCollider]s and die when their lifetime has passed.
+/// The particles are simulated on the GPU by rendering into ping-pong float textures,
+/// so only the emitted particles are transferred from the CPU each time the particle system is updated.
+///
+/// Each particle is rendered as a square facing the camera, with a size and color which is given by curves over the lifetime of the particle,
+/// see [ParticleSystem::set_size_over_life] and [ParticleSystem::set_color_over_life].
+/// The color is passed to the material as a vertex color, for example multiplied with the color of a [ColorMaterial].
+///
+/// **Note:** Requires rendering to 32-bit float textures, which on web requires the `EXT_color_buffer_float` extension.
+///
+pub struct ParticleSystem {
+    context: Context,
+    quad_buffer: VertexBuffer,
+    states: [Texture2DArray; 2],
+    current: usize,
+    sort_keys: [Texture2D; 2],
+    sorted: Option<usize>,
+    spawn_texture: Texture2D,
+    curve_texture: Texture2D,
+    color_over_life: Vec<(f32, Color)>,
+    size_over_life: Vec<(f32, f32)>,
+    capacity: usize,
+    next_slot: usize,
+    pending: Vec<[Vec4; 2]>,
+    spawn_accumulators: Vec<f32>,
+    random: Random,
+    /// The emitters which emit particles each time the particle system is updated.
+    pub emitters: Vec<ParticleEmitter>,
+    /// Up to [MAX_PARTICLE_FORCES] forces applied to the particles.
+    pub forces: Vec<ParticleForce>,
+    /// Up to [MAX_PARTICLE_COLLIDERS] planes that the particles bounce off.
+    pub colliders: Vec<ParticleCollider>,
+}
+
+impl ParticleSystem {
+    ///
+    /// Creates a new particle system with room for the given number of living particles.
+    /// When more particles are emitted, the oldest particles are replaced, so the capacity should be at least the spawn rate times the maximum lifetime.
+    ///
+    pub fn new(context: &Context, capacity: usize) -> ThreeDResult<Self> {
+        let capacity = capacity.max(1);
+        // The number of texels is a power of two to be able to sort the particles
+        let texel_count = capacity.next_power_of_two() as u32;
+        let width = 1 << texel_count.trailing_zeros().div_ceil(2);
+        let height = texel_count / width;
+        let new_state = || -> ThreeDResult<Texture2DArray> {
+            let mut state = Texture2DArray::new_empty::<[f32; 4]>(
+                context,
+                width,
+                height,
+                2,
+                Interpolation::Nearest,
+                Interpolation::Nearest,
+                None,
+                Wrapping::ClampToEdge,
+                Wrapping::ClampToEdge,
+            )?;
+            // A zero age and lifetime means that the particle is dead
+            state
+                .as_color_target(&[0, 1], None)
+                .clear(ClearState::color(0.0, 0.0, 0.0, 0.0))?;
+            Ok(state)
+        };
+        let new_sort_keys = || {
+            Texture2D::new_empty::<[f32; 4]>(
+                context,
+                width,
+                height,
+                Interpolation::Nearest,
+                Interpolation::Nearest,
+                None,
+                Wrapping::ClampToEdge,
+                Wrapping::ClampToEdge,
+            )
+        };
+        let mut particle_system = Self {
+            context: context.clone(),
+            quad_buffer: VertexBuffer::new_with_data(
+                context,
+                &[
+                    vec3(-1.0, -1.0, 0.0),
+                    vec3(1.0, -1.0, 0.0),
+                    vec3(1.0, 1.0, 0.0),
+                    vec3(1.0, 1.0, 0.0),
+                    vec3(-1.0, 1.0, 0.0),
+                    vec3(-1.0, -1.0, 0.0),
+                ],
+            )?,
+            states: [new_state()?, new_state()?],
+            current: 0,
+            sort_keys: [new_sort_keys()?, new_sort_keys()?],
+            sorted: None,
+            spawn_texture: new_spawn_texture(context, 1)?,
+            curve_texture: Texture2D::new_empty::<[f32; 4]>(
+                context,
+                2 * CURVE_RESOLUTION as u32,
+                1,
+                Interpolation::Nearest,
+                Interpolation::Nearest,
+                None,
+                Wrapping::ClampToEdge,
+                Wrapping::ClampToEdge,
+            )?,
+            color_over_life: vec![(0.0, Color::WHITE)],
+            size_over_life: vec![(0.0, 0.1)],
+            capacity,
+            next_slot: 0,
+            pending: Vec::new(),
+            spawn_accumulators: Vec::new(),
+            random: Random(0x9E37_79B9_7F4A_7C15),
+            emitters: Vec::new(),
+            forces: vec![ParticleForce::Acceleration(vec3(0.0, -9.82, 0.0))],
+            colliders: Vec::new(),
+        };
+        particle_system.update_curves()?;
+        Ok(particle_system)
+    }
+
+    ///
+    /// Returns the maximum number of living particles.
+    ///
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    ///
+    /// Sets the color of the particles over their lifetime, given as a list of (time, color) keys where the time is between 0 (when the particle is emitted) and 1 (when the particle dies).
+    /// The color is linearly interpolated between the keys. Default is white.
+    ///
+    pub fn set_color_over_life(&mut self, keys: &[(f32, Color)]) -> ThreeDResult<()> {
+        self.color_over_life = keys.to_vec();
+        self.update_curves()
+    }
+
+    ///
+    /// Sets the size of the particles in world units over their lifetime, given as a list of (time, size) keys where the time is between 0 (when the particle is emitted) and 1 (when the particle dies).
+    /// The size is linearly interpolated between the keys. Default is 0.1.
+    ///
+    pub fn set_size_over_life(&mut self, keys: &[(f32, f32)]) -> ThreeDResult<()> {
+        self.size_over_life = keys.to_vec();
+        self.update_curves()
+    }
+
+    ///
+    /// Emits the given number of particles from the given emitter, ignoring the spawn rate of the emitter.
+    /// The particles are added the next time the particle system is updated.
+    ///
+    pub fn emit(&mut self, emitter: &ParticleEmitter, count: usize) {
+        for _ in 0..count {
+            self.pending.push(emitter.spawn(&mut self.random));
+        }
+    }
+
+    ///
+    /// Moves the simulation of the particles the given time in seconds forward, for example the elapsed time since the last frame,
+    /// after emitting particles from each of the emitters according to their spawn rate.
+    ///
+    pub fn update(&mut self, delta_time: f32) -> ThreeDResult<()> {
+        self.spawn_accumulators.resize(self.emitters.len(), 0.0);
+        for (emitter, accumulator) in self.emitters.iter().zip(self.spawn_accumulators.iter_mut()) {
+            *accumulator += emitter.spawn_rate * delta_time;
+            let count = accumulator.floor();
+            *accumulator -= count;
+            for _ in 0..count as usize {
+                self.pending.push(emitter.spawn(&mut self.random));
+            }
+        }
+        let spawns = std::mem::take(&mut self.pending);
+        let spawns = &spawns[spawns.len().saturating_sub(self.capacity)..];
+        let spawn_rows = self.upload_spawns(spawns)?;
+
+        let mut forces = self
+            .forces
+            .iter()
+            .take(MAX_PARTICLE_FORCES)
+            .flat_map(|f| f.to_vec4s())
+            .collect::<Vec<_>>();
+        let force_count = forces.len() / 2;
+        forces.resize(2 * MAX_PARTICLE_FORCES, Vec4::zero());
+        let mut colliders = self
+            .colliders
+            .iter()
+            .take(MAX_PARTICLE_COLLIDERS)
+            .flat_map(|c| [c.plane, vec4(c.restitution, 0.0, 0.0, 0.0)])
+            .collect::<Vec<_>>();
+        let collider_count = colliders.len() / 2;
+        colliders.resize(2 * MAX_PARTICLE_COLLIDERS, Vec4::zero());
+
+        let (a, b) = self.states.split_at_mut(1);
+        let (source, target) = if self.current == 0 {
+            (&a[0], &mut b[0])
+        } else {
+            (&b[0], &mut a[0])
+        };
+        self.current = 1 - self.current;
+        let viewport = Viewport::new_at_origo(source.width(), source.height());
+        let context = &self.context;
+        let spawn_texture = &self.spawn_texture;
+        let (capacity, next_slot) = (self.capacity, self.next_slot);
+        target.as_color_target(&[0, 1], None).write(|| {
+            context.effect(
+                include_str!("shaders/particle_system_update.frag"),
+                |effect| {
+                    effect.use_texture_array("state", source)?;
+                    effect.use_texture("spawnData", spawn_texture)?;
+                    effect.use_uniform("stateWidth", source.width() as i32)?;
+                    effect.use_uniform("capacity", capacity as i32)?;
+                    effect.use_uniform("spawnStart", next_slot as i32)?;
+                    effect.use_uniform("spawnCount", spawns.len() as i32)?;
+                    effect.use_uniform("spawnsPerRow", SPAWNS_PER_ROW as i32)?;
+                    effect.use_uniform("spawnRows", spawn_rows as i32)?;
+                    effect.use_uniform("deltaTime", delta_time)?;
+                    effect.use_uniform("forceCount", force_count as i32)?;
+                    effect.use_uniform_array("forces", &forces)?;
+                    effect.use_uniform("colliderCount", collider_count as i32)?;
+                    effect.use_uniform_array("colliders", &colliders)?;
+                    effect.apply(
+                        RenderStates {
+                            depth_test: DepthTest::Always,
+                            write_mask: WriteMask::COLOR,
+                            ..Default::default()
+                        },
+                        viewport,
+                    )
+                },
+            )
+        })?;
+        self.next_slot = (self.next_slot + spawns.len()) % self.capacity;
+        Ok(())
+    }
+
+    ///
+    /// Sorts the particles from farthest away to closest to the camera, which is needed for rendering transparent particles correctly.
+    /// The order is used when rendering until the particles are sorted again,
+    /// so this should be called after each [ParticleSystem::update] if the particles are transparent.
+    ///
+    pub fn sort(&mut self, camera: &Camera) -> ThreeDResult<()> {
+        let state = &self.states[self.current];
+        let (width, height) = (state.width(), state.height());
+        let viewport = Viewport::new_at_origo(width, height);
+        let render_states = RenderStates {
+            depth_test: DepthTest::Always,
+            write_mask: WriteMask::COLOR,
+            ..Default::default()
+        };
+        let context = &self.context;
+        self.sort_keys[0].as_color_target(None).write(|| {
+            context.effect(
+                include_str!("shaders/particle_system_sort_keys.frag"),
+                |effect| {
+                    effect.use_texture_array("state", state)?;
+                    effect.use_uniform("stateWidth", width as i32)?;
+                    effect.use_uniform("eyePosition", camera.position())?;
+                    effect.apply(render_states, viewport)
+                },
+            )
+        })?;
+
+        // Bitonic sort, which sorts in a fixed number of passes where each particle is compared to one other particle
+        let texel_count = (width * height) as i32;
+        let mut current = 0;
+        let mut block_size = 2;
+        while block_size <= texel_count {
+            let mut stride = block_size / 2;
+            while stride > 0 {
+                let (source, target) = if current == 0 {
+                    let (a, b) = self.sort_keys.split_at_mut(1);
+                    (&a[0], &mut b[0])
+                } else {
+                    let (a, b) = self.sort_keys.split_at_mut(1);
+                    (&b[0], &mut a[0])
+                };
+                target.as_color_target(None).write(|| {
+                    context.effect(
+                        include_str!("shaders/particle_system_sort.frag"),
+                        |effect| {
+                            effect.use_texture("keys", source)?;
+                            effect.use_uniform("stateWidth", width as i32)?;
+                            effect.use_uniform("blockSize", block_size)?;
+                            effect.use_uniform("stride", stride)?;
+                            effect.apply(render_states, viewport)
+                        },
+                    )
+                })?;
+                current = 1 - current;
+                stride /= 2;
+            }
+            block_size *= 2;
+        }
+        self.sorted = Some(current);
+        Ok(())
+    }
+
+    ///
+    /// Uploads the data of the spawned particles and returns the number of rows in the spawn data texture.
+    ///
+    fn upload_spawns(&mut self, spawns: &[[Vec4; 2]]) -> ThreeDResult<usize> {
+        if spawns.is_empty() {
+            return Ok(self.spawn_texture.height() as usize);
+        }
+        let rows = spawns.len().div_ceil(SPAWNS_PER_ROW).next_power_of_two();
+        if (self.spawn_texture.height() as usize) < rows {
+            self.spawn_texture = new_spawn_texture(&self.context, rows)?;
+        }
+        let spawn_texture = &mut self.spawn_texture;
+        let mut data =
+            vec![[0.0; 4]; spawn_texture.width() as usize * spawn_texture.height() as usize];
+        for (i, spawn) in spawns.iter().enumerate() {
+            data[2 * i] = spawn[0].into();
+            data[2 * i + 1] = spawn[1].into();
+        }
+        spawn_texture.fill(&data)?;
+        Ok(spawn_texture.height() as usize)
+    }
+
+    fn update_curves(&mut self) -> ThreeDResult<()> {
+        let mut data = Vec::with_capacity(2 * CURVE_RESOLUTION);
+        for i in 0..CURVE_RESOLUTION {
+            let t = i as f32 / (CURVE_RESOLUTION - 1) as f32;
+            let color = evaluate_curve(&self.color_over_life, t, Color::WHITE.to_vec4(), |c| {
+                c.to_vec4()
+            });
+            data.push(color.into());
+        }
+        for i in 0..CURVE_RESOLUTION {
+            let t = i as f32 / (CURVE_RESOLUTION - 1) as f32;
+            let size = evaluate_curve(&self.size_over_life, t, 0.0, |s| *s);
+            data.push([size, 0.0, 0.0, 0.0]);
+        }
+        self.curve_texture.fill::<[f32; 4]>(&data)
+    }
+
+    fn vertex_shader_source(fragment_shader_source: &str) -> ThreeDResult<String> {
+        let use_positions = fragment_shader_source.find("in vec3 pos;").is_some();
+        let use_normals = fragment_shader_source.find("in vec3 nor;").is_some();
+        let use_tangents = fragment_shader_source.find("in vec3 tang;").is_some();
+        let use_uvs = fragment_shader_source.find("in vec2 uvs;").is_some();
+        let use_colors = fragment_shader_source.find("in vec4 col;").is_some();
+        Ok(format!(
+            "{}{}{}{}{}{}",
+            if use_positions {
+                "#define USE_POSITIONS\n"
+            } else {
+                ""
+            },
+            if use_normals {
+                "#define USE_NORMALS\n"
+            } else {
+                ""
+            },
+            if use_tangents {
+                if fragment_shader_source.find("in vec3 bitang;").is_none() {
+                    Err(CoreError::MissingBitangent)?;
+                }
+                "#define USE_TANGENTS\n"
+            } else {
+                ""
+            },
+            if use_uvs { "#define USE_UVS\n" } else { "" },
+            if use_colors {
+                "#define USE_COLORS\n"
+            } else {
+                ""
+            },
+            include_str!("shaders/particle_system.vert"),
+        ))
+    }
+}
+
+impl Geometry for ParticleSystem {
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        let fragment_shader_source = material.fragment_shader_source(true, lights);
+        self.context.program(
+            &Self::vertex_shader_source(&fragment_shader_source)?,
+            &fragment_shader_source,
+            |program| {
+                material.use_uniforms(program, camera, lights)?;
+                program.use_clip_planes(camera)?;
+                program.use_uniform_block("Camera", camera.uniform_buffer())?;
+                let state = &self.states[self.current];
+                program.use_texture_array("state", state)?;
+                program.use_uniform("stateWidth", state.width() as i32)?;
+                program.use_uniform("sorted", self.sorted.is_some() as i32)?;
+                program.use_texture("sortedKeys", &self.sort_keys[self.sorted.unwrap_or(0)])?;
+                program.use_texture("curves", &self.curve_texture)?;
+                program.use_uniform("curveResolution", CURVE_RESOLUTION as i32)?;
+                program.use_vertex_attribute("position", &self.quad_buffer)?;
+                program.draw_arrays_instanced(
+                    material.render_states(),
+                    camera.viewport(),
+                    6,
+                    state.width() * state.height(),
+                )
+            },
+        )
+    }
+
+    fn aabb(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::INFINITE
+    }
+}
+
+fn normalize_or_zero(v: Vec3) -> Vec3 {
+    let length = v.magnitude();
+    if length > 0.0 && length.is_finite() {
+        v / length
+    } else {
+        Vec3::zero()
+    }
+}
+
+fn new_spawn_texture(context: &Context, rows: usize) -> ThreeDResult<Texture2D> {
+    Texture2D::new_empty::<[f32; 4]>(
+        context,
+        2 * SPAWNS_PER_ROW as u32,
+        rows as u32,
+        Interpolation::Nearest,
+        Interpolation::Nearest,
+        None,
+        Wrapping::ClampToEdge,
+        Wrapping::ClampToEdge,
+    )
+}
+
+///
+/// Linearly interpolates between the values of the keys, which are sorted by time, at the given time.
+///
+fn evaluate_curve<T, V>(keys: &[(f32, T)], t: f32, default: V, value: impl Fn(&T) -> V) -> V
+where
+    V: Copy + std::ops::Add<Output = V> + std::ops::Mul<f32, Output = V>,
+{
+    let mut keys = keys.iter().collect::<Vec<_>>();
+    keys.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    match keys.iter().position(|k| k.0 > t) {
+        None => keys.last().map_or(default, |k| value(&k.1)),
+        Some(0) => value(&keys[0].1),
+        Some(i) => {
+            let (t0, v0) = (keys[i - 1].0, value(&keys[i - 1].1));
+            let (t1, v1) = (keys[i].0, value(&keys[i].1));
+            let f = (t - t0) / (t1 - t0);
+            v0 * (1.0 - f) + v1 * f
+        }
+    }
+}
+
+///
+/// A small pseudo random number generator (xorshift) used for emitting particles.
+///
+struct Random(u64);
+
+impl Random {
+    /// Returns a number in the range [0, 1).
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn between(&mut self, (min, max): (f32, f32)) -> f32 {
+        min + (max - min) * self.next()
+    }
+
+    /// Returns a uniformly distributed unit vector.
+    fn direction(&mut self) -> Vec3 {
+        let z = 2.0 * self.next() - 1.0;
+        let phi = 2.0 * std::f32::consts::PI * self.next();
+        let r = (1.0 - z * z).max(0.0).sqrt();
+        vec3(r * phi.cos(), r * phi.sin(), z)
+    }
+}
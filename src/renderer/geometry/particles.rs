@@ -17,6 +17,7 @@ pub struct ParticleData {
 /// Each particle is initialised with a position and velocity using the [update](Particles::update) function and a global acceleration.
 /// Then when time passes, their position is updated based on
 /// `new_position = start_position + start_velocity * time + 0.5 * acceleration * time * time`
+/// For particles which are emitted continuously, die, collide or change color over time, use a [ParticleSystem] instead.
 ///
 pub struct Particles {
     context: Context,
@@ -187,6 +188,7 @@ impl Geometry for Particles {
             &fragment_shader_source,
             |program| {
                 material.use_uniforms(program, camera, lights)?;
+                program.use_clip_planes(camera)?;
 
                 program.use_uniform("modelMatrix", &self.transformation)?;
                 program.use_uniform("acceleration", &self.acceleration)?;
@@ -0,0 +1,375 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// The size of the points in a [PointCloud].
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PointSize {
+    /// The size is the diameter in pixels, ie. the points have the same size on the screen independent of the distance to the camera.
+    Pixels(f32),
+    /// The size is the diameter in world units, ie. the points become smaller further away from the camera.
+    World(f32),
+}
+
+///
+/// The shape of each point, also called a splat, in a [PointCloud].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplatShape {
+    /// A square facing the camera. This is the fastest shape to render.
+    Square,
+    /// A circle facing the camera.
+    Circle,
+    /// A circle facing the camera which fades out from the center following a Gaussian curve, so the points blend smoothly together.
+    /// Requires a material which blends transparent colors, for example a transparent [ColorMaterial].
+    Gaussian,
+}
+
+// The maximum number of points in a leaf node of the octree, ie. the number of points rendered in one draw call
+const MAX_POINTS_PER_NODE: usize = 65536;
+const MAX_OCTREE_DEPTH: u32 = 16;
+
+///
+/// A point cloud, for example a lidar scan, which can contain millions of points with a position, color, normal and intensity.
+/// Each point is rendered as a splat facing the camera with the given [PointSize] and [SplatShape].
+/// The points are organized in an octree, so only the parts of the point cloud inside the camera frustum are rendered.
+///
+/// Use the [EyeDomeLightingEffect] to improve the depth perception of point clouds without normals.
+///
+pub struct PointCloud {
+    context: Context,
+    splat_buffer: VertexBuffer,
+    splat_vertex_count: u32,
+    root: Option<OctreeNode>,
+    point_count: usize,
+    use_colors: bool,
+    use_normals: bool,
+    use_intensities: bool,
+    aabb_local: AxisAlignedBoundingBox,
+    aabb: AxisAlignedBoundingBox,
+    transformation: Mat4,
+    size: PointSize,
+    shape: SplatShape,
+}
+
+impl PointCloud {
+    ///
+    /// Creates a new point cloud from the given [CpuPointCloud] where each point is rendered as a circle with the given size.
+    ///
+    pub fn new(
+        context: &Context,
+        cpu_point_cloud: &CpuPointCloud,
+        size: PointSize,
+    ) -> ThreeDResult<Self> {
+        let shape = SplatShape::Circle;
+        let splat = splat_positions(shape);
+        let mut point_cloud = Self {
+            context: context.clone(),
+            splat_buffer: VertexBuffer::new_with_data(context, &splat)?,
+            splat_vertex_count: splat.len() as u32,
+            root: None,
+            point_count: 0,
+            use_colors: false,
+            use_normals: false,
+            use_intensities: false,
+            aabb_local: AxisAlignedBoundingBox::EMPTY,
+            aabb: AxisAlignedBoundingBox::EMPTY,
+            transformation: Mat4::identity(),
+            size,
+            shape,
+        };
+        point_cloud.set_point_cloud(cpu_point_cloud)?;
+        Ok(point_cloud)
+    }
+
+    ///
+    /// Replaces the points with the points in the given [CpuPointCloud].
+    ///
+    pub fn set_point_cloud(&mut self, cpu_point_cloud: &CpuPointCloud) -> ThreeDResult<()> {
+        cpu_point_cloud.validate()?;
+        self.point_count = cpu_point_cloud.len();
+        self.use_colors = cpu_point_cloud.colors.is_some();
+        self.use_normals = cpu_point_cloud.normals.is_some();
+        self.use_intensities = cpu_point_cloud.intensities.is_some();
+        self.root = if cpu_point_cloud.is_empty() {
+            None
+        } else {
+            Some(OctreeNode::new(
+                &self.context,
+                cpu_point_cloud,
+                (0..cpu_point_cloud.len() as u32).collect(),
+                0,
+            )?)
+        };
+        self.aabb_local = cpu_point_cloud.compute_aabb();
+        self.update_aabb();
+        Ok(())
+    }
+
+    ///
+    /// Returns the number of points.
+    ///
+    pub fn point_count(&self) -> usize {
+        self.point_count
+    }
+
+    ///
+    /// Returns the size of the points.
+    ///
+    pub fn size(&self) -> PointSize {
+        self.size
+    }
+
+    ///
+    /// Set the size of the points.
+    ///
+    pub fn set_size(&mut self, size: PointSize) {
+        self.size = size;
+    }
+
+    ///
+    /// Returns the shape of the points.
+    ///
+    pub fn shape(&self) -> SplatShape {
+        self.shape
+    }
+
+    ///
+    /// Set the shape of the points.
+    ///
+    pub fn set_shape(&mut self, shape: SplatShape) -> ThreeDResult<()> {
+        if self.shape != shape {
+            let splat = splat_positions(shape);
+            self.splat_buffer.fill(&splat)?;
+            self.splat_vertex_count = splat.len() as u32;
+            self.shape = shape;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Returns the local to world transformation applied to the point cloud.
+    ///
+    pub fn transformation(&self) -> Mat4 {
+        self.transformation
+    }
+
+    ///
+    /// Set the local to world transformation applied to the point cloud.
+    ///
+    pub fn set_transformation(&mut self, transformation: Mat4) {
+        self.transformation = transformation;
+        self.update_aabb();
+    }
+
+    fn update_aabb(&mut self) {
+        let mut aabb = self.aabb_local;
+        aabb.transform(&self.transformation);
+        self.aabb = aabb;
+    }
+}
+
+impl Geometry for PointCloud {
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        let mut nodes = Vec::new();
+        if let Some(ref root) = self.root {
+            root.visible_nodes(camera, &self.transformation, &mut nodes);
+        }
+        if nodes.is_empty() {
+            return Ok(());
+        }
+        let fragment_shader_source = material.fragment_shader_source(true, lights);
+        self.context.program(
+            include_str!("shaders/point_cloud.vert"),
+            &fragment_shader_source,
+            |program| {
+                material.use_uniforms(program, camera, lights)?;
+                program.use_clip_planes(camera)?;
+                program.use_uniform_block("Camera", camera.uniform_buffer())?;
+                program.use_uniform("transformation", self.transformation)?;
+                program.use_uniform_if_required(
+                    "normalMatrix",
+                    self.transformation.invert().unwrap().transpose(),
+                )?;
+                let viewport = camera.viewport();
+                program.use_uniform(
+                    "viewportSize",
+                    vec2(viewport.width as f32, viewport.height as f32),
+                )?;
+                let (size, world_size) = match self.size {
+                    PointSize::Pixels(size) => (size, 0),
+                    PointSize::World(size) => (size, 1),
+                };
+                program.use_uniform("pointSize", size)?;
+                program.use_uniform("worldSize", world_size)?;
+                program.use_uniform_if_required("useColors", self.use_colors as i32)?;
+                program.use_uniform_if_required("useNormals", self.use_normals as i32)?;
+                program.use_uniform_if_required("useIntensities", self.use_intensities as i32)?;
+                for node in nodes {
+                    program.use_vertex_attribute("position", &self.splat_buffer)?;
+                    program.use_instance_attribute("center", &node.center_buffer)?;
+                    for (name, buffer) in [
+                        ("color", &node.color_buffer),
+                        ("normal", &node.normal_buffer),
+                        ("intensity", &node.intensity_buffer),
+                    ] {
+                        if let Some(buffer) = buffer {
+                            if program.requires_attribute(name) {
+                                program.use_instance_attribute(name, buffer)?;
+                            }
+                        }
+                    }
+                    program.draw_arrays_instanced(
+                        material.render_states(),
+                        viewport,
+                        self.splat_vertex_count,
+                        node.center_buffer.instance_count(),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    fn aabb(&self) -> AxisAlignedBoundingBox {
+        self.aabb
+    }
+}
+
+///
+/// A node in the octree containing the points inside its bounding box, either directly in the buffers if it is a leaf node or in its children.
+///
+struct OctreeNode {
+    aabb: AxisAlignedBoundingBox,
+    children: Vec<OctreeNode>,
+    center_buffer: InstanceBuffer,
+    color_buffer: Option<InstanceBuffer>,
+    normal_buffer: Option<InstanceBuffer>,
+    intensity_buffer: Option<InstanceBuffer>,
+}
+
+impl OctreeNode {
+    fn new(
+        context: &Context,
+        cpu_point_cloud: &CpuPointCloud,
+        indices: Vec<u32>,
+        depth: u32,
+    ) -> ThreeDResult<Self> {
+        let positions: Vec<Vec3> = indices
+            .iter()
+            .map(|i| cpu_point_cloud.positions[*i as usize])
+            .collect();
+        let aabb = AxisAlignedBoundingBox::new_with_positions(&positions);
+        if indices.len() <= MAX_POINTS_PER_NODE || depth >= MAX_OCTREE_DEPTH {
+            return Ok(Self {
+                aabb,
+                children: Vec::new(),
+                center_buffer: InstanceBuffer::new_with_data(context, &positions)?,
+                color_buffer: gather(context, &cpu_point_cloud.colors, &indices)?,
+                normal_buffer: gather(context, &cpu_point_cloud.normals, &indices)?,
+                intensity_buffer: gather(context, &cpu_point_cloud.intensities, &indices)?,
+            });
+        }
+
+        let center = aabb.center();
+        let mut octants = vec![Vec::new(); 8];
+        for (index, position) in indices.into_iter().zip(positions) {
+            let octant = (position.x > center.x) as usize
+                | ((position.y > center.y) as usize) << 1
+                | ((position.z > center.z) as usize) << 2;
+            octants[octant].push(index);
+        }
+        let children = octants
+            .into_iter()
+            .filter(|octant| !octant.is_empty())
+            .map(|octant| Self::new(context, cpu_point_cloud, octant, depth + 1))
+            .collect::<ThreeDResult<Vec<_>>>()?;
+        Ok(Self {
+            aabb,
+            children,
+            center_buffer: InstanceBuffer::new(context)?,
+            color_buffer: None,
+            normal_buffer: None,
+            intensity_buffer: None,
+        })
+    }
+
+    fn visible_nodes<'a>(
+        &'a self,
+        camera: &Camera,
+        transformation: &Mat4,
+        nodes: &mut Vec<&'a OctreeNode>,
+    ) {
+        let mut aabb = self.aabb;
+        aabb.transform(transformation);
+        if !camera.in_frustum(&aabb) {
+            return;
+        }
+        if self.children.is_empty() {
+            nodes.push(self);
+        }
+        for child in self.children.iter() {
+            child.visible_nodes(camera, transformation, nodes);
+        }
+    }
+}
+
+fn gather<T: BufferDataType + Copy>(
+    context: &Context,
+    values: &Option<Vec<T>>,
+    indices: &[u32],
+) -> ThreeDResult<Option<InstanceBuffer>> {
+    values
+        .as_ref()
+        .map(|values| {
+            let values: Vec<T> = indices.iter().map(|i| values[*i as usize]).collect();
+            InstanceBuffer::new_with_data(context, &values)
+        })
+        .transpose()
+}
+
+///
+/// Returns the triangles of a splat with the given shape, where the x and y coordinates are the offset from the center in the range `[-1..1]` and the z coordinate is the opacity.
+///
+fn splat_positions(shape: SplatShape) -> Vec<Vec3> {
+    let ring = |count: usize, i: usize, radius: f32, alpha: f32| {
+        let angle = 2.0 * std::f32::consts::PI * (i % count) as f32 / count as f32;
+        vec3(radius * angle.cos(), radius * angle.sin(), alpha)
+    };
+    let center = vec3(0.0, 0.0, 1.0);
+    match shape {
+        SplatShape::Square => vec![
+            vec3(-1.0, -1.0, 1.0),
+            vec3(1.0, -1.0, 1.0),
+            vec3(1.0, 1.0, 1.0),
+            vec3(1.0, 1.0, 1.0),
+            vec3(-1.0, 1.0, 1.0),
+            vec3(-1.0, -1.0, 1.0),
+        ],
+        SplatShape::Circle => (0..12)
+            .flat_map(|i| [center, ring(12, i, 1.0, 1.0), ring(12, i + 1, 1.0, 1.0)])
+            .collect(),
+        SplatShape::Gaussian => {
+            // The opacity is exp(-2 r^2) at the inner ring and fades to zero at the edge
+            let inner_alpha = (-0.5f32).exp();
+            (0..8)
+                .flat_map(|i| {
+                    let (inner0, inner1) = (
+                        ring(8, i, 0.5, inner_alpha),
+                        ring(8, i + 1, 0.5, inner_alpha),
+                    );
+                    let (outer0, outer1) = (ring(8, i, 1.0, 0.0), ring(8, i + 1, 1.0, 0.0));
+                    [
+                        center, inner0, inner1, inner0, outer0, outer1, inner0, outer1, inner1,
+                    ]
+                })
+                .collect()
+        }
+    }
+}
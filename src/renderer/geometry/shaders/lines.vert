@@ -0,0 +1,98 @@
+
+layout (std140) uniform Camera
+{
+    mat4 viewProjection;
+    mat4 view;
+    mat4 projection;
+    vec3 position;
+    float padding;
+} camera;
+
+uniform mat4 transformation;
+uniform vec2 viewportSize;
+uniform float width;
+uniform int worldWidth;
+uniform float feather;
+
+// The position along the segment (0 or 1), the side of the line (-1 or 1) and whether it is on the outer edge of the anti-aliasing (0 or 1)
+in vec3 position;
+
+in vec3 start;
+in vec3 end;
+in vec3 previous;
+in vec3 next;
+in vec4 startColor;
+in vec4 endColor;
+
+out vec3 pos;
+out vec2 uvs;
+out vec4 col;
+
+vec2 screen(vec4 clip)
+{
+    return 0.5 * viewportSize * clip.xy / clip.w;
+}
+
+// Moves the point towards the other point such that it is in front of the camera
+vec4 clipNear(vec4 point, vec4 other)
+{
+    float near = 0.0001;
+    if (point.w < near && other.w > point.w) {
+        point = mix(point, other, (near - point.w) / (other.w - point.w));
+    }
+    return point;
+}
+
+void main()
+{
+    bool isStart = position.x < 0.5;
+    vec4 worldStart = transformation * vec4(start, 1.0);
+    vec4 worldEnd = transformation * vec4(end, 1.0);
+    vec4 clipStart = clipNear(camera.viewProjection * worldStart, camera.viewProjection * worldEnd);
+    vec4 clipEnd = clipNear(camera.viewProjection * worldEnd, camera.viewProjection * worldStart);
+    vec4 clip = isStart ? clipStart : clipEnd;
+
+    vec2 delta = screen(clipEnd) - screen(clipStart);
+    vec2 direction = length(delta) > 0.00001 ? normalize(delta) : vec2(1.0, 0.0);
+    vec2 normal = vec2(-direction.y, direction.x);
+
+    // Use a miter join with the neighbouring segment, if any
+    vec3 neighbour = isStart ? previous : next;
+    vec3 point = isStart ? start : end;
+    vec2 offset = normal;
+    if (distance(neighbour, point) > 0.0) {
+        vec4 clipNeighbour = camera.viewProjection * transformation * vec4(neighbour, 1.0);
+        if (clipNeighbour.w > 0.0 && clip.w > 0.0) {
+            vec2 neighbourDelta = isStart ? screen(clip) - screen(clipNeighbour) : screen(clipNeighbour) - screen(clip);
+            if (length(neighbourDelta) > 0.00001) {
+                vec2 neighbourDirection = normalize(neighbourDelta);
+                vec2 neighbourNormal = vec2(-neighbourDirection.y, neighbourDirection.x);
+                vec2 miter = normal + neighbourNormal;
+                if (length(miter) > 0.00001) {
+                    miter = normalize(miter);
+                    offset = miter / max(dot(miter, normal), 0.25);
+                }
+            }
+        }
+    }
+
+    float halfWidth = 0.5 * width;
+    if (worldWidth == 1) {
+        // Convert the width to pixels at the depth of the point
+        halfWidth *= 0.5 * viewportSize.y * camera.projection[1][1] / clip.w;
+    }
+    float outer = halfWidth + 0.5 * feather;
+    float inner = max(halfWidth - 0.5 * feather, 0.0);
+    float alpha = feather > 0.0 ? min(2.0 * halfWidth / feather, 1.0) : 1.0;
+    if (position.z > 0.5) {
+        alpha = feather > 0.0 ? 0.0 : alpha;
+    }
+    vec2 pixelOffset = offset * position.y * (position.z > 0.5 ? outer : inner);
+    clip.xy += pixelOffset / (0.5 * viewportSize) * clip.w;
+    gl_Position = clip;
+
+    pos = (isStart ? worldStart : worldEnd).xyz;
+    uvs = vec2(position.x, 0.5 + 0.5 * position.y);
+    col = (isStart ? startColor : endColor) / 255.0;
+    col.a *= alpha;
+}
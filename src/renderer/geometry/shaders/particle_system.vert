@@ -0,0 +1,89 @@
+layout (std140) uniform Camera
+{
+    mat4 viewProjection;
+    mat4 view;
+    mat4 projection;
+    vec3 position;
+    float padding;
+} camera;
+
+uniform sampler2DArray state;
+uniform sampler2D sortedKeys;
+uniform int sorted;
+uniform int stateWidth;
+uniform sampler2D curves;
+uniform int curveResolution;
+
+// The offset from the center of the particle in the range [-1, 1]
+in vec3 position;
+
+#ifdef USE_POSITIONS
+out vec3 pos;
+#endif
+
+#ifdef USE_NORMALS
+out vec3 nor;
+#endif
+
+#ifdef USE_TANGENTS
+out vec3 tang;
+out vec3 bitang;
+#endif
+
+#ifdef USE_UVS
+out vec2 uvs;
+#endif
+
+#ifdef USE_COLORS
+out vec4 col;
+#endif
+
+vec4 curve(int offset, float t)
+{
+    float x = clamp(t, 0.0, 1.0) * float(curveResolution - 1);
+    int i = min(int(x), curveResolution - 2);
+    vec4 a = texelFetch(curves, ivec2(offset + i, 0), 0);
+    vec4 b = texelFetch(curves, ivec2(offset + i + 1, 0), 0);
+    return mix(a, b, x - float(i));
+}
+
+void main()
+{
+    int index = gl_InstanceID;
+    if (sorted == 1) {
+        index = int(texelFetch(sortedKeys, ivec2(index % stateWidth, index / stateWidth), 0).y);
+    }
+    ivec3 texel = ivec3(index % stateWidth, index / stateWidth, 0);
+    vec4 p = texelFetch(state, texel, 0);
+    float lifetime = texelFetch(state, texel + ivec3(0, 0, 1), 0).w;
+    if (p.w >= lifetime) {
+        // Dead particles are moved outside the view
+        gl_Position = vec4(2.0, 2.0, 2.0, 1.0);
+        return;
+    }
+    float t = p.w / lifetime;
+    float size = curve(curveResolution, t).x;
+
+    vec3 right = vec3(camera.view[0][0], camera.view[1][0], camera.view[2][0]);
+    vec3 up = vec3(camera.view[0][1], camera.view[1][1], camera.view[2][1]);
+    vec3 worldPosition = p.xyz + 0.5 * size * (position.x * right + position.y * up);
+    gl_Position = camera.viewProjection * vec4(worldPosition, 1.0);
+
+#ifdef USE_POSITIONS
+    pos = worldPosition;
+#endif
+#ifdef USE_NORMALS
+    nor = normalize(camera.position - p.xyz);
+#endif
+#ifdef USE_TANGENTS
+    // The texture coordinates of the camera facing quad increase along the right and up directions
+    tang = normalize(right);
+    bitang = normalize(up);
+#endif
+#ifdef USE_UVS
+    uvs = 0.5 + 0.5 * position.xy;
+#endif
+#ifdef USE_COLORS
+    col = curve(0, t);
+#endif
+}
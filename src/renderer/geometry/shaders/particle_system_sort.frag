@@ -0,0 +1,28 @@
+uniform sampler2D keys;
+uniform int stateWidth;
+uniform int blockSize;
+uniform int stride;
+
+in vec2 uv;
+
+layout (location = 0) out vec4 outKey;
+
+// Orders by key and then index, so no two entries are equal
+bool greater(vec4 a, vec4 b)
+{
+    return a.x > b.x || (a.x == b.x && a.y > b.y);
+}
+
+void main()
+{
+    ivec2 texel = ivec2(gl_FragCoord.xy);
+    int index = texel.y * stateWidth + texel.x;
+    int partner = index ^ stride;
+    vec4 a = texelFetch(keys, texel, 0);
+    vec4 b = texelFetch(keys, ivec2(partner % stateWidth, partner / stateWidth), 0);
+
+    // One step of a bitonic sort in descending order
+    bool descending = (index & blockSize) == 0;
+    bool keepGreatest = (index < partner) == descending;
+    outKey = greater(a, b) == keepGreatest ? a : b;
+}
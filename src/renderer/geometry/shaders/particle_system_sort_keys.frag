@@ -0,0 +1,17 @@
+uniform sampler2DArray state;
+uniform int stateWidth;
+uniform vec3 eyePosition;
+
+in vec2 uv;
+
+layout (location = 0) out vec4 outKey;
+
+void main()
+{
+    ivec2 texel = ivec2(gl_FragCoord.xy);
+    vec4 p = texelFetch(state, ivec3(texel, 0), 0);
+    vec4 v = texelFetch(state, ivec3(texel, 1), 0);
+    // Dead particles are sorted last
+    float key = p.w < v.w ? distance(p.xyz, eyePosition) : -1.0;
+    outKey = vec4(key, float(texel.y * stateWidth + texel.x), 0.0, 0.0);
+}
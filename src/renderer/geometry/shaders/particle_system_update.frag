@@ -0,0 +1,78 @@
+uniform sampler2DArray state;
+uniform sampler2D spawnData;
+uniform int stateWidth;
+uniform int capacity;
+uniform int spawnStart;
+uniform int spawnCount;
+uniform int spawnsPerRow;
+uniform int spawnRows;
+uniform float deltaTime;
+
+// Each force is two vectors: (vector, type) and (point, parameter)
+uniform vec4 forces[16];
+uniform int forceCount;
+// Each collider is two vectors: the plane and (restitution, 0, 0, 0)
+uniform vec4 colliders[8];
+uniform int colliderCount;
+
+in vec2 uv;
+
+layout (location = 0) out vec4 outPosition;
+layout (location = 1) out vec4 outVelocity;
+
+vec3 acceleration(vec3 p, vec3 v)
+{
+    vec3 a = vec3(0.0);
+    for (int i = 0; i < forceCount; i++) {
+        vec4 f0 = forces[2*i];
+        vec4 f1 = forces[2*i+1];
+        int type = int(f0.w);
+        if (type == 0) { // Acceleration
+            a += f0.xyz;
+        } else if (type == 1) { // Drag
+            a -= f1.w * v;
+        } else if (type == 2) { // Wind
+            a += f1.w * (f0.xyz - v);
+        } else if (type == 3) { // Vortex
+            vec3 r = p - f1.xyz;
+            r -= dot(r, f0.xyz) * f0.xyz;
+            a += f1.w * cross(f0.xyz, r);
+        }
+    }
+    return a;
+}
+
+void main()
+{
+    ivec2 texel = ivec2(gl_FragCoord.xy);
+    int index = texel.y * stateWidth + texel.x;
+    vec4 p = texelFetch(state, ivec3(texel, 0), 0);
+    vec4 v = texelFetch(state, ivec3(texel, 1), 0);
+
+    int spawnIndex = (index - spawnStart + capacity) % capacity;
+    if (index < capacity && spawnIndex < spawnCount) {
+        // The rows of the spawn data are flipped when uploaded
+        ivec2 spawnTexel = ivec2(2 * (spawnIndex % spawnsPerRow), spawnRows - 1 - spawnIndex / spawnsPerRow);
+        outPosition = vec4(texelFetch(spawnData, spawnTexel, 0).xyz, 0.0);
+        outVelocity = texelFetch(spawnData, spawnTexel + ivec2(1, 0), 0);
+        return;
+    }
+
+    // The age is stored in the w component of the position and the lifetime in the w component of the velocity
+    if (p.w < v.w) {
+        v.xyz += acceleration(p.xyz, v.xyz) * deltaTime;
+        p.xyz += v.xyz * deltaTime;
+        p.w += deltaTime;
+        for (int i = 0; i < colliderCount; i++) {
+            vec4 plane = colliders[2*i];
+            float distance = dot(plane.xyz, p.xyz) + plane.w;
+            float speed = dot(plane.xyz, v.xyz);
+            if (distance < 0.0 && speed < 0.0) {
+                p.xyz -= distance * plane.xyz;
+                v.xyz -= (1.0 + colliders[2*i+1].x) * speed * plane.xyz;
+            }
+        }
+    }
+    outPosition = p;
+    outVelocity = v;
+}
@@ -0,0 +1,54 @@
+layout (std140) uniform Camera
+{
+    mat4 viewProjection;
+    mat4 view;
+    mat4 projection;
+    vec3 position;
+    float padding;
+} camera;
+
+uniform mat4 transformation;
+uniform mat4 normalMatrix;
+uniform vec2 viewportSize;
+uniform float pointSize;
+uniform int worldSize;
+uniform int useColors;
+uniform int useNormals;
+uniform int useIntensities;
+
+// The offset from the center of the splat in the range [-1, 1] and the opacity at that offset
+in vec3 position;
+
+in vec3 center;
+in vec4 color;
+in vec3 normal;
+in float intensity;
+
+out vec3 pos;
+out vec3 nor;
+out vec2 uvs;
+out vec4 col;
+
+void main()
+{
+    vec4 worldCenter = transformation * vec4(center, 1.0);
+    vec4 clip = camera.viewProjection * worldCenter;
+
+    float halfSize = 0.5 * pointSize;
+    if (worldSize == 1) {
+        // Convert the size to pixels at the depth of the point
+        halfSize *= 0.5 * viewportSize.y * camera.projection[1][1] / clip.w;
+    }
+    clip.xy += position.xy * halfSize / (0.5 * viewportSize) * clip.w;
+    gl_Position = clip;
+
+    pos = worldCenter.xyz;
+    // Points without normals are lit as if they are facing the camera
+    nor = useNormals == 1 ? normalize(mat3(normalMatrix) * normal) : normalize(camera.position - pos);
+    uvs = 0.5 + 0.5 * position.xy;
+    col = useColors == 1 ? color / 255.0 : vec4(1.0);
+    if (useIntensities == 1) {
+        col.rgb *= intensity;
+    }
+    col.a *= position.z;
+}
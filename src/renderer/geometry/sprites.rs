@@ -96,6 +96,7 @@ impl Geometry for Sprites {
             &fragment_shader_source,
             |program| {
                 material.use_uniforms(program, camera, lights)?;
+                program.use_clip_planes(camera)?;
                 program.use_uniform_block("Camera", camera.uniform_buffer())?;
                 program.use_uniform("transformation", self.transformation)?;
                 program.use_vertex_attribute("position", &self.position_buffer)?;
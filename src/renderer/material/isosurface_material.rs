@@ -27,6 +27,7 @@ pub struct IsourfaceMaterial {
 impl Material for IsourfaceMaterial {
     fn fragment_shader_source(&self, _use_vertex_colors: bool, lights: &[&dyn Light]) -> String {
         let mut output = lights_fragment_shader_source(lights, self.lighting_model);
+        output.push_str(include_str!("shaders/clip_planes.frag"));
         output.push_str(include_str!("shaders/isosurface_material.frag"));
         output
     }
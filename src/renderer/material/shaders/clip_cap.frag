@@ -0,0 +1,28 @@
+uniform vec4 surfaceColor;
+
+#ifdef USE_HATCH
+uniform vec4 hatchColor;
+uniform float hatchSpacing;
+uniform float hatchWidth;
+in vec3 pos;
+in vec3 nor;
+#endif
+
+layout (location = 0) out vec4 outColor;
+
+void main()
+{
+    outColor = surfaceColor;
+
+#ifdef USE_HATCH
+    // Diagonal lines in the plane of the cap
+    vec3 normal = normalize(nor);
+    vec3 tangent = normalize(abs(normal.y) < 0.99 ? cross(normal, vec3(0.0, 1.0, 0.0)) : cross(normal, vec3(1.0, 0.0, 0.0)));
+    vec3 direction = normalize(tangent + cross(normal, tangent));
+    if(fract(dot(pos, direction) / hatchSpacing) * hatchSpacing < hatchWidth) {
+        outColor = hatchColor;
+    }
+#endif
+
+    outColor.rgb = srgb_from_rgb(outColor.rgb);
+}
@@ -0,0 +1,19 @@
+
+uniform vec4 clipPlanes[8];
+uniform int clipPlaneCount;
+uniform int clipIntersection;
+
+// Returns true if the given point is clipped away by the clip planes set on the context
+bool is_clipped_by_context(vec3 p) {
+    if(clipPlaneCount == 0) {
+        return false;
+    }
+    bool clipped = clipIntersection == 1;
+    for(int i = 0; i < 8; i++) {
+        if(i < clipPlaneCount) {
+            bool c = dot(clipPlanes[i].xyz, p) + clipPlanes[i].w < 0.0;
+            clipped = clipIntersection == 1 ? clipped && c : clipped || c;
+        }
+    }
+    return clipped;
+}
@@ -29,6 +29,10 @@ void main() {
             outColor = vec4(0.0, 0.0, 0.0, 0.0);
             break;
         }
+        if(is_clipped_by_context(p)) {
+            p += step;
+            continue;
+        }
         vec3 uvw = (p / size) + 0.5;
         float value = texture(tex, uvw).r;
         if(value >= threshold) {
@@ -0,0 +1,118 @@
+
+uniform vec3 camera_position;
+uniform float metallic;
+uniform float roughness;
+uniform sampler3D tex;
+uniform sampler2D transferFunction;
+uniform vec3 size;
+uniform vec3 h;
+uniform int mode;
+uniform int stepCount;
+uniform float earlyTermination;
+uniform int jitter;
+uniform int useLighting;
+uniform vec3 clipMin;
+uniform vec3 clipMax;
+uniform int volumeClipPlaneCount;
+uniform vec4 volumeClipPlanes[8];
+
+in vec3 pos;
+
+layout (location = 0) out vec4 outColor;
+
+vec3 gradient(vec3 uvw) {
+    float x = texture(tex, uvw + vec3(h.x, 0.0, 0.0)).r - texture(tex, uvw - vec3(h.x, 0.0, 0.0)).r;
+    float y = texture(tex, uvw + vec3(0.0, h.y, 0.0)).r - texture(tex, uvw - vec3(0.0, h.y, 0.0)).r;
+    float z = texture(tex, uvw + vec3(0.0, 0.0, h.z)).r - texture(tex, uvw - vec3(0.0, 0.0, h.z)).r;
+    return vec3(x, y, z) / (2.0 * h);
+}
+
+bool is_clipped(vec3 p) {
+    for(int i = 0; i < 8; i++) {
+        if(i >= volumeClipPlaneCount) {
+            break;
+        }
+        if(dot(volumeClipPlanes[i].xyz, p) + volumeClipPlanes[i].w < 0.0) {
+            return true;
+        }
+    }
+    return is_clipped_by_context(p);
+}
+
+void main() {
+    // Find the part of the ray from the camera that is inside the volume and the clip box
+    vec3 direction = normalize(pos - camera_position);
+    vec3 boxMin = max(-0.5 * size, clipMin);
+    vec3 boxMax = min(0.5 * size, clipMax);
+    vec3 t0 = (boxMin - camera_position) / direction;
+    vec3 t1 = (boxMax - camera_position) / direction;
+    vec3 tMin = min(t0, t1);
+    vec3 tMax = max(t0, t1);
+    float tNear = max(max(max(tMin.x, tMin.y), tMin.z), 0.0);
+    float tFar = min(min(tMax.x, tMax.y), tMax.z);
+    if(tNear >= tFar) {
+        discard;
+    }
+
+    float stepSize = length(size) / float(stepCount);
+    float offset = jitter == 1 ? fract(sin(dot(gl_FragCoord.xy, vec2(12.9898, 78.233))) * 43758.5453) : 0.5;
+    float t = tNear + offset * stepSize;
+
+    vec4 accumulated = vec4(0.0);
+    float extreme = mode == 2 ? 1.0 : 0.0;
+    float sum = 0.0;
+    int count = 0;
+    for(int i = 0; i < 1024; i++) {
+        if(i >= stepCount || t > tFar) {
+            break;
+        }
+        vec3 p = camera_position + t * direction;
+        t += stepSize;
+        if(is_clipped(p)) {
+            continue;
+        }
+        vec3 uvw = (p / size) + 0.5;
+        float value = texture(tex, uvw).r;
+        count++;
+        if(mode == 0) {
+            vec4 sampleColor = texture(transferFunction, vec2(value, 0.5));
+            if(sampleColor.a > 0.0) {
+                vec3 color = sampleColor.rgb;
+                if(useLighting == 1) {
+                    vec3 g = gradient(uvw);
+                    if(length(g) > 0.0001) {
+                        color = calculate_lighting(color, p, -normalize(g), metallic, roughness, 1.0);
+                    }
+                }
+                accumulated.rgb += (1.0 - accumulated.a) * sampleColor.a * color;
+                accumulated.a += (1.0 - accumulated.a) * sampleColor.a;
+                if(accumulated.a >= earlyTermination) {
+                    break;
+                }
+            }
+        } else if(mode == 1) {
+            extreme = max(extreme, value);
+        } else if(mode == 2) {
+            extreme = min(extreme, value);
+        } else {
+            sum += value;
+        }
+    }
+    if(count == 0) {
+        discard;
+    }
+
+    if(mode == 0) {
+        // The accumulated color is premultiplied with the opacity
+        outColor = vec4(accumulated.rgb / max(accumulated.a, 0.0001), accumulated.a);
+    } else {
+        outColor = texture(transferFunction, vec2(mode == 3 ? sum / float(count) : extreme, 0.5));
+    }
+    if(outColor.a <= 0.0) {
+        discard;
+    }
+    if(useLighting == 1) {
+        outColor.rgb = reinhard_tone_mapping(outColor.rgb);
+    }
+    outColor.rgb = srgb_from_rgb(outColor.rgb);
+}
@@ -0,0 +1,239 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// Maps the values in the volume data to colors and opacities, see [VolumeMaterial].
+/// The transfer function is defined by a set of control points, each consisting of a value in the range `[0..1]` and a color,
+/// and the colors are linearly interpolated between the control points.
+/// The alpha channel of the color is the opacity.
+///
+#[derive(Clone, Debug)]
+pub struct TransferFunction {
+    /// The control points as pairs of a value in the range `[0..1]` and a color. Assumed to be sorted by value.
+    /// The colors are assumed to be in linear color space.
+    pub control_points: Vec<(f32, Color)>,
+}
+
+impl TransferFunction {
+    ///
+    /// Creates a transfer function from the given control points, see [TransferFunction::control_points].
+    ///
+    pub fn new(control_points: Vec<(f32, Color)>) -> Self {
+        Self { control_points }
+    }
+
+    ///
+    /// Creates a transfer function which maps a value of zero to transparent black and a value of one to opaque white.
+    ///
+    pub fn grayscale() -> Self {
+        Self::new(vec![(0.0, Color::new(0, 0, 0, 0)), (1.0, Color::WHITE)])
+    }
+
+    ///
+    /// Returns the color and opacity for the given value.
+    ///
+    pub fn color(&self, value: f32) -> Color {
+        let points = &self.control_points;
+        if points.is_empty() {
+            return Color::new(0, 0, 0, 0);
+        }
+        let i = points.partition_point(|(v, _)| *v < value);
+        if i == 0 {
+            return points[0].1;
+        }
+        if i == points.len() {
+            return points[i - 1].1;
+        }
+        let (v0, c0) = points[i - 1];
+        let (v1, c1) = points[i];
+        let t = if v1 > v0 {
+            (value - v0) / (v1 - v0)
+        } else {
+            1.0
+        };
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Color::new(
+            mix(c0.r, c1.r),
+            mix(c0.g, c1.g),
+            mix(c0.b, c1.b),
+            mix(c0.a, c1.a),
+        )
+    }
+
+    ///
+    /// Returns a texture with a width of 256 pixels and a height of one pixel containing the transfer function, which is the format used by [VolumeMaterial].
+    ///
+    pub fn to_cpu_texture(&self) -> CpuTexture {
+        CpuTexture {
+            data: TextureData::RgbaU8(
+                (0..256)
+                    .map(|i| {
+                        let c = self.color(i as f32 / 255.0);
+                        [c.r, c.g, c.b, c.a]
+                    })
+                    .collect(),
+            ),
+            width: 256,
+            height: 1,
+            mip_map_filter: None,
+            wrap_s: Wrapping::ClampToEdge,
+            wrap_t: Wrapping::ClampToEdge,
+            ..Default::default()
+        }
+    }
+}
+
+///
+/// The way the samples along each ray through the volume are combined in a [VolumeMaterial].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VolumeRenderMode {
+    /// The colors and opacities given by the transfer function are composited front-to-back along the ray.
+    Composite,
+    /// Maximum intensity projection, ie. the largest value along the ray is mapped through the transfer function.
+    MaximumIntensity,
+    /// Minimum intensity projection, ie. the smallest value along the ray is mapped through the transfer function.
+    MinimumIntensity,
+    /// The average value along the ray is mapped through the transfer function.
+    Average,
+}
+
+///
+/// A material that renders the volume defined by the voxel data in the [VolumeMaterial::voxels] by marching along a ray through the volume for each pixel.
+/// The red channel of the voxel data is mapped to a color and an opacity by the [VolumeMaterial::transfer_function], and the samples along the ray are combined as specified by the [VolumeMaterial::mode].
+/// This material should be applied to a cube with center in origo, for example [CpuMesh::cube], scaled to the [VolumeMaterial::size].
+///
+#[derive(Clone)]
+pub struct VolumeMaterial {
+    /// The voxel data.
+    pub voxels: std::rc::Rc<Texture3D>,
+    /// The transfer function texture, which maps a value in the voxel data to a color and an opacity, see [TransferFunction::to_cpu_texture].
+    pub transfer_function: std::rc::Rc<Texture2D>,
+    /// The way the samples along each ray are combined.
+    pub mode: VolumeRenderMode,
+    /// The size of the cube that is used to render the voxel data. The texture is scaled to fill the entire cube.
+    pub size: Vec3,
+    /// The number of samples along the diagonal of the cube. More samples gives a more accurate result but is slower to render.
+    pub step_count: u32,
+    /// The accumulated opacity at which the compositing along a ray is stopped, since the samples further away are not visible. Only used in [VolumeRenderMode::Composite].
+    pub early_termination: f32,
+    /// Whether the start of each ray is randomly offset by up to one step, which replaces banding artifacts with less visible noise.
+    pub jitter: bool,
+    /// The part of the volume to render, the rest is clipped away. The box is in the same coordinate system as the cube, ie. within `-0.5 * size` and `0.5 * size`.
+    pub clip_box: Option<AxisAlignedBoundingBox>,
+    /// Up to eight planes that clip away part of the volume. Each plane is given as the normal in the xyz components and the distance in the w component,
+    /// and the points `p` where `dot(normal, p) + distance < 0` are clipped away.
+    /// The clip planes set on the context (see [Context::set_clip_planes]) are applied in addition to these.
+    pub clip_planes: Vec<Vec4>,
+    /// Whether the samples are lit by the lights using the gradient of the voxel data as the normal. Only used in [VolumeRenderMode::Composite] and requires at least one light.
+    pub lighting: bool,
+    /// A value in the range `[0..1]` specifying how metallic the volume is when it is lit.
+    pub metallic: f32,
+    /// A value in the range `[0..1]` specifying how rough the volume is when it is lit.
+    pub roughness: f32,
+    /// The lighting model used when the volume is lit.
+    pub lighting_model: LightingModel,
+}
+
+impl VolumeMaterial {
+    ///
+    /// Creates a new volume material from the given [CpuVolume], for example loaded using [Loaded::vol](crate::io::Loaded::vol), and the given transfer function.
+    ///
+    pub fn new(
+        context: &Context,
+        cpu_volume: &CpuVolume,
+        transfer_function: &TransferFunction,
+    ) -> ThreeDResult<Self> {
+        Ok(Self {
+            voxels: std::rc::Rc::new(Texture3D::new(context, &cpu_volume.voxels)?),
+            transfer_function: std::rc::Rc::new(Texture2D::new(
+                context,
+                &transfer_function.to_cpu_texture(),
+            )?),
+            mode: VolumeRenderMode::Composite,
+            size: cpu_volume.size,
+            step_count: 256,
+            early_termination: 0.99,
+            jitter: true,
+            clip_box: None,
+            clip_planes: Vec::new(),
+            lighting: false,
+            metallic: 0.0,
+            roughness: 1.0,
+            lighting_model: LightingModel::Blinn,
+        })
+    }
+}
+
+impl Material for VolumeMaterial {
+    fn fragment_shader_source(&self, _use_vertex_colors: bool, lights: &[&dyn Light]) -> String {
+        let mut output = lights_fragment_shader_source(lights, self.lighting_model);
+        output.push_str(include_str!("shaders/clip_planes.frag"));
+        output.push_str(include_str!("shaders/volume_material.frag"));
+        output
+    }
+    fn use_uniforms(
+        &self,
+        program: &Program,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        for (i, light) in lights.iter().enumerate() {
+            light.use_uniforms(program, i as u32)?;
+        }
+        program.use_uniform_if_required("eyePosition", camera.position())?;
+        program.use_uniform_if_required("metallic", self.metallic)?;
+        program.use_uniform_if_required("roughness", self.roughness)?;
+        program.use_uniform("camera_position", camera.position())?;
+        program.use_uniform("size", self.size)?;
+        program.use_uniform(
+            "mode",
+            match self.mode {
+                VolumeRenderMode::Composite => 0,
+                VolumeRenderMode::MaximumIntensity => 1,
+                VolumeRenderMode::MinimumIntensity => 2,
+                VolumeRenderMode::Average => 3,
+            },
+        )?;
+        program.use_uniform("stepCount", self.step_count.clamp(1, 1024) as i32)?;
+        program.use_uniform_if_required("earlyTermination", self.early_termination)?;
+        program.use_uniform("jitter", self.jitter as i32)?;
+        program.use_uniform_if_required(
+            "useLighting",
+            (self.lighting && self.mode == VolumeRenderMode::Composite) as i32,
+        )?;
+        let (clip_min, clip_max) = self
+            .clip_box
+            .map(|b| (b.min(), b.max()))
+            .unwrap_or((-0.5 * self.size, 0.5 * self.size));
+        program.use_uniform("clipMin", clip_min)?;
+        program.use_uniform("clipMax", clip_max)?;
+        let clip_planes = &self.clip_planes[..self.clip_planes.len().min(8)];
+        program.use_uniform("volumeClipPlaneCount", clip_planes.len() as i32)?;
+        if !clip_planes.is_empty() {
+            program.use_uniform_array("volumeClipPlanes", clip_planes)?;
+        }
+        program.use_uniform_if_required(
+            "h",
+            vec3(
+                1.0 / self.voxels.width() as f32,
+                1.0 / self.voxels.height() as f32,
+                1.0 / self.voxels.depth() as f32,
+            ),
+        )?;
+        program.use_texture_3d("tex", &self.voxels)?;
+        program.use_texture("transferFunction", &self.transfer_function)
+    }
+    fn render_states(&self) -> RenderStates {
+        // The back faces are rendered, so the volume is also visible when the camera is inside the cube
+        RenderStates {
+            write_mask: WriteMask::COLOR,
+            blend: Blend::TRANSPARENCY,
+            cull: Cull::Front,
+            ..Default::default()
+        }
+    }
+    fn is_transparent(&self) -> bool {
+        true
+    }
+}
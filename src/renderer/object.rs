@@ -27,6 +27,18 @@ mod circle;
 #[doc(inline)]
 pub use circle::*;
 
+mod path;
+#[doc(inline)]
+pub use path::*;
+
+mod shape;
+#[doc(inline)]
+pub use shape::*;
+
+mod sprite_batch;
+#[doc(inline)]
+pub use sprite_batch::*;
+
 mod skybox;
 #[doc(inline)]
 pub use skybox::*;
@@ -43,6 +55,24 @@ mod bounding_box;
 #[doc(inline)]
 pub use bounding_box::*;
 
+mod grid;
+#[doc(inline)]
+pub use grid::*;
+
+mod units;
+#[doc(inline)]
+pub use units::*;
+
+#[cfg(feature = "text")]
+mod measurement;
+#[cfg(feature = "text")]
+#[doc(inline)]
+pub use measurement::*;
+
+mod transform_gizmo;
+#[doc(inline)]
+pub use transform_gizmo::*;
+
 use crate::core::*;
 use crate::renderer::*;
 
@@ -125,6 +155,17 @@ pub trait Object2D: Geometry2D {
     ///
     fn render(&self, viewport: Viewport) -> ThreeDResult<()>;
 
+    ///
+    /// Render the object as seen by the given 2D camera, ie. the coordinates of the object are in world units instead of pixels.
+    /// Must be called in the callback given as input to a [RenderTarget], [ColorTarget] or [DepthTarget] write method.
+    ///
+    /// The default implementation ignores the pan, zoom and rotation of the camera and renders the object in pixels in the viewport of the camera
+    /// using [Object2D::render].
+    ///
+    fn render_with_camera(&self, camera: &Camera2D) -> ThreeDResult<()> {
+        self.render(camera.viewport())
+    }
+
     ///
     /// Returns whether or not this object should be considered transparent.
     ///
@@ -136,6 +177,10 @@ impl<T: Object2D + ?Sized> Object2D for &T {
         (*self).render(viewport)
     }
 
+    fn render_with_camera(&self, camera: &Camera2D) -> ThreeDResult<()> {
+        (*self).render_with_camera(camera)
+    }
+
     fn is_transparent(&self) -> bool {
         (*self).is_transparent()
     }
@@ -146,6 +191,10 @@ impl<T: Object2D + ?Sized> Object2D for &mut T {
         (**self).render(viewport)
     }
 
+    fn render_with_camera(&self, camera: &Camera2D) -> ThreeDResult<()> {
+        (**self).render_with_camera(camera)
+    }
+
     fn is_transparent(&self) -> bool {
         (**self).is_transparent()
     }
@@ -156,6 +205,10 @@ impl<T: Object2D> Object2D for Box<T> {
         self.as_ref().render(viewport)
     }
 
+    fn render_with_camera(&self, camera: &Camera2D) -> ThreeDResult<()> {
+        self.as_ref().render_with_camera(camera)
+    }
+
     fn is_transparent(&self) -> bool {
         self.as_ref().is_transparent()
     }
@@ -166,6 +219,10 @@ impl<T: Object2D> Object2D for std::rc::Rc<T> {
         self.as_ref().render(viewport)
     }
 
+    fn render_with_camera(&self, camera: &Camera2D) -> ThreeDResult<()> {
+        self.as_ref().render_with_camera(camera)
+    }
+
     fn is_transparent(&self) -> bool {
         self.as_ref().is_transparent()
     }
@@ -176,7 +233,23 @@ impl<T: Object2D> Object2D for std::rc::Rc<std::cell::RefCell<T>> {
         self.borrow().render(viewport)
     }
 
+    fn render_with_camera(&self, camera: &Camera2D) -> ThreeDResult<()> {
+        self.borrow().render_with_camera(camera)
+    }
+
     fn is_transparent(&self) -> bool {
         self.borrow().is_transparent()
     }
 }
+
+// Returns the size of a pixel in world units at the given point, used for rendering helpers with a constant size on the screen.
+pub(crate) fn pixel_size(camera: &Camera, point: Vec3) -> f32 {
+    let height = match camera.projection_type() {
+        ProjectionType::Perspective { field_of_view_y } => {
+            let depth = (point - camera.position()).dot(camera.view_direction().normalize());
+            2.0 * depth.max(camera.z_near()) * (0.5 * field_of_view_y.0).tan()
+        }
+        ProjectionType::Orthographic { height } => *height,
+    };
+    height / camera.viewport().height.max(1) as f32
+}
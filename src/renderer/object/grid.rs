@@ -0,0 +1,154 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// An infinite grid, typically used as a ground plane for orientation in editors and visualizations.
+/// The grid is rendered by reconstructing the intersection with the plane for each pixel on the screen, so it has no geometry and is anti-aliased at any distance.
+/// The spacing of the grid lines adapts to the distance to the camera, such that every [Grid::subdivisions] lines is a major line,
+/// and it fades out in the distance.
+///
+pub struct Grid {
+    /// The color of the grid lines. The alpha value determines the opacity of the major lines.
+    pub color: Color,
+    /// The size of the smallest cells in world units.
+    pub cell_size: f32,
+    /// The number of cells between each major line, which is also the factor between the cell sizes when zooming out.
+    pub subdivisions: u32,
+    /// The minimum size of the cells in pixels. Cells that would be smaller than this are replaced by larger cells.
+    pub min_cell_size: f32,
+    /// The width of the lines in pixels.
+    pub line_width: f32,
+    /// The distance from the camera at which the grid has faded out completely. A value of zero disables the fading.
+    pub fade_distance: f32,
+    /// Whether to highlight the x-axis (red) and z-axis (blue) of the grid.
+    pub show_axes: bool,
+    context: Context,
+    transformation: Mat4,
+    plane: std::cell::RefCell<Mesh>,
+}
+
+impl Grid {
+    ///
+    /// Creates a new grid in the xz-plane with the given size of the smallest cells.
+    ///
+    pub fn new(context: &Context, cell_size: f32) -> ThreeDResult<Self> {
+        let mut plane = CpuMesh::square();
+        plane.transform(&Mat4::from_angle_x(degrees(-90.0)))?;
+        Ok(Self {
+            color: Color::new(128, 128, 128, 200),
+            cell_size,
+            subdivisions: 10,
+            min_cell_size: 10.0,
+            line_width: 1.0,
+            fade_distance: 0.0,
+            show_axes: true,
+            context: context.clone(),
+            transformation: Mat4::identity(),
+            plane: std::cell::RefCell::new(Mesh::new(context, &plane)?),
+        })
+    }
+
+    ///
+    /// Returns the local to world transformation applied to the grid.
+    ///
+    pub fn transformation(&self) -> Mat4 {
+        self.transformation
+    }
+
+    ///
+    /// Set the local to world transformation applied to the grid. The grid is placed in the local xz-plane.
+    ///
+    pub fn set_transformation(&mut self, transformation: Mat4) {
+        self.transformation = transformation;
+    }
+
+    ///
+    /// Returns the size of the smallest visible cells at the center of the screen, for example for labelling the units of the grid using [Units::format_length].
+    ///
+    pub fn visible_cell_size(&self, camera: &Camera) -> f32 {
+        let normal = self.transformation.y.truncate().normalize();
+        let origin = self.transformation.w.truncate();
+        let direction = camera.view_direction().normalize();
+        let t = (origin - camera.position()).dot(normal) / direction.dot(normal);
+        let point = if t > 0.0 && t.is_finite() {
+            camera.position() + direction * t
+        } else {
+            *camera.target()
+        };
+        // The cells are foreshortened when looking at the grid from a low angle
+        let pixel_size = pixel_size(camera, point) / direction.dot(normal).abs().max(0.1);
+        let subdivisions = self.subdivisions.max(2) as f32;
+        let lod = (self.min_cell_size * pixel_size / self.cell_size)
+            .log(subdivisions)
+            .max(0.0);
+        self.cell_size * subdivisions.powf(lod.floor())
+    }
+}
+
+impl Geometry for Grid {
+    fn aabb(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::INFINITE
+    }
+
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        // The infinite plane cannot be rendered with a material, so render a large square of the plane below the camera instead
+        let inverse = self.transformation.invert().unwrap_or_else(Mat4::identity);
+        let center = (inverse * camera.position().extend(1.0)).truncate();
+        let mut plane = self.plane.borrow_mut();
+        plane.set_transformation(
+            self.transformation
+                * Mat4::from_translation(vec3(center.x, 0.0, center.z))
+                * Mat4::from_scale(camera.z_far()),
+        );
+        plane.render_with_material(material, camera, lights)
+    }
+}
+
+impl Object for Grid {
+    fn render(&self, camera: &Camera, _lights: &[&dyn Light]) -> ThreeDResult<()> {
+        let fragment_shader_source = format!(
+            "{}{}",
+            include_str!("../../core/shared.frag"),
+            include_str!("shaders/grid.frag")
+        );
+        self.context.effect(&fragment_shader_source, |effect| {
+            let view_projection = camera.projection() * camera.view();
+            effect.use_uniform("viewProjection", view_projection)?;
+            effect.use_uniform(
+                "viewProjectionInverse",
+                view_projection.invert().unwrap_or_else(Mat4::identity),
+            )?;
+            effect.use_uniform("transformation", self.transformation)?;
+            effect.use_uniform(
+                "transformationInverse",
+                self.transformation.invert().unwrap_or_else(Mat4::identity),
+            )?;
+            effect.use_uniform("eyePosition", camera.position())?;
+            effect.use_uniform("color", self.color)?;
+            effect.use_uniform("cellSize", self.cell_size)?;
+            effect.use_uniform("subdivisions", self.subdivisions.max(2) as f32)?;
+            effect.use_uniform("minCellSize", self.min_cell_size)?;
+            effect.use_uniform("lineWidth", self.line_width)?;
+            effect.use_uniform("fadeDistance", self.fade_distance)?;
+            effect.use_uniform("showAxes", if self.show_axes { 1 } else { 0 })?;
+            effect.apply(
+                RenderStates {
+                    write_mask: WriteMask::COLOR,
+                    blend: Blend::TRANSPARENCY,
+                    cull: Cull::None,
+                    ..Default::default()
+                },
+                camera.viewport(),
+            )
+        })
+    }
+
+    fn is_transparent(&self) -> bool {
+        true
+    }
+}
@@ -87,6 +87,14 @@ impl<M: Material> Geometry2D for Line<M> {
             self.model.render_with_material(material, camera2d, &[])
         })
     }
+
+    fn render_with_material_and_camera(
+        &self,
+        material: &dyn Material,
+        camera: &Camera2D,
+    ) -> ThreeDResult<()> {
+        self.model.render_with_material(material, camera, &[])
+    }
 }
 
 impl<M: Material> Object2D for Line<M> {
@@ -95,6 +103,10 @@ impl<M: Material> Object2D for Line<M> {
             .camera2d(viewport, |camera2d| self.model.render(camera2d, &[]))
     }
 
+    fn render_with_camera(&self, camera: &Camera2D) -> ThreeDResult<()> {
+        self.model.render(camera, &[])
+    }
+
     fn is_transparent(&self) -> bool {
         self.model.is_transparent()
     }
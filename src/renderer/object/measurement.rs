@@ -0,0 +1,273 @@
+use crate::core::*;
+use crate::renderer::*;
+use crate::window::{Event, FrameInput, MouseButton};
+use std::cell::RefCell;
+
+///
+/// A ruler which measures the distances between a sequence of points and the angles between the line segments,
+/// for example points picked on the surface of objects.
+/// The line segments are labelled with their length and the inner points with the angle in the given [Units].
+/// It is rendered on top of everything else and the labels have a constant size on the screen.
+///
+pub struct Measurement {
+    /// The units used in the labels.
+    pub units: Units,
+    /// The maximum number of points. When adding a point to a full measurement, a new measurement is started.
+    /// For example, use two points for measuring a distance, three points for measuring an angle and `None` for measuring the length of a path.
+    pub max_points: Option<usize>,
+    /// Whether to label the angles at the inner points.
+    pub show_angles: bool,
+    /// The height of the labels in pixels.
+    pub label_size: f32,
+    context: Context,
+    font: Font,
+    points: Vec<Vec3>,
+    press_position: Option<(f64, f64)>,
+    lines: Lines3D,
+    line_material: ColorMaterial,
+    marker: RefCell<Model<ColorMaterial>>,
+    labels: RefCell<Vec<(Vec3, Text3D)>>,
+    // The units and whether the angles were shown when the labels were created, or None if the labels need to be created
+    labelled: RefCell<Option<(Units, bool)>>,
+    label_material: TextMaterial,
+}
+
+impl Measurement {
+    ///
+    /// Creates a new empty measurement of a distance between two points with labels in the given font and color.
+    ///
+    pub fn new(context: &Context, font: &Font, color: Color) -> ThreeDResult<Self> {
+        let render_states = RenderStates {
+            depth_test: DepthTest::Always,
+            write_mask: WriteMask::COLOR,
+            blend: Blend::TRANSPARENCY,
+            cull: Cull::None,
+            ..Default::default()
+        };
+        let line_material = ColorMaterial {
+            color,
+            texture: None,
+            is_transparent: true,
+            render_states,
+        };
+        let mut sphere = CpuMesh::sphere(8);
+        sphere.transform(&Mat4::from_scale(0.5))?;
+        let mut label_material = TextMaterial::new(font, color);
+        label_material.outline_color = Color::BLACK;
+        label_material.outline_width = 0.15;
+        label_material.render_states.depth_test = DepthTest::Always;
+        Ok(Self {
+            units: Units::default(),
+            max_points: Some(2),
+            show_angles: true,
+            label_size: 16.0,
+            context: context.clone(),
+            font: font.clone(),
+            points: Vec::new(),
+            press_position: None,
+            lines: Lines3D::new(context, &[], LineWidth::Pixels(2.0))?,
+            marker: RefCell::new(Model::new_with_material(
+                context,
+                &sphere,
+                line_material.clone(),
+            )?),
+            line_material,
+            labels: RefCell::new(Vec::new()),
+            labelled: RefCell::new(None),
+            label_material,
+        })
+    }
+
+    ///
+    /// Returns the measured points.
+    ///
+    pub fn points(&self) -> &[Vec3] {
+        &self.points
+    }
+
+    ///
+    /// Replaces the measured points.
+    ///
+    pub fn set_points(&mut self, points: &[Vec3]) -> ThreeDResult<()> {
+        self.points = points.to_vec();
+        self.update()
+    }
+
+    ///
+    /// Adds a point to the measurement. If the measurement already has the maximum number of points, a new measurement is started from the given point.
+    ///
+    pub fn add_point(&mut self, point: Vec3) -> ThreeDResult<()> {
+        if self
+            .max_points
+            .map(|m| self.points.len() >= m)
+            .unwrap_or(false)
+        {
+            self.points.clear();
+        }
+        self.points.push(point);
+        self.update()
+    }
+
+    ///
+    /// Removes all points.
+    ///
+    pub fn clear(&mut self) -> ThreeDResult<()> {
+        self.points.clear();
+        self.update()
+    }
+
+    ///
+    /// Returns the total length of the line segments between the points.
+    ///
+    pub fn length(&self) -> f32 {
+        self.points.windows(2).map(|p| p[0].distance(p[1])).sum()
+    }
+
+    ///
+    /// Returns the angles between the line segments at each of the inner points.
+    ///
+    pub fn angles(&self) -> Vec<Radians> {
+        self.points
+            .windows(3)
+            .map(|p| (p[0] - p[1]).angle(p[2] - p[1]))
+            .collect()
+    }
+
+    ///
+    /// Handles the events. Must be called each frame.
+    /// Clicking with the left mouse button adds the point on the given geometries under the cursor, see [pick].
+    /// Dragging is ignored so the measurement can be combined with a camera control.
+    /// Returns whether a point was added.
+    ///
+    pub fn handle_events(
+        &mut self,
+        camera: &Camera,
+        frame_input: &mut FrameInput,
+        geometries: &[&dyn Geometry],
+    ) -> ThreeDResult<bool> {
+        let mut change = false;
+        for event in frame_input.events.iter_mut() {
+            match event {
+                Event::MousePress {
+                    button: MouseButton::Left,
+                    position,
+                    handled,
+                    ..
+                } if !*handled => {
+                    self.press_position = Some(*position);
+                }
+                Event::MouseRelease {
+                    button: MouseButton::Left,
+                    position,
+                    handled,
+                    ..
+                } if !*handled => {
+                    if let Some(press_position) = self.press_position.take() {
+                        let moved = (position.0 - press_position.0).abs()
+                            + (position.1 - press_position.1).abs();
+                        if moved < 3.0 {
+                            let pixel = (
+                                (frame_input.device_pixel_ratio * position.0) as f32,
+                                (frame_input.viewport.height as f64
+                                    - frame_input.device_pixel_ratio * position.1)
+                                    as f32,
+                            );
+                            if let Some(point) = pick(&self.context, camera, pixel, geometries)? {
+                                self.add_point(point)?;
+                                *handled = true;
+                                change = true;
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(change)
+    }
+
+    fn update(&mut self) -> ThreeDResult<()> {
+        *self.labelled.borrow_mut() = None;
+        self.lines
+            .set_polylines(&[Polyline::new(self.points.clone())])
+    }
+
+    fn update_labels(&self) -> ThreeDResult<()> {
+        let labelled = Some((self.units.clone(), self.show_angles));
+        if *self.labelled.borrow() == labelled {
+            return Ok(());
+        }
+        let layout = TextLayout {
+            alignment: TextAlignment::Center,
+            vertical_alignment: TextVerticalAlignment::Bottom,
+            ..Default::default()
+        };
+        let mut labels = Vec::new();
+        for p in self.points.windows(2) {
+            labels.push((
+                0.5 * (p[0] + p[1]),
+                self.units.format_length(p[0].distance(p[1])),
+            ));
+        }
+        if self.show_angles {
+            for (i, angle) in self.angles().into_iter().enumerate() {
+                labels.push((self.points[i + 1], self.units.format_angle(angle)));
+            }
+        }
+        *self.labels.borrow_mut() = labels
+            .into_iter()
+            .map(|(position, text)| {
+                Text3D::new(&self.context, &self.font, &text, &layout, true).map(|t| (position, t))
+            })
+            .collect::<ThreeDResult<Vec<_>>>()?;
+        *self.labelled.borrow_mut() = labelled;
+        Ok(())
+    }
+}
+
+impl Geometry for Measurement {
+    fn aabb(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::new_with_positions(&self.points)
+    }
+
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        self.lines.render_with_material(material, camera, lights)
+    }
+}
+
+impl Object for Measurement {
+    fn render(&self, camera: &Camera, _lights: &[&dyn Light]) -> ThreeDResult<()> {
+        self.lines
+            .render_with_material(&self.line_material, camera, &[])?;
+        let mut marker = self.marker.borrow_mut();
+        for point in self.points.iter() {
+            marker.set_transformation(
+                Mat4::from_translation(*point) * Mat4::from_scale(6.0 * pixel_size(camera, *point)),
+            );
+            marker.render(camera, &[])?;
+        }
+        self.update_labels()?;
+        let up = camera
+            .right_direction()
+            .cross(camera.view_direction())
+            .normalize();
+        for (position, label) in self.labels.borrow_mut().iter_mut() {
+            let scale = self.label_size * pixel_size(camera, *position);
+            // Place the label slightly above the point or line
+            label.set_transformation(
+                Mat4::from_translation(*position + up * 0.5 * scale) * Mat4::from_scale(scale),
+            );
+            label.render_with_material(&self.label_material, camera, &[])?;
+        }
+        Ok(())
+    }
+
+    fn is_transparent(&self) -> bool {
+        true
+    }
+}
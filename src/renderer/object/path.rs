@@ -0,0 +1,774 @@
+use crate::core::*;
+use crate::renderer::dashes::*;
+
+// The maximum distance in pixels between a curve and the line segments approximating it
+const TOLERANCE: f32 = 0.1;
+
+// The width in pixels of the fading edge used for anti-aliasing
+const FEATHER: f32 = 1.0;
+
+///
+/// The shape used at the corners of a stroke, see [StrokeStyle].
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineJoin {
+    /// The outer edges of the two line segments are extended until they meet.
+    /// If the length of the resulting miter divided by half the stroke width exceeds the limit, a [LineJoin::Bevel] is used instead.
+    Miter {
+        /// The miter limit.
+        limit: f32,
+    },
+    /// The corner is rounded.
+    Round,
+    /// The corner is cut off.
+    Bevel,
+}
+
+impl Default for LineJoin {
+    fn default() -> Self {
+        Self::Miter { limit: 4.0 }
+    }
+}
+
+///
+/// The shape used at the start and end of an open stroke, see [StrokeStyle].
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineCap {
+    /// The stroke ends exactly at the end point.
+    #[default]
+    Butt,
+    /// The stroke ends with a half circle around the end point.
+    Round,
+    /// The stroke is extended with half the stroke width beyond the end point.
+    Square,
+}
+
+///
+/// Specifies how a [Path2D] is stroked, see [Path2D::stroke].
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrokeStyle {
+    /// The width of the stroke.
+    pub width: f32,
+    /// The shape used at the corners.
+    pub join: LineJoin,
+    /// The shape used at the start and end of open subpaths and of each dash.
+    pub cap: LineCap,
+    /// The dash pattern as alternating lengths of dashes and gaps. An empty pattern gives a solid stroke.
+    pub dashes: Vec<f32>,
+    /// The distance into the dash pattern at which the stroke starts.
+    pub dash_offset: f32,
+    /// Whether to fade out the edges of the stroke over one pixel which reduces aliasing.
+    /// This requires a material which uses the vertex colors and blends transparent colors, for example a transparent [ColorMaterial](crate::renderer::ColorMaterial).
+    pub anti_alias: bool,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            join: LineJoin::default(),
+            cap: LineCap::default(),
+            dashes: Vec::new(),
+            dash_offset: 0.0,
+            anti_alias: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct SubPath {
+    points: Vec<Vec2>,
+    closed: bool,
+}
+
+///
+/// A 2D path consisting of a number of subpaths, each built from line segments, Bézier curves and arcs.
+/// Use [Path2D::fill] and [Path2D::stroke] to create a mesh that can be rendered using a [Shape2D](crate::renderer::Shape2D).
+/// The curves are approximated by line segments which are precise to a tenth of a unit, so the path should be specified in pixels.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Path2D {
+    subpaths: Vec<SubPath>,
+}
+
+impl Path2D {
+    ///
+    /// Creates a new empty path.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Creates a path with an open subpath through the given points.
+    ///
+    pub fn polyline(points: &[Vec2]) -> Self {
+        let mut path = Self::new();
+        for (i, point) in points.iter().enumerate() {
+            if i == 0 {
+                path.move_to(*point);
+            } else {
+                path.line_to(*point);
+            }
+        }
+        path
+    }
+
+    ///
+    /// Creates a path with a closed subpath through the given points.
+    ///
+    pub fn polygon(points: &[Vec2]) -> Self {
+        let mut path = Self::polyline(points);
+        path.close();
+        path
+    }
+
+    ///
+    /// Creates a path with a closed subpath around a rectangle with the given center and size and corners rounded with the given radius.
+    ///
+    pub fn rounded_rectangle(center: Vec2, width: f32, height: f32, radius: f32) -> Self {
+        let half_size = vec2(0.5 * width, 0.5 * height);
+        let radius = radius.max(0.0).min(half_size.x).min(half_size.y);
+        let inner = half_size - vec2(radius, radius);
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let mut path = Self::new();
+        path.move_to(center + vec2(inner.x, -half_size.y));
+        for (i, corner) in [
+            vec2(inner.x, -inner.y),
+            vec2(inner.x, inner.y),
+            vec2(-inner.x, inner.y),
+            vec2(-inner.x, -inner.y),
+        ]
+        .iter()
+        .enumerate()
+        {
+            let start = (i as f32 - 1.0) * quarter;
+            path.arc(
+                center + corner,
+                radius,
+                radians(start),
+                radians(start + quarter),
+            );
+        }
+        path.close();
+        path
+    }
+
+    ///
+    /// Creates a path with a closed subpath around a circle with the given center and radius.
+    ///
+    pub fn circle(center: Vec2, radius: f32) -> Self {
+        let mut path = Self::new();
+        path.move_to(center + vec2(radius, 0.0));
+        path.arc(
+            center,
+            radius,
+            radians(0.0),
+            radians(2.0 * std::f32::consts::PI),
+        );
+        path.close();
+        path
+    }
+
+    ///
+    /// Starts a new subpath at the given point.
+    ///
+    pub fn move_to(&mut self, point: Vec2) -> &mut Self {
+        self.subpaths.push(SubPath {
+            points: vec![point],
+            closed: false,
+        });
+        self
+    }
+
+    ///
+    /// Adds a line segment from the current point to the given point.
+    /// If there is no current point, this starts a new subpath at the given point.
+    ///
+    pub fn line_to(&mut self, point: Vec2) -> &mut Self {
+        self.current_subpath(point).points.push(point);
+        self
+    }
+
+    ///
+    /// Adds a quadratic Bézier curve from the current point to the given point using the given control point.
+    ///
+    pub fn quadratic_to(&mut self, control: Vec2, point: Vec2) -> &mut Self {
+        let subpath = self.current_subpath(control);
+        let start = *subpath.points.last().unwrap();
+        let curvature = (start - 2.0 * control + point).magnitude();
+        let count = segment_count((curvature / (8.0 * TOLERANCE)).sqrt());
+        for i in 1..=count {
+            let t = i as f32 / count as f32;
+            let s = 1.0 - t;
+            subpath
+                .points
+                .push(s * s * start + 2.0 * s * t * control + t * t * point);
+        }
+        self
+    }
+
+    ///
+    /// Adds a cubic Bézier curve from the current point to the given point using the two given control points.
+    ///
+    pub fn cubic_to(&mut self, control0: Vec2, control1: Vec2, point: Vec2) -> &mut Self {
+        let subpath = self.current_subpath(control0);
+        let start = *subpath.points.last().unwrap();
+        let curvature = (start - 2.0 * control0 + control1)
+            .magnitude()
+            .max((control0 - 2.0 * control1 + point).magnitude());
+        let count = segment_count((0.75 * curvature / TOLERANCE).sqrt());
+        for i in 1..=count {
+            let t = i as f32 / count as f32;
+            let s = 1.0 - t;
+            subpath.points.push(
+                s * s * s * start
+                    + 3.0 * s * s * t * control0
+                    + 3.0 * s * t * t * control1
+                    + t * t * t * point,
+            );
+        }
+        self
+    }
+
+    ///
+    /// Adds a circular arc with the given center and radius from the start angle to the end angle.
+    /// The angles are measured from the x-axis towards the y-axis and the arc goes in the negative direction if the end angle is smaller than the start angle.
+    /// A line segment is added from the current point to the start of the arc.
+    ///
+    pub fn arc(
+        &mut self,
+        center: Vec2,
+        radius: f32,
+        start_angle: impl Into<Radians>,
+        end_angle: impl Into<Radians>,
+    ) -> &mut Self {
+        let start_angle = start_angle.into().0;
+        let end_angle = end_angle.into().0;
+        let count = arc_segment_count((end_angle - start_angle).abs(), radius);
+        let point = |angle: f32| center + radius * vec2(angle.cos(), angle.sin());
+        self.line_to(point(start_angle));
+        let subpath = self.subpaths.last_mut().unwrap();
+        for i in 1..=count {
+            let t = i as f32 / count as f32;
+            subpath
+                .points
+                .push(point(start_angle + t * (end_angle - start_angle)));
+        }
+        self
+    }
+
+    ///
+    /// Closes the current subpath by adding a line segment back to the start of the subpath.
+    ///
+    pub fn close(&mut self) -> &mut Self {
+        if let Some(subpath) = self.subpaths.last_mut() {
+            subpath.closed = true;
+        }
+        self
+    }
+
+    ///
+    /// Returns a mesh which covers the area enclosed by the subpaths, where open subpaths are treated as closed.
+    /// A subpath inside another subpath is a hole, unless it is inside a hole in which case it is filled again.
+    /// Subpaths that intersect themselves or each other are not supported.
+    ///
+    /// If anti-aliasing is enabled, the edges fade out over one pixel by adding vertex colors with a decreasing alpha value.
+    /// This requires a material which uses the vertex colors and blends transparent colors, for example a transparent [ColorMaterial](crate::renderer::ColorMaterial).
+    ///
+    pub fn fill(&self, anti_alias: bool) -> CpuMesh {
+        let mut contours: Vec<Vec<Vec2>> = self
+            .subpaths
+            .iter()
+            .map(|subpath| remove_duplicates(&subpath.points, true))
+            .filter(|points| points.len() > 2)
+            .collect();
+
+        // Find the contours that are holes from the number of contours they are inside
+        let depths: Vec<usize> = (0..contours.len())
+            .map(|i| {
+                (0..contours.len())
+                    .filter(|j| *j != i && is_inside(contours[i][0], &contours[*j]))
+                    .count()
+            })
+            .collect();
+        for (contour, depth) in contours.iter_mut().zip(depths.iter()) {
+            if (signed_area(contour) > 0.0) == (depth % 2 == 1) {
+                contour.reverse();
+            }
+        }
+
+        let mut mesh = MeshBuilder::default();
+        for (i, outer) in contours.iter().enumerate() {
+            if depths[i] % 2 == 1 {
+                continue;
+            }
+            let holes: Vec<&Vec<Vec2>> = (0..contours.len())
+                .filter(|j| depths[*j] == depths[i] + 1 && is_inside(contours[*j][0], outer))
+                .map(|j| &contours[j])
+                .collect();
+            let polygon = bridge_holes(outer, &holes);
+            let start = mesh.positions.len() as u32;
+            for point in polygon.iter() {
+                mesh.push_vertex(*point, 1.0);
+            }
+            for index in ear_clipping(&polygon) {
+                mesh.indices.push(start + index);
+            }
+        }
+        if anti_alias {
+            // The filled area is to the left of every contour, so the fading edge is added to the right
+            for contour in contours.iter() {
+                mesh.push_stroke(
+                    contour,
+                    true,
+                    &[(0.0, 1.0), (-FEATHER, 0.0)],
+                    LineJoin::default(),
+                    LineCap::Butt,
+                    0.0,
+                );
+            }
+        }
+        mesh.build(anti_alias)
+    }
+
+    ///
+    /// Returns a mesh which covers the stroke along the subpaths using the given style.
+    ///
+    pub fn stroke(&self, style: &StrokeStyle) -> CpuMesh {
+        let half_width = 0.5 * style.width.max(0.0);
+        let (rails, feather) = if style.anti_alias {
+            // Two rails on each side at the edge of the stroke, one inside with full alpha and one outside with zero alpha.
+            // Strokes thinner than a pixel are faded out instead of becoming thinner.
+            let inner = (half_width - 0.5 * FEATHER).max(0.0);
+            let outer = half_width + 0.5 * FEATHER;
+            let alpha = (2.0 * half_width / FEATHER).min(1.0);
+            (
+                vec![(outer, 0.0), (inner, alpha), (-inner, alpha), (-outer, 0.0)],
+                FEATHER,
+            )
+        } else {
+            (vec![(half_width, 1.0), (-half_width, 1.0)], 0.0)
+        };
+
+        let mut mesh = MeshBuilder::default();
+        for subpath in self.subpaths.iter() {
+            let points = remove_duplicates(&subpath.points, subpath.closed);
+            if points.len() < 2 {
+                continue;
+            }
+            let pattern_length: f32 = style.dashes.iter().map(|d| d.max(0.0)).sum();
+            if pattern_length > 0.0 {
+                for dash in dashes(&points, subpath.closed, &style.dashes, style.dash_offset) {
+                    mesh.push_stroke(&dash, false, &rails, style.join, style.cap, feather);
+                }
+            } else {
+                mesh.push_stroke(
+                    &points,
+                    subpath.closed,
+                    &rails,
+                    style.join,
+                    style.cap,
+                    feather,
+                );
+            }
+        }
+        mesh.build(style.anti_alias)
+    }
+
+    fn current_subpath(&mut self, start: Vec2) -> &mut SubPath {
+        if self.subpaths.last().map(|s| s.closed).unwrap_or(true) {
+            // Continue from the end of a closed subpath or start a new subpath if there is no current point
+            let start = self.subpaths.last().map(|s| s.points[0]).unwrap_or(start);
+            self.move_to(start);
+        }
+        self.subpaths.last_mut().unwrap()
+    }
+}
+
+#[derive(Default)]
+struct MeshBuilder {
+    positions: Vec<Vec3>,
+    colors: Vec<Color>,
+    indices: Vec<u32>,
+}
+
+// A cross section of a stroke. A rail with a positive offset is placed at center + left * offset and with a negative offset at center - right * offset.
+struct Section {
+    center: Vec2,
+    left: Vec2,
+    right: Vec2,
+    alpha: f32,
+}
+
+impl MeshBuilder {
+    fn push_vertex(&mut self, position: Vec2, alpha: f32) {
+        self.positions.push(vec3(position.x, position.y, 0.0));
+        self.colors
+            .push(Color::new(255, 255, 255, (alpha * 255.0).round() as u8));
+    }
+
+    fn push_stroke(
+        &mut self,
+        points: &[Vec2],
+        closed: bool,
+        rails: &[(f32, f32)],
+        join: LineJoin,
+        cap: LineCap,
+        feather: f32,
+    ) {
+        let max_offset = rails.iter().fold(0.0f32, |m, r| m.max(r.0.abs()));
+        let sections = sections(points, closed, join, cap, max_offset, feather);
+        let start = self.positions.len() as u32;
+        for section in sections.iter() {
+            for (offset, alpha) in rails {
+                let position = if *offset >= 0.0 {
+                    section.center + section.left * *offset
+                } else {
+                    section.center - section.right * *offset
+                };
+                self.push_vertex(position, alpha * section.alpha);
+            }
+        }
+        let rail_count = rails.len() as u32;
+        let section_count = sections.len() as u32;
+        let segment_count = if closed {
+            section_count
+        } else {
+            section_count - 1
+        };
+        for s in 0..segment_count {
+            let s0 = start + s * rail_count;
+            let s1 = start + ((s + 1) % section_count) * rail_count;
+            for r in 0..rail_count - 1 {
+                self.indices.extend_from_slice(&[
+                    s0 + r,
+                    s1 + r,
+                    s1 + r + 1,
+                    s0 + r,
+                    s1 + r + 1,
+                    s0 + r + 1,
+                ]);
+            }
+        }
+    }
+
+    fn build(self, use_colors: bool) -> CpuMesh {
+        CpuMesh {
+            positions: Positions::F32(self.positions),
+            indices: Some(Indices::U32(self.indices)),
+            colors: if use_colors { Some(self.colors) } else { None },
+            ..Default::default()
+        }
+    }
+}
+
+fn sections(
+    points: &[Vec2],
+    closed: bool,
+    join: LineJoin,
+    cap: LineCap,
+    half_width: f32,
+    feather: f32,
+) -> Vec<Section> {
+    let count = points.len();
+    let direction = |i: usize| (points[(i + 1) % count] - points[i]).normalize();
+    let mut sections = Vec::new();
+    for i in 0..count {
+        if !closed && i == 0 {
+            let d = direction(0);
+            start_cap(&mut sections, points[0], d, cap, half_width, feather);
+        } else if !closed && i == count - 1 {
+            let d = direction(count - 2);
+            let mut cap_sections = Vec::new();
+            start_cap(&mut cap_sections, points[i], -d, cap, half_width, feather);
+            // An end cap is a reversed start cap in the opposite direction
+            for section in cap_sections.into_iter().rev() {
+                sections.push(Section {
+                    center: section.center,
+                    left: section.right,
+                    right: section.left,
+                    alpha: section.alpha,
+                });
+            }
+        } else {
+            let d0 = direction((i + count - 1) % count);
+            let d1 = direction(i);
+            corner(&mut sections, points[i], d0, d1, join, half_width);
+        }
+    }
+    sections
+}
+
+fn start_cap(
+    sections: &mut Vec<Section>,
+    point: Vec2,
+    d: Vec2,
+    cap: LineCap,
+    half_width: f32,
+    feather: f32,
+) {
+    let n = perpendicular(d);
+    match cap {
+        LineCap::Butt | LineCap::Square => {
+            let extension = if cap == LineCap::Square {
+                half_width - 0.5 * feather
+            } else {
+                0.0
+            };
+            if feather > 0.0 {
+                sections.push(Section {
+                    center: point - d * (extension + feather),
+                    left: n,
+                    right: -n,
+                    alpha: 0.0,
+                });
+            }
+            if extension > 0.0 {
+                sections.push(Section {
+                    center: point - d * extension,
+                    left: n,
+                    right: -n,
+                    alpha: 1.0,
+                });
+            }
+        }
+        LineCap::Round => {
+            let quarter = std::f32::consts::FRAC_PI_2;
+            let count = arc_segment_count(quarter, half_width);
+            for j in 0..count {
+                let angle = quarter * (1.0 - j as f32 / count as f32);
+                sections.push(Section {
+                    center: point,
+                    left: n * angle.cos() - d * angle.sin(),
+                    right: -n * angle.cos() - d * angle.sin(),
+                    alpha: 1.0,
+                });
+            }
+        }
+    }
+    sections.push(Section {
+        center: point,
+        left: n,
+        right: -n,
+        alpha: 1.0,
+    });
+}
+
+fn corner(
+    sections: &mut Vec<Section>,
+    point: Vec2,
+    d0: Vec2,
+    d1: Vec2,
+    join: LineJoin,
+    half_width: f32,
+) {
+    let n0 = perpendicular(d0);
+    let n1 = perpendicular(d1);
+    let cross = d0.x * d1.y - d0.y * d1.x;
+    if cross.abs() < 0.0001 && d0.dot(d1) > 0.0 {
+        sections.push(Section {
+            center: point,
+            left: n0,
+            right: -n0,
+            alpha: 1.0,
+        });
+        return;
+    }
+    // The miter is the direction to the intersection of the offset edges, scaled such that it reaches the intersection at an offset of one
+    let miter = if (n0 + n1).magnitude2() > 0.000001 {
+        let m = (n0 + n1).normalize();
+        m / m.dot(n0)
+    } else {
+        n0
+    };
+    if let LineJoin::Miter { limit } = join {
+        if miter.magnitude() <= limit {
+            sections.push(Section {
+                center: point,
+                left: miter,
+                right: -miter,
+                alpha: 1.0,
+            });
+            return;
+        }
+    }
+    // Turning right gives the outer side of the corner to the left
+    let outer_sign = if cross < 0.0 { 1.0 } else { -1.0 };
+    let angle = n0.angle(n1).0.abs();
+    let count = if join == LineJoin::Round {
+        arc_segment_count(angle, half_width)
+    } else {
+        1
+    };
+    // The inner side is limited to avoid spikes at sharp corners
+    let inner = if miter.magnitude() > 8.0 {
+        miter.normalize() * 8.0
+    } else {
+        miter
+    };
+    for j in 0..=count {
+        let t = j as f32 / count as f32;
+        let normal = if join == LineJoin::Round {
+            Mat2::from_angle(n0.angle(n1) * t) * n0
+        } else if j == 0 {
+            n0
+        } else {
+            n1
+        };
+        let (left, right) = if outer_sign > 0.0 {
+            (normal, -inner)
+        } else {
+            (inner, -normal)
+        };
+        sections.push(Section {
+            center: point,
+            left,
+            right,
+            alpha: 1.0,
+        });
+    }
+}
+
+fn bridge_holes(outer: &[Vec2], holes: &[&Vec<Vec2>]) -> Vec<Vec2> {
+    let mut polygon = outer.to_vec();
+    let mut holes = holes.to_vec();
+    // Connect the holes with the largest x-coordinate first, since they are closest to the outer contour
+    let rightmost = |hole: &[Vec2]| {
+        (0..hole.len())
+            .max_by(|a, b| hole[*a].x.total_cmp(&hole[*b].x))
+            .unwrap()
+    };
+    holes.sort_by(|a, b| b[rightmost(b)].x.total_cmp(&a[rightmost(a)].x));
+    for (h, hole) in holes.iter().enumerate() {
+        let m = rightmost(hole);
+        let point = hole[m];
+        let mut candidates: Vec<usize> = (0..polygon.len()).collect();
+        candidates.sort_by(|a, b| {
+            polygon[*a]
+                .distance2(point)
+                .total_cmp(&polygon[*b].distance2(point))
+        });
+        let is_visible = |v: usize| {
+            let edges_of = |contour: &[Vec2]| {
+                (0..contour.len())
+                    .map(|i| (contour[i], contour[(i + 1) % contour.len()]))
+                    .collect::<Vec<_>>()
+            };
+            let mut edges = edges_of(&polygon);
+            for other in holes[h..].iter() {
+                edges.extend(edges_of(other));
+            }
+            edges
+                .iter()
+                .all(|(a, b)| !segments_intersect(point, polygon[v], *a, *b))
+        };
+        let v = candidates
+            .iter()
+            .copied()
+            .find(|v| is_visible(*v))
+            .unwrap_or(candidates[0]);
+        let mut bridged = polygon[..=v].to_vec();
+        bridged.extend(hole[m..].iter());
+        bridged.extend(hole[..=m].iter());
+        bridged.extend(polygon[v..].iter());
+        polygon = bridged;
+    }
+    polygon
+}
+
+fn ear_clipping(polygon: &[Vec2]) -> Vec<u32> {
+    let mut remaining: Vec<usize> = (0..polygon.len()).collect();
+    let mut indices = Vec::new();
+    let mut i = 0;
+    let mut attempts = 0;
+    while remaining.len() > 3 {
+        let count = remaining.len();
+        let (prev, current, next) = (
+            remaining[(i + count - 1) % count],
+            remaining[i % count],
+            remaining[(i + 1) % count],
+        );
+        let (a, b, c) = (polygon[prev], polygon[current], polygon[next]);
+        let is_convex = cross(b - a, c - b) > 0.0;
+        let is_ear = is_convex
+            && remaining.iter().all(|j| {
+                let p = polygon[*j];
+                p == a || p == b || p == c || !is_inside_triangle(p, a, b, c)
+            });
+        // If no ear is found in a full round, the remaining polygon is degenerate and the vertex is clipped anyway
+        if is_ear || attempts > count {
+            if cross(b - a, c - b).abs() > 0.0 {
+                indices.extend_from_slice(&[prev as u32, current as u32, next as u32]);
+            }
+            remaining.remove(i % count);
+            attempts = 0;
+        } else {
+            i += 1;
+            attempts += 1;
+        }
+        i %= remaining.len();
+    }
+    if remaining.len() == 3 {
+        indices.extend(remaining.iter().map(|i| *i as u32));
+    }
+    indices
+}
+
+fn perpendicular(d: Vec2) -> Vec2 {
+    vec2(-d.y, d.x)
+}
+
+fn cross(a: Vec2, b: Vec2) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+fn signed_area(contour: &[Vec2]) -> f32 {
+    (0..contour.len())
+        .map(|i| cross(contour[i], contour[(i + 1) % contour.len()]))
+        .sum::<f32>()
+        * 0.5
+}
+
+fn is_inside(point: Vec2, contour: &[Vec2]) -> bool {
+    let mut inside = false;
+    for i in 0..contour.len() {
+        let a = contour[i];
+        let b = contour[(i + 1) % contour.len()];
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x)
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+fn is_inside_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    cross(b - a, p - a) >= 0.0 && cross(c - b, p - b) >= 0.0 && cross(a - c, p - c) >= 0.0
+}
+
+fn segments_intersect(a0: Vec2, a1: Vec2, b0: Vec2, b1: Vec2) -> bool {
+    if a0 == b0 || a0 == b1 || a1 == b0 || a1 == b1 {
+        return false;
+    }
+    let d0 = cross(a1 - a0, b0 - a0);
+    let d1 = cross(a1 - a0, b1 - a0);
+    let d2 = cross(b1 - b0, a0 - b0);
+    let d3 = cross(b1 - b0, a1 - b0);
+    d0 * d1 < 0.0 && d2 * d3 < 0.0
+}
+
+fn segment_count(count: f32) -> usize {
+    (count.ceil() as usize).clamp(1, 100)
+}
+
+fn arc_segment_count(angle: f32, radius: f32) -> usize {
+    if radius <= TOLERANCE {
+        return 1;
+    }
+    let max_angle = 2.0 * (1.0 - TOLERANCE / radius).acos();
+    segment_count(angle / max_angle)
+}
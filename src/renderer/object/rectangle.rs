@@ -91,6 +91,14 @@ impl<M: Material> Geometry2D for Rectangle<M> {
             self.model.render_with_material(material, camera2d, &[])
         })
     }
+
+    fn render_with_material_and_camera(
+        &self,
+        material: &dyn Material,
+        camera: &Camera2D,
+    ) -> ThreeDResult<()> {
+        self.model.render_with_material(material, camera, &[])
+    }
 }
 
 impl<M: Material> Object2D for Rectangle<M> {
@@ -99,6 +107,10 @@ impl<M: Material> Object2D for Rectangle<M> {
             .camera2d(viewport, |camera2d| self.model.render(camera2d, &[]))
     }
 
+    fn render_with_camera(&self, camera: &Camera2D) -> ThreeDResult<()> {
+        self.model.render(camera, &[])
+    }
+
     fn is_transparent(&self) -> bool {
         self.model.is_transparent()
     }
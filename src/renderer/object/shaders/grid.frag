@@ -0,0 +1,80 @@
+
+uniform mat4 viewProjection;
+uniform mat4 viewProjectionInverse;
+uniform mat4 transformation;
+uniform mat4 transformationInverse;
+uniform vec3 eyePosition;
+uniform vec4 color;
+uniform float cellSize;
+uniform float subdivisions;
+uniform float minCellSize;
+uniform float lineWidth;
+uniform float fadeDistance;
+uniform int showAxes;
+
+in vec2 uv;
+
+layout (location = 0) out vec4 outColor;
+
+// Returns the coverage of the grid lines with the given spacing at the given position in the grid plane
+float gridLines(vec2 coord, vec2 derivative, float spacing)
+{
+    vec2 distance = abs(fract(coord / spacing - 0.5) - 0.5) * spacing / derivative;
+    return clamp(0.5 * lineWidth + 0.5 - min(distance.x, distance.y), 0.0, 1.0);
+}
+
+void main()
+{
+    // Reconstruct the ray through the pixel in the local coordinates of the grid, where the grid is the xz-plane
+    vec2 ndc = 2.0 * uv - 1.0;
+    vec4 near = viewProjectionInverse * vec4(ndc, -1.0, 1.0);
+    vec4 far = viewProjectionInverse * vec4(ndc, 1.0, 1.0);
+    vec3 start = (transformationInverse * vec4(near.xyz / near.w, 1.0)).xyz;
+    vec3 end = (transformationInverse * vec4(far.xyz / far.w, 1.0)).xyz;
+    vec3 direction = end - start;
+    if (abs(direction.y) < 0.000001) {
+        discard;
+    }
+    float t = -start.y / direction.y;
+    if (t < 0.0 || t > 1.0) {
+        discard;
+    }
+    vec3 p = start + t * direction;
+    vec3 worldPosition = (transformation * vec4(p, 1.0)).xyz;
+    vec4 clipPosition = viewProjection * vec4(worldPosition, 1.0);
+    gl_FragDepth = 0.5 * clipPosition.z / clipPosition.w + 0.5;
+
+    // Choose the spacing such that the cells are at least the minimum size on the screen and blend between the levels
+    vec2 coord = p.xz;
+    vec2 derivative = max(fwidth(coord), vec2(0.000001));
+    float pixelSize = max(derivative.x, derivative.y);
+    float lod = max(0.0, log(minCellSize * pixelSize / cellSize) / log(subdivisions));
+    float level = floor(lod);
+    float blend = fract(lod);
+    float spacing = cellSize * pow(subdivisions, level);
+    float minor = gridLines(coord, derivative, spacing) * 0.3 * (1.0 - blend);
+    float middle = gridLines(coord, derivative, spacing * subdivisions) * mix(1.0, 0.3, blend);
+    float major = gridLines(coord, derivative, spacing * subdivisions * subdivisions);
+    float alpha = max(minor, max(middle, major));
+
+    vec3 rgb = color.rgb;
+    if (showAxes == 1) {
+        vec2 axisDistance = abs(coord) / derivative;
+        float xAxis = clamp(0.5 * lineWidth + 1.0 - axisDistance.y, 0.0, 1.0);
+        float zAxis = clamp(0.5 * lineWidth + 1.0 - axisDistance.x, 0.0, 1.0);
+        rgb = mix(rgb, vec3(0.0, 0.0, 1.0), zAxis);
+        rgb = mix(rgb, vec3(1.0, 0.0, 0.0), xAxis);
+        alpha = max(alpha, max(xAxis, zAxis));
+    }
+
+    // Fade out the grid in the distance and when looking at it from a low angle to avoid aliasing
+    if (fadeDistance > 0.0) {
+        alpha *= 1.0 - smoothstep(0.5 * fadeDistance, fadeDistance, distance(worldPosition, eyePosition));
+    }
+    alpha *= smoothstep(0.0, 0.1, abs(normalize(direction).y));
+    alpha *= color.a;
+    if (alpha < 0.001) {
+        discard;
+    }
+    outColor = vec4(srgb_from_rgb(rgb), alpha);
+}
@@ -0,0 +1,33 @@
+
+layout (std140) uniform Camera
+{
+    mat4 viewProjection;
+    mat4 view;
+    mat4 projection;
+    vec3 position;
+    float padding;
+} camera;
+
+// The corner of the sprite in the range [-0.5, 0.5]
+in vec2 position;
+
+in vec3 row1;
+in vec3 row2;
+in vec4 uvRect;
+in vec4 tint;
+
+out vec3 pos;
+out vec2 uvs;
+out vec4 col;
+
+void main()
+{
+    vec3 p = vec3(position, 1.0);
+    pos = vec3(dot(row1, p), dot(row2, p), 0.0);
+
+    // The y-axis points down in 2D, so the top of the sprite is at the maximum v coordinate
+    vec2 t = position + 0.5;
+    uvs = vec2(mix(uvRect.x, uvRect.z, t.x), mix(uvRect.w, uvRect.y, t.y));
+    col = tint / 255.0;
+    gl_Position = camera.viewProjection * vec4(pos, 1.0);
+}
@@ -0,0 +1,84 @@
+use crate::renderer::*;
+
+///
+/// A 2D object with any shape, for example created from a [Path2D] using [Path2D::fill] or [Path2D::stroke], which can be rendered.
+/// The positions of the mesh are in pixels.
+///
+pub struct Shape2D<M: Material> {
+    context: Context,
+    model: Model<M>,
+    transformation: Mat3,
+}
+
+impl<M: Material> Shape2D<M> {
+    ///
+    /// Constructs a new shape object from the given mesh with the given material.
+    ///
+    pub fn new_with_material(
+        context: &Context,
+        cpu_mesh: &CpuMesh,
+        material: M,
+    ) -> ThreeDResult<Self> {
+        Ok(Self {
+            context: context.clone(),
+            model: Model::new_with_material(context, cpu_mesh, material)?,
+            transformation: Mat3::identity(),
+        })
+    }
+
+    /// Set the transformation applied to the shape.
+    pub fn set_transformation(&mut self, transformation: Mat3) {
+        self.transformation = transformation;
+        self.model.set_transformation_2d(transformation);
+    }
+
+    /// Get the transformation applied to the shape.
+    pub fn transformation(&self) -> Mat3 {
+        self.transformation
+    }
+
+    /// Get the material used for rendering the shape.
+    pub fn material(&self) -> &M {
+        &self.model.material
+    }
+
+    /// Get the mutable material used for rendering the shape.
+    pub fn material_mut(&mut self) -> &mut M {
+        &mut self.model.material
+    }
+}
+
+impl<M: Material> Geometry2D for Shape2D<M> {
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        viewport: Viewport,
+    ) -> ThreeDResult<()> {
+        self.context.camera2d(viewport, |camera2d| {
+            self.model.render_with_material(material, camera2d, &[])
+        })
+    }
+
+    fn render_with_material_and_camera(
+        &self,
+        material: &dyn Material,
+        camera: &Camera2D,
+    ) -> ThreeDResult<()> {
+        self.model.render_with_material(material, camera, &[])
+    }
+}
+
+impl<M: Material> Object2D for Shape2D<M> {
+    fn render(&self, viewport: Viewport) -> ThreeDResult<()> {
+        self.context
+            .camera2d(viewport, |camera2d| self.model.render(camera2d, &[]))
+    }
+
+    fn render_with_camera(&self, camera: &Camera2D) -> ThreeDResult<()> {
+        self.model.render(camera, &[])
+    }
+
+    fn is_transparent(&self) -> bool {
+        self.model.is_transparent()
+    }
+}
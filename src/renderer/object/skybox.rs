@@ -45,6 +45,9 @@ impl Skybox {
             | TextureData::RF32(_) => {
                 TextureCubeMap::new_from_equirectangular::<f32>(context, cpu_texture)?
             }
+            TextureData::Compressed { .. } => {
+                TextureCubeMap::new_from_equirectangular::<u8>(context, &cpu_texture.decompress()?)?
+            }
         };
 
         Self::new_with_texture(context, texture)
@@ -0,0 +1,203 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// A single sprite in a [SpriteBatch].
+///
+#[derive(Clone, Copy, Debug)]
+pub struct Sprite {
+    /// The transformation of the sprite in pixels, applied to a unit square centered at the origin.
+    pub transformation: Mat3,
+    /// The color multiplied onto the color of the material, assumed to be in linear color space.
+    pub tint: Color,
+    /// The area of the texture shown on the sprite, for example an area in a [TextureAtlas].
+    pub uv_rect: UvRect,
+    /// Sprites with a higher z-order are drawn on top of sprites with a lower z-order.
+    /// Sprites with the same z-order are drawn in the order they are given.
+    pub z_order: i32,
+}
+
+impl Sprite {
+    ///
+    /// Creates a new sprite with the given center, size and rotation in pixels, which shows the entire texture.
+    ///
+    pub fn new(center: Vec2, size: Vec2, rotation: impl Into<Radians>) -> Self {
+        Self {
+            transformation: Mat3::from_translation(center)
+                * Mat3::from_angle_z(rotation.into())
+                * Mat3::from_nonuniform_scale(size.x, size.y),
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for Sprite {
+    fn default() -> Self {
+        Self {
+            transformation: Mat3::identity(),
+            tint: Color::WHITE,
+            uv_rect: UvRect::default(),
+            z_order: 0,
+        }
+    }
+}
+
+///
+/// A large number of 2D sprites, ie. textured quads, which are rendered in one draw call.
+/// Each sprite has its own transformation, tint, area of the texture and z-order, see [Sprite].
+/// Combine it with a material which uses the uv coordinates and vertex colors, for example a [ColorMaterial] with a texture created using a [TextureAtlas].
+///
+pub struct SpriteBatch<M: Material> {
+    context: Context,
+    position_buffer: VertexBuffer,
+    row1_buffer: InstanceBuffer,
+    row2_buffer: InstanceBuffer,
+    uv_rect_buffer: InstanceBuffer,
+    tint_buffer: InstanceBuffer,
+    /// The material applied to all of the sprites.
+    pub material: M,
+}
+
+impl<M: Material> SpriteBatch<M> {
+    ///
+    /// Constructs a new sprite batch with the given sprites and material.
+    ///
+    pub fn new_with_material(
+        context: &Context,
+        sprites: &[Sprite],
+        material: M,
+    ) -> ThreeDResult<Self> {
+        let mut batch = Self {
+            context: context.clone(),
+            position_buffer: VertexBuffer::new_with_data(
+                context,
+                &[
+                    vec2(-0.5, -0.5),
+                    vec2(0.5, -0.5),
+                    vec2(0.5, 0.5),
+                    vec2(0.5, 0.5),
+                    vec2(-0.5, 0.5),
+                    vec2(-0.5, -0.5),
+                ],
+            )?,
+            row1_buffer: InstanceBuffer::new(context)?,
+            row2_buffer: InstanceBuffer::new(context)?,
+            uv_rect_buffer: InstanceBuffer::new(context)?,
+            tint_buffer: InstanceBuffer::new(context)?,
+            material,
+        };
+        batch.set_sprites(sprites)?;
+        Ok(batch)
+    }
+
+    ///
+    /// Replaces the sprites, which also determines the number of sprites.
+    ///
+    pub fn set_sprites(&mut self, sprites: &[Sprite]) -> ThreeDResult<()> {
+        let mut sorted: Vec<&Sprite> = sprites.iter().collect();
+        sorted.sort_by_key(|s| s.z_order);
+        let row1: Vec<Vec3> = sorted
+            .iter()
+            .map(|s| {
+                let t = s.transformation;
+                vec3(t.x.x, t.y.x, t.z.x)
+            })
+            .collect();
+        let row2: Vec<Vec3> = sorted
+            .iter()
+            .map(|s| {
+                let t = s.transformation;
+                vec3(t.x.y, t.y.y, t.z.y)
+            })
+            .collect();
+        let uv_rects: Vec<Vec4> = sorted
+            .iter()
+            .map(|s| {
+                vec4(
+                    s.uv_rect.min.x,
+                    s.uv_rect.min.y,
+                    s.uv_rect.max.x,
+                    s.uv_rect.max.y,
+                )
+            })
+            .collect();
+        let tints: Vec<Color> = sorted.iter().map(|s| s.tint).collect();
+        self.row1_buffer.fill(&row1)?;
+        self.row2_buffer.fill(&row2)?;
+        self.uv_rect_buffer.fill(&uv_rects)?;
+        self.tint_buffer.fill(&tints)?;
+        Ok(())
+    }
+
+    ///
+    /// Returns the number of sprites.
+    ///
+    pub fn sprite_count(&self) -> u32 {
+        self.tint_buffer.instance_count()
+    }
+
+    fn draw(&self, material: &dyn Material, camera: &Camera) -> ThreeDResult<()> {
+        if self.sprite_count() == 0 {
+            return Ok(());
+        }
+        let fragment_shader_source = material.fragment_shader_source(true, &[]);
+        self.context.program(
+            include_str!("shaders/sprite_batch.vert"),
+            &fragment_shader_source,
+            |program| {
+                material.use_uniforms(program, camera, &[])?;
+                program.use_clip_planes(camera)?;
+                program.use_uniform_block("Camera", camera.uniform_buffer())?;
+                program.use_vertex_attribute("position", &self.position_buffer)?;
+                program.use_instance_attribute("row1", &self.row1_buffer)?;
+                program.use_instance_attribute("row2", &self.row2_buffer)?;
+                // The uv coordinates and colors are not used by all materials
+                if program.requires_attribute("uvRect") {
+                    program.use_instance_attribute("uvRect", &self.uv_rect_buffer)?;
+                }
+                if program.requires_attribute("tint") {
+                    program.use_instance_attribute("tint", &self.tint_buffer)?;
+                }
+                program.draw_arrays_instanced(
+                    material.render_states(),
+                    camera.viewport(),
+                    6,
+                    self.sprite_count(),
+                )
+            },
+        )
+    }
+}
+
+impl<M: Material> Geometry2D for SpriteBatch<M> {
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        viewport: Viewport,
+    ) -> ThreeDResult<()> {
+        self.context
+            .camera2d(viewport, |camera2d| self.draw(material, camera2d))
+    }
+
+    fn render_with_material_and_camera(
+        &self,
+        material: &dyn Material,
+        camera: &Camera2D,
+    ) -> ThreeDResult<()> {
+        self.draw(material, camera)
+    }
+}
+
+impl<M: Material> Object2D for SpriteBatch<M> {
+    fn render(&self, viewport: Viewport) -> ThreeDResult<()> {
+        self.render_with_material(&self.material, viewport)
+    }
+
+    fn render_with_camera(&self, camera: &Camera2D) -> ThreeDResult<()> {
+        self.render_with_material_and_camera(&self.material, camera)
+    }
+
+    fn is_transparent(&self) -> bool {
+        self.material.is_transparent()
+    }
+}
@@ -0,0 +1,576 @@
+use crate::core::*;
+use crate::renderer::*;
+use crate::window::{Event, FrameInput, MouseButton};
+use std::cell::RefCell;
+
+///
+/// The type of transformation applied by a [TransformGizmo].
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GizmoMode {
+    /// Translation along an axis using the arrows, in a plane using the squares or parallel to the screen using the center.
+    #[default]
+    Translate,
+    /// Rotation around an axis using the rings.
+    Rotate,
+    /// Scaling along an axis using the boxes, in a plane using the squares or uniformly using the center.
+    Scale,
+}
+
+///
+/// The coordinate system of the axes of a [TransformGizmo].
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GizmoSpace {
+    /// The axes are the world axes.
+    #[default]
+    World,
+    /// The axes are the axes of the transformation of the gizmo, ie. the local axes of the transformed object.
+    Local,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Handle {
+    Axis(usize),
+    Plane(usize),
+    Center,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Drag {
+    handle: Handle,
+    start_transformation: Mat4,
+    // The gizmo frame (translation, rotation and scale to world) at the start of the drag
+    frame: Mat4,
+    origin: Vec3,
+    rotation: Mat4,
+    scale: f32,
+    start_point: Vec3,
+}
+
+///
+/// Handles for interactively translating, rotating and scaling an object, also known as a manipulator.
+/// Call [TransformGizmo::handle_events] each frame to turn dragging the handles with the left mouse button into a new transformation,
+/// and render the gizmo after the rest of the scene. It is rendered on top of everything else with a constant size on the screen.
+///
+pub struct TransformGizmo {
+    /// The type of transformation applied when dragging the handles.
+    pub mode: GizmoMode,
+    /// The coordinate system of the axes.
+    pub space: GizmoSpace,
+    /// The length of the axes in pixels.
+    pub size: f32,
+    /// If specified, translations are snapped to multiples of this distance along each axis.
+    pub translation_snap: Option<f32>,
+    /// If specified, rotations are snapped to multiples of this angle.
+    pub rotation_snap: Option<Radians>,
+    /// If specified, scale factors are snapped to multiples of this value.
+    pub scale_snap: Option<f32>,
+    transformation: Mat4,
+    hovered: Option<Handle>,
+    drag: Option<Drag>,
+    arrow: RefCell<Model<ColorMaterial>>,
+    scale_handle: RefCell<Model<ColorMaterial>>,
+    plane: RefCell<Model<ColorMaterial>>,
+    sphere: RefCell<Model<ColorMaterial>>,
+    cube: RefCell<Model<ColorMaterial>>,
+    ring: RefCell<Lines3D>,
+}
+
+impl TransformGizmo {
+    ///
+    /// Creates a new translation gizmo in world space with an axis length of 100 pixels and no snapping.
+    ///
+    pub fn new(context: &Context) -> ThreeDResult<Self> {
+        let mut arrow = CpuMesh::arrow(0.8, 0.3, 16);
+        arrow.transform(&Mat4::from_nonuniform_scale(1.0, 0.08, 0.08))?;
+        let mut shaft = CpuMesh::cylinder(16);
+        shaft.transform(&Mat4::from_nonuniform_scale(0.9, 0.02, 0.02))?;
+        let mut end = CpuMesh::cube();
+        end.transform(&(Mat4::from_translation(vec3(0.94, 0.0, 0.0)) * Mat4::from_scale(0.06)))?;
+        let mut plane = CpuMesh::square();
+        plane
+            .transform(&(Mat4::from_translation(vec3(0.35, 0.35, 0.0)) * Mat4::from_scale(0.1)))?;
+        let mut sphere = CpuMesh::sphere(16);
+        sphere.transform(&Mat4::from_scale(0.08))?;
+        let mut cube = CpuMesh::cube();
+        cube.transform(&Mat4::from_scale(0.08))?;
+        let ring = Polyline {
+            positions: (0..64)
+                .map(|i| {
+                    let angle = 2.0 * std::f32::consts::PI * i as f32 / 64.0;
+                    vec3(angle.cos(), angle.sin(), 0.0)
+                })
+                .collect(),
+            closed: true,
+            ..Default::default()
+        };
+        let model = |mesh: &CpuMesh| {
+            Model::new_with_material(context, mesh, ColorMaterial::default()).map(RefCell::new)
+        };
+        Ok(Self {
+            mode: GizmoMode::Translate,
+            space: GizmoSpace::World,
+            size: 100.0,
+            translation_snap: None,
+            rotation_snap: None,
+            scale_snap: None,
+            transformation: Mat4::identity(),
+            hovered: None,
+            drag: None,
+            arrow: model(&arrow)?,
+            scale_handle: RefCell::new(Model::new_with_material(
+                context,
+                &merge(&shaft, &end),
+                ColorMaterial::default(),
+            )?),
+            plane: model(&plane)?,
+            sphere: model(&sphere)?,
+            cube: model(&cube)?,
+            ring: RefCell::new(Lines3D::new(context, &[ring], LineWidth::Pixels(3.0))?),
+        })
+    }
+
+    ///
+    /// Returns the transformation of the object that is manipulated by this gizmo. The gizmo is placed at the translation of the transformation.
+    ///
+    pub fn transformation(&self) -> Mat4 {
+        self.transformation
+    }
+
+    ///
+    /// Set the transformation of the object that is manipulated by this gizmo.
+    ///
+    pub fn set_transformation(&mut self, transformation: Mat4) {
+        self.transformation = transformation;
+    }
+
+    ///
+    /// Returns whether a handle is currently being dragged.
+    ///
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    ///
+    /// Handles the events. Must be called each frame.
+    /// Returns the change in transformation since the last call, if a handle was dragged.
+    /// Multiply the change from the left onto the transformation of the object, ie. `change * transformation`,
+    /// or use [TransformGizmo::transformation] which already has the change applied.
+    /// The events used by the gizmo are marked as handled, so call this before the camera control.
+    ///
+    pub fn handle_events(
+        &mut self,
+        camera: &Camera,
+        frame_input: &mut FrameInput,
+    ) -> ThreeDResult<Option<Mat4>> {
+        let device_pixel_ratio = frame_input.device_pixel_ratio;
+        let viewport = frame_input.viewport;
+        let ray = |position: (f64, f64)| {
+            let pixel = (
+                (device_pixel_ratio * position.0) as f32,
+                (viewport.height as f64 - device_pixel_ratio * position.1) as f32,
+            );
+            (
+                camera.position_at_pixel(pixel),
+                camera.view_direction_at_pixel(pixel),
+            )
+        };
+        let previous_transformation = self.transformation;
+        for event in frame_input.events.iter_mut() {
+            match event {
+                Event::MousePress {
+                    button: MouseButton::Left,
+                    position,
+                    handled,
+                    ..
+                } if !*handled => {
+                    let (origin, direction) = ray(*position);
+                    self.hovered = self.hit(camera, origin, direction);
+                    if let Some(handle) = self.hovered {
+                        let (frame, rotation, scale) = self.frame(camera);
+                        let gizmo_origin = self.transformation.w.truncate();
+                        let mut drag = Drag {
+                            handle,
+                            start_transformation: self.transformation,
+                            frame,
+                            origin: gizmo_origin,
+                            rotation,
+                            scale,
+                            start_point: vec3(0.0, 0.0, 0.0),
+                        };
+                        if let Some(point) = self.drag_point(&drag, camera, origin, direction) {
+                            drag.start_point = point;
+                            self.drag = Some(drag);
+                        }
+                        *handled = true;
+                    }
+                }
+                Event::MouseRelease {
+                    button: MouseButton::Left,
+                    handled,
+                    ..
+                } if self.drag.is_some() => {
+                    self.drag = None;
+                    *handled = true;
+                }
+                Event::MouseMotion {
+                    position, handled, ..
+                } => {
+                    let (origin, direction) = ray(*position);
+                    if let Some(drag) = self.drag {
+                        if let Some(point) = self.drag_point(&drag, camera, origin, direction) {
+                            self.transformation = self.drag_transformation(&drag, camera, point)
+                                * drag.start_transformation;
+                        }
+                        *handled = true;
+                    } else if !*handled {
+                        self.hovered = self.hit(camera, origin, direction);
+                    }
+                }
+                Event::MouseLeave => {
+                    self.hovered = None;
+                }
+                _ => {}
+            }
+        }
+        if self.transformation != previous_transformation {
+            Ok(previous_transformation
+                .invert()
+                .map(|inverse| self.transformation * inverse))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // Returns the transformation from the gizmo space, where the axes have length one, to world space as well as the rotation and scale.
+    fn frame(&self, camera: &Camera) -> (Mat4, Mat4, f32) {
+        let origin = self.transformation.w.truncate();
+        let rotation = match self.space {
+            GizmoSpace::World => Mat4::identity(),
+            GizmoSpace::Local => {
+                let x = self.transformation.x.truncate().normalize();
+                let y = self.transformation.y.truncate();
+                let y = (y - x * x.dot(y)).normalize();
+                let z = x.cross(y);
+                Mat4::from(Mat3::from_cols(x, y, z))
+            }
+        };
+        let scale = self.size * pixel_size(camera, origin);
+        (
+            Mat4::from_translation(origin) * rotation * Mat4::from_scale(scale),
+            rotation,
+            scale,
+        )
+    }
+
+    fn hit(&self, camera: &Camera, origin: Vec3, direction: Vec3) -> Option<Handle> {
+        let (frame, _, _) = self.frame(camera);
+        let inverse = frame.invert()?;
+        let o = (inverse * origin.extend(1.0)).truncate();
+        let d = (inverse * direction.extend(0.0)).truncate().normalize();
+        // The distance in pixels within which a handle is hit, relative to the axis length
+        let tolerance = 8.0 / self.size;
+        let mut closest: Option<(f32, Handle)> = None;
+        let mut consider = |t: f32, handle: Handle| {
+            if t > 0.0 && closest.map(|(c, _)| t < c).unwrap_or(true) {
+                closest = Some((t, handle));
+            }
+        };
+
+        if self.mode != GizmoMode::Rotate {
+            if let Some(t) = ray_sphere(o, d, 0.12) {
+                consider(t, Handle::Center);
+            }
+            for i in 0..3 {
+                let axis = unit(i);
+                if let Some((t, u, distance)) = ray_line(o, d, axis) {
+                    if (0.15..=1.05).contains(&u) && distance < tolerance.max(0.08) {
+                        consider(t, Handle::Axis(i));
+                    }
+                }
+                if let Some(t) = ray_plane(o, d, axis) {
+                    let p = o + d * t;
+                    let (a, b) = (p[(i + 1) % 3], p[(i + 2) % 3]);
+                    if (0.25..=0.45).contains(&a) && (0.25..=0.45).contains(&b) {
+                        consider(t, Handle::Plane(i));
+                    }
+                }
+            }
+        } else {
+            for i in 0..3 {
+                if let Some(t) = ray_plane(o, d, unit(i)) {
+                    if ((o + d * t).magnitude() - 1.0).abs() < tolerance {
+                        consider(t, Handle::Axis(i));
+                    }
+                }
+            }
+        }
+        closest.map(|(_, handle)| handle)
+    }
+
+    // Returns the point under the cursor on the surface that constrains the drag, in gizmo space for axes and planes and in world space for the center.
+    fn drag_point(
+        &self,
+        drag: &Drag,
+        camera: &Camera,
+        origin: Vec3,
+        direction: Vec3,
+    ) -> Option<Vec3> {
+        let inverse = drag.frame.invert()?;
+        let o = (inverse * origin.extend(1.0)).truncate();
+        let d = (inverse * direction.extend(0.0)).truncate().normalize();
+        match (self.mode, drag.handle) {
+            (GizmoMode::Rotate, Handle::Axis(i)) | (_, Handle::Plane(i)) => {
+                ray_plane(o, d, unit(i)).map(|t| o + d * t)
+            }
+            (_, Handle::Axis(i)) => ray_line(o, d, unit(i)).map(|(_, u, _)| unit(i) * u),
+            (_, Handle::Center) => {
+                let normal = camera.view_direction();
+                let denominator = direction.dot(normal);
+                if denominator.abs() < 0.000001 {
+                    return None;
+                }
+                let t = (drag.origin - origin).dot(normal) / denominator;
+                Some(origin + direction * t)
+            }
+        }
+    }
+
+    // Returns the world space transformation from the start of the drag to the given drag point.
+    fn drag_transformation(&self, drag: &Drag, camera: &Camera, point: Vec3) -> Mat4 {
+        let around_origin = |m: Mat4| {
+            Mat4::from_translation(drag.origin)
+                * drag.rotation
+                * m
+                * drag.rotation.transpose()
+                * Mat4::from_translation(-drag.origin)
+        };
+        match self.mode {
+            GizmoMode::Translate => {
+                let offset = match drag.handle {
+                    // Convert the offset in gizmo space into world units along the gizmo axes
+                    Handle::Axis(_) | Handle::Plane(_) => (point - drag.start_point) * drag.scale,
+                    Handle::Center => (drag.rotation.transpose()
+                        * (point - drag.start_point).extend(0.0))
+                    .truncate(),
+                };
+                let offset = offset.map(|v| snap(v, self.translation_snap));
+                Mat4::from_translation((drag.rotation * offset.extend(0.0)).truncate())
+            }
+            GizmoMode::Rotate => {
+                if let Handle::Axis(i) = drag.handle {
+                    let axis = unit(i);
+                    let angle = drag
+                        .start_point
+                        .cross(point)
+                        .dot(axis)
+                        .atan2(drag.start_point.dot(point));
+                    let angle = snap(angle, self.rotation_snap.map(|s| s.0));
+                    around_origin(Mat4::from_axis_angle(axis, radians(angle)))
+                } else {
+                    Mat4::identity()
+                }
+            }
+            GizmoMode::Scale => {
+                let factor = |start: f32, current: f32| {
+                    if start.abs() < 0.000001 {
+                        1.0
+                    } else {
+                        snap(current / start, self.scale_snap).max(0.01)
+                    }
+                };
+                let scale = match drag.handle {
+                    Handle::Axis(i) => {
+                        let mut scale = vec3(1.0, 1.0, 1.0);
+                        scale[i] = factor(drag.start_point[i], point[i]);
+                        scale
+                    }
+                    Handle::Plane(i) => {
+                        let diagonal = unit((i + 1) % 3) + unit((i + 2) % 3);
+                        let f = factor(drag.start_point.dot(diagonal), point.dot(diagonal));
+                        let mut scale = vec3(f, f, f);
+                        scale[i] = 1.0;
+                        scale
+                    }
+                    Handle::Center => {
+                        // Dragging to the right increases the scale, where the axis length doubles the size
+                        let change =
+                            (point - drag.start_point).dot(camera.right_direction().normalize());
+                        let f = factor(1.0, 1.0 + change / drag.scale);
+                        vec3(f, f, f)
+                    }
+                };
+                around_origin(Mat4::from_nonuniform_scale(scale.x, scale.y, scale.z))
+            }
+        }
+    }
+
+    fn material(&self, handle: Handle, color: Color) -> ColorMaterial {
+        let active = self.drag.map(|d| d.handle).or(self.hovered);
+        ColorMaterial {
+            color: if active == Some(handle) {
+                Color::new_opaque(255, 220, 0)
+            } else {
+                color
+            },
+            texture: None,
+            is_transparent: true,
+            render_states: RenderStates {
+                depth_test: DepthTest::Always,
+                write_mask: WriteMask::COLOR,
+                blend: Blend::TRANSPARENCY,
+                cull: Cull::None,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn render_handles(
+        &self,
+        camera: &Camera,
+        material: Option<&dyn Material>,
+        lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        let (frame, _, _) = self.frame(camera);
+        let colors = [Color::RED, Color::GREEN, Color::BLUE];
+        let render = |geometry: &dyn Geometry, handle: Handle, color: Color| match material {
+            Some(material) => geometry.render_with_material(material, camera, lights),
+            None => geometry.render_with_material(&self.material(handle, color), camera, &[]),
+        };
+        if self.mode == GizmoMode::Rotate {
+            let mut ring = self.ring.borrow_mut();
+            for (i, color) in colors.into_iter().enumerate() {
+                ring.set_transformation(frame * axes_frame(i, true));
+                render(&*ring, Handle::Axis(i), color)?;
+            }
+            return Ok(());
+        }
+
+        let mut plane = self.plane.borrow_mut();
+        for (i, color) in colors.into_iter().enumerate() {
+            plane.set_transformation(frame * axes_frame(i, true));
+            render(&*plane, Handle::Plane(i), Color { a: 150, ..color })?;
+        }
+        let mut axis = if self.mode == GizmoMode::Translate {
+            self.arrow.borrow_mut()
+        } else {
+            self.scale_handle.borrow_mut()
+        };
+        for (i, color) in colors.into_iter().enumerate() {
+            axis.set_transformation(frame * axes_frame(i, false));
+            render(&*axis, Handle::Axis(i), color)?;
+        }
+        let mut center = if self.mode == GizmoMode::Translate {
+            self.sphere.borrow_mut()
+        } else {
+            self.cube.borrow_mut()
+        };
+        center.set_transformation(frame);
+        render(&*center, Handle::Center, Color::WHITE)
+    }
+}
+
+impl Geometry for TransformGizmo {
+    fn aabb(&self) -> AxisAlignedBoundingBox {
+        // The size depends on the camera
+        AxisAlignedBoundingBox::INFINITE
+    }
+
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        self.render_handles(camera, Some(material), lights)
+    }
+}
+
+impl Object for TransformGizmo {
+    fn render(&self, camera: &Camera, _lights: &[&dyn Light]) -> ThreeDResult<()> {
+        self.render_handles(camera, None, &[])
+    }
+
+    fn is_transparent(&self) -> bool {
+        // Transparent objects are rendered after the opaque objects, which is needed for the gizmo to be on top
+        true
+    }
+}
+
+// Maps the x-axis to the given axis, or the z-axis to the given axis if the handle is in the plane orthogonal to the axis.
+fn axes_frame(i: usize, orthogonal: bool) -> Mat4 {
+    let (a, b, c) = (unit(i), unit((i + 1) % 3), unit((i + 2) % 3));
+    if orthogonal {
+        Mat4::from(Mat3::from_cols(b, c, a))
+    } else {
+        Mat4::from(Mat3::from_cols(a, b, c))
+    }
+}
+
+fn unit(i: usize) -> Vec3 {
+    let mut v = vec3(0.0, 0.0, 0.0);
+    v[i] = 1.0;
+    v
+}
+
+fn snap(value: f32, increment: Option<f32>) -> f32 {
+    match increment {
+        Some(increment) if increment > 0.0 => (value / increment).round() * increment,
+        _ => value,
+    }
+}
+
+// Returns the ray parameter, the line parameter and the distance between the closest points of the ray and the line through origo in the given direction.
+fn ray_line(o: Vec3, d: Vec3, axis: Vec3) -> Option<(f32, f32, f32)> {
+    let b = d.dot(axis);
+    let denominator = 1.0 - b * b;
+    if denominator < 0.000001 {
+        return None;
+    }
+    let (dd, ea) = (d.dot(o), axis.dot(o));
+    let t = (b * ea - dd) / denominator;
+    let u = (ea - b * dd) / denominator;
+    Some((t, u, (o + d * t - axis * u).magnitude()))
+}
+
+fn ray_plane(o: Vec3, d: Vec3, normal: Vec3) -> Option<f32> {
+    let denominator = d.dot(normal);
+    if denominator.abs() < 0.000001 {
+        return None;
+    }
+    Some(-o.dot(normal) / denominator)
+}
+
+fn ray_sphere(o: Vec3, d: Vec3, radius: f32) -> Option<f32> {
+    let t = -o.dot(d);
+    if (o + d * t).magnitude() < radius {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+fn merge(a: &CpuMesh, b: &CpuMesh) -> CpuMesh {
+    let mut positions = a.positions.to_f32();
+    positions.extend(b.positions.to_f32());
+    let mut indices = a
+        .indices
+        .as_ref()
+        .map(|i| i.to_u32())
+        .unwrap_or_else(|| (0..a.positions.len() as u32).collect());
+    let offset = a.positions.len() as u32;
+    indices.extend(
+        b.indices
+            .as_ref()
+            .map(|i| i.to_u32())
+            .unwrap_or_else(|| (0..b.positions.len() as u32).collect())
+            .into_iter()
+            .map(|i| i + offset),
+    );
+    CpuMesh {
+        positions: Positions::F32(positions),
+        indices: Some(Indices::U32(indices)),
+        ..Default::default()
+    }
+}
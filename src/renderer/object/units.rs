@@ -0,0 +1,68 @@
+use crate::core::*;
+
+///
+/// The units used for labelling lengths and angles, for example when labelling measurements or the cell size of a [Grid](crate::renderer::Grid).
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Units {
+    /// The symbol appended to lengths, for example "m" or "mm".
+    pub symbol: String,
+    /// The number of units per world unit, for example 1000 if one world unit is a meter and the units are millimeters.
+    pub scale: f32,
+    /// The number of decimals shown.
+    pub decimals: usize,
+}
+
+impl Units {
+    ///
+    /// Creates new units with the given symbol, number of units per world unit and number of decimals.
+    ///
+    pub fn new(symbol: &str, scale: f32, decimals: usize) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            scale,
+            decimals,
+        }
+    }
+
+    ///
+    /// Meters, assuming that one world unit is a meter.
+    ///
+    pub fn meters() -> Self {
+        Self::new("m", 1.0, 2)
+    }
+
+    ///
+    /// Centimeters, assuming that one world unit is a meter.
+    ///
+    pub fn centimeters() -> Self {
+        Self::new("cm", 100.0, 1)
+    }
+
+    ///
+    /// Millimeters, assuming that one world unit is a meter.
+    ///
+    pub fn millimeters() -> Self {
+        Self::new("mm", 1000.0, 0)
+    }
+
+    ///
+    /// Formats the given length in world units, for example "1.25 m".
+    ///
+    pub fn format_length(&self, length: f32) -> String {
+        format!("{:.*} {}", self.decimals, length * self.scale, self.symbol)
+    }
+
+    ///
+    /// Formats the given angle in degrees, for example "90.0°".
+    ///
+    pub fn format_angle(&self, angle: Radians) -> String {
+        format!("{:.*}°", self.decimals.min(1), Degrees::from(angle).0)
+    }
+}
+
+impl Default for Units {
+    fn default() -> Self {
+        Self::meters()
+    }
+}
@@ -0,0 +1,19 @@
+//!
+//! Text rendering using signed distance field fonts, both in 2D and 3D.
+//!
+
+mod font;
+#[doc(inline)]
+pub use font::*;
+
+mod text_material;
+#[doc(inline)]
+pub use text_material::*;
+
+mod text2d;
+#[doc(inline)]
+pub use text2d::*;
+
+mod text3d;
+#[doc(inline)]
+pub use text3d::*;
@@ -0,0 +1,438 @@
+use crate::core::*;
+use ab_glyph::Font as _;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+///
+/// Settings used when constructing a [Font].
+///
+#[derive(Clone, Debug)]
+pub struct FontSettings {
+    /// The size in pixels at which the glyphs are rasterized into the glyph atlas, measured as the height from the lowest descender to the highest ascender.
+    /// A larger size gives sharper corners but a larger atlas.
+    pub glyph_size: u32,
+    /// The maximum distance in pixels to the glyph outlines which is stored in the signed distance field.
+    /// This limits how wide an outline can be, see [TextMaterial::outline_width].
+    pub distance_range: u32,
+    /// The characters to include in the glyph atlas. Characters that are not included are rendered as a question mark.
+    pub characters: String,
+}
+
+impl Default for FontSettings {
+    fn default() -> Self {
+        Self {
+            glyph_size: 64,
+            distance_range: 8,
+            characters: (32u8..127).chain(160u8..=255).map(|c| c as char).collect(),
+        }
+    }
+}
+
+///
+/// The horizontal alignment of the lines of a text relative to the origin of the text.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextAlignment {
+    /// The lines start at the origin.
+    #[default]
+    Left,
+    /// The lines are centered around the origin.
+    Center,
+    /// The lines end at the origin.
+    Right,
+}
+
+///
+/// The vertical alignment of a text relative to the origin of the text.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextVerticalAlignment {
+    /// The top of the first line is at the origin.
+    #[default]
+    Top,
+    /// The text is centered around the origin.
+    Middle,
+    /// The baseline of the first line is at the origin.
+    Baseline,
+    /// The bottom of the last line is at the origin.
+    Bottom,
+}
+
+///
+/// Specifies how a text is laid out, see [Font::text_mesh].
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextLayout {
+    /// The horizontal alignment of the lines.
+    pub alignment: TextAlignment,
+    /// The vertical alignment of the text.
+    pub vertical_alignment: TextVerticalAlignment,
+    /// The distance between the baselines of two lines relative to the line spacing defined by the font.
+    pub line_height: f32,
+    /// The maximum width of a line relative to the font size. Lines are broken between words to fit within this width.
+    /// If None is specified, lines are only broken at line breaks in the text.
+    pub max_width: Option<f32>,
+}
+
+impl Default for TextLayout {
+    fn default() -> Self {
+        Self {
+            alignment: TextAlignment::Left,
+            vertical_alignment: TextVerticalAlignment::Top,
+            line_height: 1.0,
+            max_width: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Glyph {
+    min: Vec2,
+    max: Vec2,
+    uv_min: Vec2,
+    uv_max: Vec2,
+}
+
+///
+/// A font which can be used for rendering text using a [Text2D] or [Text3D].
+/// The glyphs are baked into a glyph atlas containing a signed distance field of each glyph,
+/// which makes it possible to render the text sharp at any size.
+///
+#[derive(Clone)]
+pub struct Font {
+    cpu_font: CpuFont,
+    texture: Rc<Texture2D>,
+    glyphs: HashMap<char, Glyph>,
+    glyph_size: u32,
+    distance_range: u32,
+}
+
+impl Font {
+    ///
+    /// Constructs a new font from the given [CpuFont] using the default [FontSettings].
+    ///
+    pub fn new(context: &Context, cpu_font: &CpuFont) -> ThreeDResult<Self> {
+        Self::new_with_settings(context, cpu_font, FontSettings::default())
+    }
+
+    ///
+    /// Constructs a new font from the given [CpuFont] using the given [FontSettings].
+    ///
+    pub fn new_with_settings(
+        context: &Context,
+        cpu_font: &CpuFont,
+        settings: FontSettings,
+    ) -> ThreeDResult<Self> {
+        let font = cpu_font.font();
+        let scale = settings.glyph_size.max(1) as f32;
+        let padding = settings.distance_range.max(1) as usize;
+
+        let mut bitmaps = Vec::new();
+        for c in settings.characters.chars() {
+            let id = font.glyph_id(c);
+            if id.0 == 0 {
+                continue;
+            }
+            if let Some(outlined) =
+                font.outline_glyph(id.with_scale_and_position(scale, ab_glyph::point(0.0, 0.0)))
+            {
+                let bounds = outlined.px_bounds();
+                let width = bounds.width() as usize + 2 * padding;
+                let height = bounds.height() as usize + 2 * padding;
+                let mut coverage = vec![0.0; width * height];
+                outlined.draw(|x, y, c| {
+                    coverage[(y as usize + padding) * width + x as usize + padding] = c;
+                });
+                let glyph = Glyph {
+                    min: vec2(
+                        bounds.min.x - padding as f32,
+                        -bounds.max.y - padding as f32,
+                    ) / scale,
+                    max: vec2(
+                        bounds.max.x + padding as f32,
+                        -bounds.min.y + padding as f32,
+                    ) / scale,
+                    uv_min: vec2(0.0, 0.0),
+                    uv_max: vec2(0.0, 0.0),
+                };
+                let sdf = signed_distance_field(&coverage, width, height, padding as f32);
+                bitmaps.push((c, glyph, width, height, sdf));
+            }
+        }
+
+        // Pack the glyphs into rows sorted by height
+        bitmaps.sort_by_key(|b| std::cmp::Reverse(b.3));
+        let atlas_width = bitmaps.iter().map(|b| b.2 + 1).max().unwrap_or(1).max(1024);
+        let mut offsets = Vec::with_capacity(bitmaps.len());
+        let (mut x, mut y, mut row_height) = (0, 0, 0);
+        for (_, _, width, height, _) in bitmaps.iter() {
+            if x + width > atlas_width {
+                x = 0;
+                y += row_height + 1;
+                row_height = 0;
+            }
+            offsets.push((x, y));
+            x += width + 1;
+            row_height = row_height.max(*height);
+        }
+        let atlas_height = (y + row_height).max(1).next_power_of_two();
+
+        let mut data = vec![0u8; atlas_width * atlas_height];
+        let mut glyphs = HashMap::new();
+        for ((c, mut glyph, width, height, sdf), (x, y)) in bitmaps.into_iter().zip(offsets) {
+            for row in 0..height {
+                let start = (y + row) * atlas_width + x;
+                data[start..start + width].copy_from_slice(&sdf[row * width..(row + 1) * width]);
+            }
+            // The first row of the texture data is at the top of the texture
+            glyph.uv_min = vec2(
+                x as f32 / atlas_width as f32,
+                1.0 - (y + height) as f32 / atlas_height as f32,
+            );
+            glyph.uv_max = vec2(
+                (x + width) as f32 / atlas_width as f32,
+                1.0 - y as f32 / atlas_height as f32,
+            );
+            glyphs.insert(c, glyph);
+        }
+
+        let texture = Texture2D::new(
+            context,
+            &CpuTexture {
+                data: TextureData::RU8(data),
+                width: atlas_width as u32,
+                height: atlas_height as u32,
+                min_filter: Interpolation::Linear,
+                mag_filter: Interpolation::Linear,
+                mip_map_filter: None,
+                wrap_s: Wrapping::ClampToEdge,
+                wrap_t: Wrapping::ClampToEdge,
+            },
+        )?;
+        Ok(Self {
+            cpu_font: cpu_font.clone(),
+            texture: Rc::new(texture),
+            glyphs,
+            glyph_size: settings.glyph_size.max(1),
+            distance_range: settings.distance_range.max(1),
+        })
+    }
+
+    ///
+    /// Returns the glyph atlas containing the signed distance field of each glyph in the red channel.
+    ///
+    pub fn texture(&self) -> &Rc<Texture2D> {
+        &self.texture
+    }
+
+    ///
+    /// Lays out the given text and returns a mesh with positions and uv coordinates into the glyph [texture](Self::texture) for each glyph.
+    /// The mesh is in the xy-plane with the y-axis pointing up and the size of the font equal to one,
+    /// ie. the height from the lowest descender to the highest ascender of a line is one.
+    /// The origin of the mesh is given by the [TextAlignment] and [TextVerticalAlignment] of the layout.
+    ///
+    pub fn text_mesh(&self, text: &str, layout: &TextLayout) -> CpuMesh {
+        let mut positions = Vec::new();
+        let mut uvs = Vec::new();
+        let mut indices = Vec::new();
+        self.layout(text, layout, |glyph, offset| {
+            let index = positions.len() as u32;
+            positions.push(vec3(glyph.min.x + offset.x, glyph.min.y + offset.y, 0.0));
+            positions.push(vec3(glyph.max.x + offset.x, glyph.min.y + offset.y, 0.0));
+            positions.push(vec3(glyph.max.x + offset.x, glyph.max.y + offset.y, 0.0));
+            positions.push(vec3(glyph.min.x + offset.x, glyph.max.y + offset.y, 0.0));
+            uvs.push(vec2(glyph.uv_min.x, glyph.uv_min.y));
+            uvs.push(vec2(glyph.uv_max.x, glyph.uv_min.y));
+            uvs.push(vec2(glyph.uv_max.x, glyph.uv_max.y));
+            uvs.push(vec2(glyph.uv_min.x, glyph.uv_max.y));
+            indices.extend_from_slice(&[index, index + 1, index + 2, index, index + 2, index + 3]);
+        });
+        CpuMesh {
+            name: text.to_string(),
+            positions: Positions::F32(positions),
+            indices: Some(Indices::U32(indices)),
+            uvs: Some(uvs),
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Returns the width and height of the given text when laid out using the given layout, relative to the font size.
+    ///
+    pub fn text_size(&self, text: &str, layout: &TextLayout) -> Vec2 {
+        self.layout(text, layout, |_, _| {})
+    }
+
+    pub(super) fn distance_scale(&self) -> f32 {
+        // Converts a distance relative to the font size into a difference in the values stored in the signed distance field
+        self.glyph_size as f32 / (2.0 * self.distance_range as f32)
+    }
+
+    fn layout(
+        &self,
+        text: &str,
+        layout: &TextLayout,
+        mut callback: impl FnMut(&Glyph, Vec2),
+    ) -> Vec2 {
+        let font = self.cpu_font.font();
+        let unit = 1.0 / font.height_unscaled();
+        let ascent = font.ascent_unscaled() * unit;
+        let descent = font.descent_unscaled() * unit;
+        let line_advance =
+            (ascent - descent + font.line_gap_unscaled() * unit) * layout.line_height;
+        let width = |line: &str| {
+            let mut width = 0.0;
+            let mut previous = None;
+            for c in line.chars() {
+                let id = font.glyph_id(c);
+                if let Some(previous) = previous {
+                    width += font.kern_unscaled(previous, id) * unit;
+                }
+                width += font.h_advance_unscaled(id) * unit;
+                previous = Some(id);
+            }
+            width
+        };
+
+        let mut lines = Vec::new();
+        for paragraph in text.lines() {
+            let mut line = String::new();
+            for word in paragraph.split(' ') {
+                let candidate = if line.is_empty() {
+                    word.to_string()
+                } else {
+                    format!("{} {}", line, word)
+                };
+                match layout.max_width {
+                    Some(max_width) if !line.is_empty() && width(&candidate) > max_width => {
+                        lines.push(std::mem::replace(&mut line, word.to_string()));
+                    }
+                    _ => line = candidate,
+                }
+            }
+            lines.push(line);
+        }
+
+        let line_count = lines.len().max(1);
+        let height = ascent - descent + (line_count - 1) as f32 * line_advance;
+        let top = match layout.vertical_alignment {
+            TextVerticalAlignment::Top => 0.0,
+            TextVerticalAlignment::Middle => 0.5 * height,
+            TextVerticalAlignment::Baseline => ascent,
+            TextVerticalAlignment::Bottom => height,
+        };
+
+        let mut max_line_width: f32 = 0.0;
+        for (i, line) in lines.iter().enumerate() {
+            let line_width = width(line);
+            max_line_width = max_line_width.max(line_width);
+            let mut x = match layout.alignment {
+                TextAlignment::Left => 0.0,
+                TextAlignment::Center => -0.5 * line_width,
+                TextAlignment::Right => -line_width,
+            };
+            let y = top - ascent - i as f32 * line_advance;
+            let mut previous = None;
+            for c in line.chars() {
+                let id = font.glyph_id(c);
+                if let Some(previous) = previous {
+                    x += font.kern_unscaled(previous, id) * unit;
+                }
+                if let Some(glyph) = self.glyphs.get(&c).or_else(|| {
+                    if font.outline_glyph(id.with_scale(1.0)).is_some() {
+                        self.glyphs.get(&'?')
+                    } else {
+                        None
+                    }
+                }) {
+                    callback(glyph, vec2(x, y));
+                }
+                x += font.h_advance_unscaled(id) * unit;
+                previous = Some(id);
+            }
+        }
+        vec2(max_line_width, height)
+    }
+}
+
+///
+/// Computes a signed distance field from the given coverage and encodes it such that 0.5 is at the outline,
+/// larger values are inside the glyph and the given range in pixels maps to the range from zero to one.
+///
+fn signed_distance_field(coverage: &[f32], width: usize, height: usize, range: f32) -> Vec<u8> {
+    const FAR: f32 = 1e20;
+    let mut inside: Vec<f32> = coverage
+        .iter()
+        .map(|c| if *c >= 0.5 { 0.0 } else { FAR })
+        .collect();
+    let mut outside: Vec<f32> = coverage
+        .iter()
+        .map(|c| if *c >= 0.5 { FAR } else { 0.0 })
+        .collect();
+    distance_transform(&mut inside, width, height);
+    distance_transform(&mut outside, width, height);
+    inside
+        .iter()
+        .zip(outside.iter())
+        .map(|(distance_to_inside, distance_to_outside)| {
+            let distance = if *distance_to_inside == 0.0 {
+                0.5 - distance_to_outside.sqrt()
+            } else {
+                distance_to_inside.sqrt() - 0.5
+            };
+            ((0.5 - 0.5 * distance / range).clamp(0.0, 1.0) * 255.0).round() as u8
+        })
+        .collect()
+}
+
+// Computes the squared euclidean distance to the nearest zero in the grid (Felzenszwalb and Huttenlocher).
+fn distance_transform(grid: &mut [f32], width: usize, height: usize) {
+    let size = width.max(height);
+    let mut f = vec![0.0; size];
+    let mut d = vec![0.0; size];
+    let mut v = vec![0; size];
+    let mut z = vec![0.0; size + 1];
+    for x in 0..width {
+        for y in 0..height {
+            f[y] = grid[y * width + x];
+        }
+        distance_transform_1d(&f[..height], &mut d[..height], &mut v, &mut z);
+        for y in 0..height {
+            grid[y * width + x] = d[y];
+        }
+    }
+    for y in 0..height {
+        f[..width].copy_from_slice(&grid[y * width..(y + 1) * width]);
+        distance_transform_1d(&f[..width], &mut d[..width], &mut v, &mut z);
+        grid[y * width..(y + 1) * width].copy_from_slice(&d[..width]);
+    }
+}
+
+fn distance_transform_1d(f: &[f32], d: &mut [f32], v: &mut [usize], z: &mut [f32]) {
+    let parabola_intersection = |q: usize, p: usize| {
+        ((f[q] + (q * q) as f32) - (f[p] + (p * p) as f32)) / (2 * q - 2 * p) as f32
+    };
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = f32::NEG_INFINITY;
+    z[1] = f32::INFINITY;
+    for q in 1..f.len() {
+        let mut s = parabola_intersection(q, v[k]);
+        while s <= z[k] {
+            k -= 1;
+            s = parabola_intersection(q, v[k]);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f32::INFINITY;
+    }
+    k = 0;
+    for (q, distance) in d.iter_mut().enumerate() {
+        while z[k + 1] < q as f32 {
+            k += 1;
+        }
+        let offset = q as f32 - v[k] as f32;
+        *distance = offset * offset + f[v[k]];
+    }
+}
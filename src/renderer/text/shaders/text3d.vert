@@ -0,0 +1,34 @@
+
+layout (std140) uniform Camera
+{
+    mat4 viewProjection;
+    mat4 view;
+    mat4 projection;
+    vec3 position;
+    float padding;
+} camera;
+
+uniform mat4 transformation;
+uniform int billboard;
+
+in vec3 position;
+in vec2 uv_coordinate;
+
+out vec2 uvs;
+
+void main()
+{
+    uvs = uv_coordinate;
+
+    vec3 worldPosition;
+    if (billboard == 1) {
+        // Use the camera right and up directions so that the text always faces the camera
+        vec3 right = vec3(camera.view[0][0], camera.view[1][0], camera.view[2][0]);
+        vec3 up = vec3(camera.view[0][1], camera.view[1][1], camera.view[2][1]);
+        vec2 scale = vec2(length(transformation[0].xyz), length(transformation[1].xyz));
+        worldPosition = transformation[3].xyz + right * position.x * scale.x + up * position.y * scale.y;
+    } else {
+        worldPosition = (transformation * vec4(position, 1.0)).xyz;
+    }
+    gl_Position = camera.viewProjection * vec4(worldPosition, 1.0);
+}
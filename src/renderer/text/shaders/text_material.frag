@@ -0,0 +1,25 @@
+uniform sampler2D sdfTexture;
+uniform vec4 textColor;
+uniform vec4 outlineColor;
+uniform float outlineWidth;
+
+in vec2 uvs;
+
+layout (location = 0) out vec4 outColor;
+
+void main()
+{
+    float distance = texture(sdfTexture, uvs).r;
+    float smoothing = max(0.75 * fwidth(distance), 0.0001);
+    float alpha = smoothstep(0.5 - smoothing, 0.5 + smoothing, distance);
+    vec4 color = textColor;
+    if (outlineWidth > 0.0) {
+        color = mix(outlineColor, textColor, alpha);
+        alpha = smoothstep(0.5 - outlineWidth - smoothing, 0.5 - outlineWidth + smoothing, distance);
+    }
+    alpha *= color.a;
+    if (alpha < 0.005) {
+        discard;
+    }
+    outColor = vec4(srgb_from_rgb(color.rgb), alpha);
+}
@@ -0,0 +1,166 @@
+use crate::renderer::*;
+
+///
+/// A text 2D object which can be rendered, see [Font] for how the text is laid out.
+///
+pub struct Text2D {
+    context: Context,
+    model: Model<TextMaterial>,
+    text_size: Vec2,
+    position: Vec2,
+    rotation: Radians,
+    font_size: f32,
+}
+
+impl Text2D {
+    ///
+    /// Constructs a new text object with the given text laid out using the given font and layout.
+    /// The origin of the text, given by the alignment in the layout, is placed at the given position in pixels
+    /// and the font size is the height in pixels from the lowest descender to the highest ascender of a line.
+    ///
+    pub fn new(
+        context: &Context,
+        font: &Font,
+        text: &str,
+        layout: &TextLayout,
+        position: Vec2,
+        font_size: f32,
+        color: Color,
+    ) -> ThreeDResult<Self> {
+        Self::new_with_material(
+            context,
+            font,
+            text,
+            layout,
+            position,
+            font_size,
+            TextMaterial::new(font, color),
+        )
+    }
+
+    ///
+    /// Constructs a new text object with the given material, see [Text2D::new].
+    ///
+    pub fn new_with_material(
+        context: &Context,
+        font: &Font,
+        text: &str,
+        layout: &TextLayout,
+        position: Vec2,
+        font_size: f32,
+        material: TextMaterial,
+    ) -> ThreeDResult<Self> {
+        let mut text2d = Self {
+            context: context.clone(),
+            model: Model::new_with_material(context, &font.text_mesh(text, layout), material)?,
+            text_size: font.text_size(text, layout),
+            position,
+            rotation: radians(0.0),
+            font_size,
+        };
+        text2d.update();
+        Ok(text2d)
+    }
+
+    /// Set the text which is laid out using the given font and layout.
+    pub fn set_text(&mut self, font: &Font, text: &str, layout: &TextLayout) -> ThreeDResult<()> {
+        let material = self.model.material.clone();
+        self.model =
+            Model::new_with_material(&self.context, &font.text_mesh(text, layout), material)?;
+        self.text_size = font.text_size(text, layout);
+        self.update();
+        Ok(())
+    }
+
+    /// Get the width and height of the text in pixels.
+    pub fn size(&self) -> Vec2 {
+        self.text_size * self.font_size
+    }
+
+    /// Set the position of the origin of the text in pixels.
+    pub fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+        self.update();
+    }
+
+    /// Get the position of the origin of the text in pixels.
+    pub fn position(&self) -> &Vec2 {
+        &self.position
+    }
+
+    /// Set the rotation of the text around its origin.
+    pub fn set_rotation(&mut self, rotation: impl Into<Radians>) {
+        self.rotation = rotation.into();
+        self.update();
+    }
+
+    /// Get the rotation of the text around its origin.
+    pub fn rotation(&self) -> Radians {
+        self.rotation
+    }
+
+    /// Set the font size in pixels.
+    pub fn set_font_size(&mut self, font_size: f32) {
+        self.font_size = font_size;
+        self.update();
+    }
+
+    /// Get the font size in pixels.
+    pub fn font_size(&self) -> f32 {
+        self.font_size
+    }
+
+    /// Get the material used for rendering the text.
+    pub fn material(&self) -> &TextMaterial {
+        &self.model.material
+    }
+
+    /// Get the mutable material used for rendering the text, for example to change the color.
+    pub fn material_mut(&mut self) -> &mut TextMaterial {
+        &mut self.model.material
+    }
+
+    fn update(&mut self) {
+        // The text mesh has the y-axis pointing up while the y-axis of the 2D pixel coordinates points down
+        self.model.set_transformation_2d(
+            Mat3::from_translation(self.position)
+                * Mat3::from_angle_z(self.rotation)
+                * Mat3::from_nonuniform_scale(self.font_size, -self.font_size),
+        );
+    }
+}
+
+impl Geometry2D for Text2D {
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        viewport: Viewport,
+    ) -> ThreeDResult<()> {
+        self.context.camera2d(viewport, |camera2d| {
+            self.model.render_with_material(material, camera2d, &[])
+        })
+    }
+
+    fn render_with_material_and_camera(
+        &self,
+        material: &dyn Material,
+        camera: &Camera2D,
+    ) -> ThreeDResult<()> {
+        self.model.render_with_material(material, camera, &[])
+    }
+}
+
+impl Object2D for Text2D {
+    fn render(&self, viewport: Viewport) -> ThreeDResult<()> {
+        self.context
+            .camera2d(viewport, |camera2d| self.model.render(camera2d, &[]))
+    }
+
+    fn render_with_camera(&self, camera: &Camera2D) -> ThreeDResult<()> {
+        self.model.render(camera, &[])
+    }
+
+    fn is_transparent(&self) -> bool {
+        self.model.is_transparent()
+    }
+}
@@ -0,0 +1,146 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// A text geometry in 3D, for example for labelling points and measurements, see [Font] for how the text is laid out.
+/// Combine it with a [TextMaterial] in a [Gm] to render it.
+/// The text is placed in the xy-plane of its local coordinate system with the font size equal to one, which can be changed with the transformation.
+/// If billboarding is enabled, the text always faces the camera.
+///
+pub struct Text3D {
+    context: Context,
+    position_buffer: VertexBuffer,
+    uv_buffer: VertexBuffer,
+    index_buffer: ElementBuffer,
+    aabb_local: AxisAlignedBoundingBox,
+    transformation: Mat4,
+    billboard: bool,
+}
+
+impl Text3D {
+    ///
+    /// Constructs a new text geometry with the given text laid out using the given font and layout.
+    ///
+    pub fn new(
+        context: &Context,
+        font: &Font,
+        text: &str,
+        layout: &TextLayout,
+        billboard: bool,
+    ) -> ThreeDResult<Self> {
+        let mut text3d = Self {
+            context: context.clone(),
+            position_buffer: VertexBuffer::new(context)?,
+            uv_buffer: VertexBuffer::new(context)?,
+            index_buffer: ElementBuffer::new(context)?,
+            aabb_local: AxisAlignedBoundingBox::EMPTY,
+            transformation: Mat4::identity(),
+            billboard,
+        };
+        text3d.set_text(font, text, layout)?;
+        Ok(text3d)
+    }
+
+    ///
+    /// Set the text which is laid out using the given font and layout.
+    ///
+    pub fn set_text(&mut self, font: &Font, text: &str, layout: &TextLayout) -> ThreeDResult<()> {
+        let mesh = font.text_mesh(text, layout);
+        let positions = mesh.positions.to_f32();
+        self.aabb_local = AxisAlignedBoundingBox::new_with_positions(&positions);
+        self.position_buffer.fill(&positions)?;
+        self.uv_buffer.fill(mesh.uvs.as_ref().unwrap())?;
+        if let Some(Indices::U32(ref indices)) = mesh.indices {
+            self.index_buffer.fill(indices)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Returns the local to world transformation applied to the text.
+    ///
+    pub fn transformation(&self) -> Mat4 {
+        self.transformation
+    }
+
+    ///
+    /// Set the local to world transformation applied to the text.
+    /// If billboarding is enabled, only the translation and scale of the transformation is used.
+    ///
+    pub fn set_transformation(&mut self, transformation: Mat4) {
+        self.transformation = transformation;
+    }
+
+    ///
+    /// Set whether the text always faces the camera.
+    ///
+    pub fn set_billboard(&mut self, billboard: bool) {
+        self.billboard = billboard;
+    }
+
+    ///
+    /// Returns whether the text always faces the camera.
+    ///
+    pub fn is_billboard(&self) -> bool {
+        self.billboard
+    }
+}
+
+impl Geometry for Text3D {
+    fn render_with_material(
+        &self,
+        material: &dyn Material,
+        camera: &Camera,
+        lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        let fragment_shader_source = material.fragment_shader_source(false, lights);
+        self.context.program(
+            include_str!("shaders/text3d.vert"),
+            &fragment_shader_source,
+            |program| {
+                material.use_uniforms(program, camera, lights)?;
+                program.use_clip_planes(camera)?;
+                program.use_uniform_block("Camera", camera.uniform_buffer())?;
+                program.use_uniform("transformation", self.transformation)?;
+                program.use_uniform("billboard", if self.billboard { 1 } else { 0 })?;
+                program.use_vertex_attribute("position", &self.position_buffer)?;
+                program.use_vertex_attribute("uv_coordinate", &self.uv_buffer)?;
+                program.draw_elements(
+                    material.render_states(),
+                    camera.viewport(),
+                    &self.index_buffer,
+                )
+            },
+        )
+    }
+
+    fn aabb(&self) -> AxisAlignedBoundingBox {
+        if self.aabb_local.is_empty() {
+            return AxisAlignedBoundingBox::EMPTY;
+        }
+        if self.billboard {
+            // The text can face any direction, so use a box containing all orientations
+            let scale = self
+                .transformation
+                .x
+                .truncate()
+                .magnitude()
+                .max(self.transformation.y.truncate().magnitude());
+            let radius = self
+                .aabb_local
+                .min()
+                .magnitude()
+                .max(self.aabb_local.max().magnitude())
+                * scale;
+            let center = self.transformation.w.truncate();
+            AxisAlignedBoundingBox::new_with_positions(&[
+                center - vec3(radius, radius, radius),
+                center + vec3(radius, radius, radius),
+            ])
+        } else {
+            let mut aabb = self.aabb_local;
+            aabb.transform(&self.transformation);
+            aabb
+        }
+    }
+}
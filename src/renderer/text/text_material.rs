@@ -0,0 +1,73 @@
+use crate::core::*;
+use crate::renderer::*;
+use std::rc::Rc;
+
+///
+/// A material that renders text using the signed distance field glyph atlas of a [Font].
+/// Use it together with a [Text2D] or [Text3D]. This material is not affected by lights.
+///
+#[derive(Clone)]
+pub struct TextMaterial {
+    /// The color of the text. Assumed to be in linear color space.
+    pub color: Color,
+    /// The color of the outline. Assumed to be in linear color space.
+    pub outline_color: Color,
+    /// The width of the outline around the glyphs relative to the font size. Zero disables the outline.
+    /// The outline is limited by the [FontSettings::distance_range] of the font.
+    pub outline_width: f32,
+    /// Render states.
+    pub render_states: RenderStates,
+    texture: Rc<Texture2D>,
+    distance_scale: f32,
+}
+
+impl TextMaterial {
+    ///
+    /// Constructs a new text material which renders text using the glyphs of the given font in the given color.
+    ///
+    pub fn new(font: &Font, color: Color) -> Self {
+        Self {
+            color,
+            outline_color: Color::BLACK,
+            outline_width: 0.0,
+            render_states: RenderStates {
+                write_mask: WriteMask::COLOR,
+                blend: Blend::TRANSPARENCY,
+                cull: Cull::None,
+                ..Default::default()
+            },
+            texture: font.texture().clone(),
+            distance_scale: font.distance_scale(),
+        }
+    }
+}
+
+impl Material for TextMaterial {
+    fn fragment_shader_source(&self, _use_vertex_colors: bool, _lights: &[&dyn Light]) -> String {
+        format!(
+            "{}{}",
+            include_str!("../../core/shared.frag"),
+            include_str!("shaders/text_material.frag")
+        )
+    }
+    fn use_uniforms(
+        &self,
+        program: &Program,
+        _camera: &Camera,
+        _lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        program.use_texture("sdfTexture", &self.texture)?;
+        program.use_uniform("textColor", self.color)?;
+        program.use_uniform("outlineColor", self.outline_color)?;
+        program.use_uniform(
+            "outlineWidth",
+            (self.outline_width * self.distance_scale).clamp(0.0, 0.5),
+        )
+    }
+    fn render_states(&self) -> RenderStates {
+        self.render_states
+    }
+    fn is_transparent(&self) -> bool {
+        true
+    }
+}
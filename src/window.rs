@@ -38,6 +38,7 @@ pub use canvas::*;
 
 /// Type of mouse button.
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseButton {
     /// Left mouse button or one finger on touch.
     Left,
@@ -81,6 +82,8 @@ pub enum Event {
         /// Type of button if a button is pressed.
         button: Option<MouseButton>,
         /// The relative movement of the mouse/finger since last [Event::MouseMotion] event.
+        /// If the cursor is grabbed (see [WindowSettings::cursor_grab]), this is the raw movement of the mouse,
+        /// since the cursor position does not change.
         delta: (f64, f64),
         /// The screen position in logical pixels, to get it in physical pixels, multiply it with [FrameInput::device_pixel_ratio].
         /// The first value defines the position on the horizontal axis with zero being at the left border of the window
@@ -133,11 +136,39 @@ pub enum Event {
     },
     /// Fires when some text has been written.
     Text(String),
+    /// Fired when text is pasted from the clipboard, for example using Ctrl+V or Cmd+V.
+    Clipboard(String),
+    /// Fired when a file is dragged over the window.
+    HoveredFile {
+        /// The path of the hovered file.
+        /// On web, this is always `None` since the browser does not reveal information about the file before it is dropped.
+        path: Option<std::path::PathBuf>,
+    },
+    /// Fired when a file was hovered over the window, but then left the window or was cancelled.
+    HoveredFileCancelled,
+    /// Fired when a file is dropped onto the window.
+    /// If the file cannot be read, the error is logged using the [log](https://crates.io/crates/log) crate and no event is fired.
+    /// Use [Loaded::insert_bytes](crate::Loaded::insert_bytes) to insert the file into a set of loaded resources, for example:
+    ///
+    /// ```notrust
+    /// if let Event::DroppedFile { path, bytes } = event {
+    ///     let mut loaded = Loaded::new();
+    ///     loaded.insert_bytes(path, bytes.clone());
+    ///     let (meshes, materials) = loaded.gltf(path).unwrap();
+    /// }
+    /// ```
+    DroppedFile {
+        /// The path of the dropped file. On web, this is only the file name.
+        path: std::path::PathBuf,
+        /// The content of the dropped file.
+        bytes: Vec<u8>,
+    },
 }
 
 /// Keyboard key input.
 #[allow(missing_docs)]
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum Key {
     ArrowDown,
     ArrowLeft,
@@ -208,6 +239,7 @@ pub enum Key {
 
 /// State of modifiers (alt, ctrl, shift and command).
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Modifiers {
     /// Either of the alt keys are down (option ⌥ on Mac).
     pub alt: bool,
@@ -223,6 +255,28 @@ pub struct Modifiers {
 
 use crate::core::*;
 
+///
+/// Unique identifier of a window which is used to tell the windows apart when using multiple windows (see [FrameOutput::open_windows]).
+///
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Hash)]
+pub struct WindowId(u64);
+
+impl WindowId {
+    ///
+    /// Creates a new unique window id.
+    ///
+    pub fn new() -> Self {
+        static NEXT_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+        Self(NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed))
+    }
+}
+
+impl Default for WindowId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 ///
 /// Input from the window to the rendering (and whatever else needs it) each frame.
 ///
@@ -253,7 +307,11 @@ pub struct FrameInput {
     pub first_frame: bool,
 
     /// The graphics context for the window.
+    /// When using multiple windows, the graphics contexts of all of the windows share resources (buffers, textures, programs etc.).
     pub context: Context,
+
+    /// The id of the window that this frame input belongs to.
+    pub window_id: WindowId,
 }
 
 impl FrameInput {
@@ -277,7 +335,7 @@ impl FrameInput {
 pub struct FrameOutput {
     ///
     /// If this is true:
-    /// - On desktop, the window is closed and the renderloop is stopped.
+    /// - On desktop, the window is closed. If it is the window the render loop was started from, all windows are closed and the renderloop is stopped.
     /// - On web, the render loop is stopped, the event handlers are removed and the `Window` dropped. Note that the canvas is not removed.
     ///
     pub exit: bool,
@@ -302,6 +360,43 @@ pub struct FrameOutput {
     /// Whether to stop the render loop until next event.
     ///
     pub wait_next_event: bool,
+
+    ///
+    /// Opens a new window for each of the given ids and settings.
+    /// The new windows share graphics resources (buffers, textures, programs etc.) with this window and the render loop callback is called
+    /// for each of them with their own [FrameInput], in which the [FrameInput::window_id] is the given id.
+    /// If a window cannot be opened, the error is logged using the [log](https://crates.io/crates/log) crate and the window is skipped.
+    ///
+    /// A window, which is not the window the render loop was started from, is closed when the user closes it or when [FrameOutput::exit] is set to true for a frame belonging to that window.
+    /// Only works on desktop.
+    ///
+    #[cfg(any(feature = "glutin-window", feature = "canvas"))]
+    pub open_windows: Vec<(WindowId, WindowSettings)>,
+
+    ///
+    /// Changes the fullscreen mode of the window if this is set to some value.
+    /// `Some(Some(mode))` enters fullscreen using the given mode and `Some(None)` exits fullscreen.
+    ///
+    /// On web, the browser only allows entering fullscreen in response to user input,
+    /// so the change is applied on the next mouse click or key press.
+    ///
+    #[cfg(any(feature = "glutin-window", feature = "canvas"))]
+    pub fullscreen: Option<Option<Fullscreen>>,
+
+    ///
+    /// Shows or hides the cursor if this is set to some value (see [WindowSettings::cursor_visible]).
+    ///
+    #[cfg(any(feature = "glutin-window", feature = "canvas"))]
+    pub cursor_visible: Option<bool>,
+
+    ///
+    /// Grabs or releases the cursor if this is set to some value (see [WindowSettings::cursor_grab]).
+    ///
+    /// On web, the browser only allows grabbing the cursor in response to user input,
+    /// so the cursor is grabbed on the next mouse click or key press.
+    ///
+    #[cfg(any(feature = "glutin-window", feature = "canvas"))]
+    pub cursor_grab: Option<bool>,
 }
 
 impl Default for FrameOutput {
@@ -311,6 +406,14 @@ impl Default for FrameOutput {
             swap_buffers: true,
             screenshot: None,
             wait_next_event: false,
+            #[cfg(any(feature = "glutin-window", feature = "canvas"))]
+            open_windows: Vec::new(),
+            #[cfg(any(feature = "glutin-window", feature = "canvas"))]
+            fullscreen: None,
+            #[cfg(any(feature = "glutin-window", feature = "canvas"))]
+            cursor_visible: None,
+            #[cfg(any(feature = "glutin-window", feature = "canvas"))]
+            cursor_grab: None,
         }
     }
 }
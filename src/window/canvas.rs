@@ -33,6 +33,8 @@ pub enum CanvasError {
     PerformanceMissing,
     #[error("unable to add {0} event listener: {1}")]
     EventListenerFail(String, String),
+    #[error("unable to change the cursor: {0}")]
+    CursorChangeFailed(String),
 }
 
 ///
@@ -42,12 +44,15 @@ pub struct Window {
     canvas: Option<web_sys::HtmlCanvasElement>,
     window: Rc<web_sys::Window>,
     settings: WindowSettings,
+    id: WindowId,
     closures: Vec<Closure<dyn FnMut()>>,
     closures_with_event: Vec<Closure<dyn FnMut(web_sys::Event)>>,
     closures_with_mouseevent: Vec<Closure<dyn FnMut(web_sys::MouseEvent)>>,
     closures_with_wheelevent: Vec<Closure<dyn FnMut(web_sys::WheelEvent)>>,
     closures_with_touchevent: Vec<Closure<dyn FnMut(web_sys::TouchEvent)>>,
     closures_with_keyboardevent: Vec<Closure<dyn FnMut(web_sys::KeyboardEvent)>>,
+    closures_with_dragevent: Vec<Closure<dyn FnMut(web_sys::DragEvent)>>,
+    closures_with_clipboardevent: Vec<Closure<dyn FnMut(web_sys::ClipboardEvent)>>,
 }
 
 impl Window {
@@ -64,12 +69,15 @@ impl Window {
             canvas: None,
             window: Rc::new(websys_window),
             settings,
+            id: WindowId::new(),
             closures: Vec::new(),
             closures_with_event: Vec::new(),
             closures_with_mouseevent: Vec::new(),
             closures_with_wheelevent: Vec::new(),
             closures_with_touchevent: Vec::new(),
             closures_with_keyboardevent: Vec::new(),
+            closures_with_dragevent: Vec::new(),
+            closures_with_clipboardevent: Vec::new(),
         };
         if let Some(canvas) = document.get_elements_by_tag_name("canvas").item(0) {
             window.set_canvas(
@@ -116,12 +124,20 @@ impl Window {
         Ok(Viewport::new_at_origo(w, h))
     }
 
+    ///
+    /// Returns the id of this window which is used to identify the [FrameInput] for this window.
+    ///
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
     ///
     /// Returns the graphics context for this window.
     ///
     pub fn gl(&self) -> ThreeDResult<Context> {
         let context_options = ContextOptions {
             antialias: self.settings.multisamples > 0,
+            stencil: true,
         };
         let context = self
             .canvas
@@ -144,10 +160,22 @@ impl Window {
         context
             .get_extension("OES_texture_float_linear")
             .map_err(|e| CanvasError::OESTextureFloatNotSupported(format!(": {:?}", e)))?;
+        // The compressed texture formats can only be used if the extensions are enabled, but they are optional (see CompressedFormat::is_supported)
+        for extension in [
+            "WEBGL_compressed_texture_s3tc",
+            "EXT_texture_compression_rgtc",
+            "EXT_texture_compression_bptc",
+            "WEBGL_compressed_texture_etc",
+            "WEBGL_compressed_texture_astc",
+        ] {
+            context.get_extension(extension).ok();
+        }
 
-        crate::core::Context::from_gl_context(Rc::new(
-            crate::context::Context::from_webgl2_context(context),
-        ))
+        let mut c = crate::core::Context::from_gl_context(Rc::new(
+            crate::context::Context::from_webgl2_context(context.clone()),
+        ))?;
+        c.webgl_context = Some(context);
+        Ok(c)
     }
 
     ///
@@ -165,8 +193,16 @@ impl Window {
         let mut accumulated_time = 0.0;
         let mut first_frame = true;
         let context = self.gl()?;
+        let window_id = self.id;
 
-        let input = Input::new(self.window.clone());
+        let input = Input::new(self.window.clone(), self.canvas()?.clone());
+        if self.settings.fullscreen.is_some() {
+            input.borrow_mut().fullscreen_request = Some(true);
+        }
+        if self.settings.cursor_grab {
+            input.borrow_mut().cursor_grab_request = Some(true);
+        }
+        self.set_cursor_visible(self.settings.cursor_visible)?;
         self.add_context_menu_event_listener()?;
         self.add_resize_event_listener(input.clone())?;
         self.add_mouseenter_event_listener(input.clone())?;
@@ -180,6 +216,10 @@ impl Window {
         self.add_touchmove_event_listener(input.clone())?;
         self.add_key_down_event_listener(input.clone())?;
         self.add_key_up_event_listener(input.clone())?;
+        self.add_paste_event_listener(input.clone())?;
+        self.add_dragover_event_listener(input.clone())?;
+        self.add_dragleave_event_listener(input.clone())?;
+        self.add_drop_event_listener(input.clone())?;
 
         let input_clone = input.clone();
         input.borrow_mut().render_loop_closure = Some(Closure::wrap(Box::new(move || {
@@ -202,10 +242,21 @@ impl Window {
                 window_height: (height as f64 / device_pixel_ratio) as u32,
                 device_pixel_ratio,
                 first_frame: first_frame,
+                window_id,
             };
             first_frame = false;
             let frame_output = callback(frame_input);
 
+            if let Some(fullscreen) = frame_output.fullscreen {
+                input_clone.borrow_mut().fullscreen_request = Some(fullscreen.is_some());
+            }
+            if let Some(cursor_grab) = frame_output.cursor_grab {
+                input_clone.borrow_mut().cursor_grab_request = Some(cursor_grab);
+            }
+            if let Some(cursor_visible) = frame_output.cursor_visible {
+                self.set_cursor_visible(cursor_visible).unwrap();
+            }
+
             if frame_output.exit {
                 input_clone.borrow_mut().render_loop_closure = None;
             } else if !frame_output.wait_next_event {
@@ -217,6 +268,14 @@ impl Window {
         Ok(())
     }
 
+    fn set_cursor_visible(&self, visible: bool) -> ThreeDResult<()> {
+        self.canvas()?
+            .style()
+            .set_property("cursor", if visible { "auto" } else { "none" })
+            .map_err(|e| CanvasError::CursorChangeFailed(format!("{:?}", e)))?;
+        Ok(())
+    }
+
     fn pixels_per_point(&self) -> f64 {
         let pixels_per_point = self.window.device_pixel_ratio() as f64;
         if pixels_per_point > 0.0 && pixels_per_point.is_finite() {
@@ -364,6 +423,7 @@ impl Window {
                     2 => Some(MouseButton::Right),
                     _ => None,
                 };
+                input.handle_requests();
                 if let Some(button) = button {
                     let modifiers = input.modifiers;
                     input.mouse_pressed = Some(button);
@@ -399,6 +459,7 @@ impl Window {
                     2 => Some(MouseButton::Right),
                     _ => None,
                 };
+                input.handle_requests();
                 if let Some(button) = button {
                     let modifiers = input.modifiers;
                     input.mouse_pressed = None;
@@ -428,7 +489,10 @@ impl Window {
         let closure = Closure::wrap(Box::new(move |event: web_sys::MouseEvent| {
             if !event.default_prevented() {
                 let mut input = input.borrow_mut();
-                let delta = if let Some((x, y)) = input.last_position {
+                let delta = if input.is_cursor_grabbed() {
+                    // The cursor position does not change when the pointer is locked
+                    (event.movement_x() as f64, event.movement_y() as f64)
+                } else if let Some((x, y)) = input.last_position {
                     ((event.offset_x() - x) as f64, (event.offset_y() - y) as f64)
                 } else {
                     (0.0, 0.0)
@@ -529,6 +593,7 @@ impl Window {
         let closure = Closure::wrap(Box::new(move |event: web_sys::TouchEvent| {
             if !event.default_prevented() {
                 let mut input = input.borrow_mut();
+                input.handle_requests();
                 if let Some((x, y)) = input.last_position {
                     let modifiers = input.modifiers;
                     input.mouse_pressed = None;
@@ -619,6 +684,7 @@ impl Window {
         let closure = Closure::wrap(Box::new(move |event: web_sys::KeyboardEvent| {
             if !event.default_prevented() {
                 let mut input = input.borrow_mut();
+                input.handle_requests();
                 if update_modifiers(&mut input.modifiers, &event) {
                     let modifiers = input.modifiers;
                     input.events.push(Event::ModifiersChange { modifiers });
@@ -654,6 +720,7 @@ impl Window {
         let closure = Closure::wrap(Box::new(move |event: web_sys::KeyboardEvent| {
             if !event.default_prevented() {
                 let mut input = input.borrow_mut();
+                input.handle_requests();
                 if update_modifiers(&mut input.modifiers, &event) {
                     let modifiers = input.modifiers;
                     input.events.push(Event::ModifiersChange { modifiers });
@@ -678,15 +745,129 @@ impl Window {
         self.closures_with_keyboardevent.push(closure);
         Ok(())
     }
+
+    fn add_paste_event_listener(&mut self, input: Rc<RefCell<Input>>) -> ThreeDResult<()> {
+        let closure = Closure::wrap(Box::new(move |event: web_sys::ClipboardEvent| {
+            if !event.default_prevented() {
+                if let Some(data) = event.clipboard_data() {
+                    if let Ok(text) = data.get_data("text") {
+                        let mut input = input.borrow_mut();
+                        input
+                            .events
+                            .push(Event::Clipboard(text.replace("\r\n", "\n")));
+                        event.stop_propagation();
+                        event.prevent_default();
+                        input.request_animation_frame();
+                    }
+                }
+            }
+        }) as Box<dyn FnMut(_)>);
+        web_sys::window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .add_event_listener_with_callback("paste", closure.as_ref().unchecked_ref())
+            .map_err(|e| CanvasError::EventListenerFail("paste".to_string(), format!("{:?}", e)))?;
+        self.closures_with_clipboardevent.push(closure);
+        Ok(())
+    }
+
+    fn add_dragover_event_listener(&mut self, input: Rc<RefCell<Input>>) -> ThreeDResult<()> {
+        let closure = Closure::wrap(Box::new(move |event: web_sys::DragEvent| {
+            if !event.default_prevented() {
+                let mut input = input.borrow_mut();
+                if !input.file_hovered {
+                    input.file_hovered = true;
+                    input.events.push(Event::HoveredFile { path: None });
+                    input.request_animation_frame();
+                }
+                // Prevent default to allow dropping files onto the canvas
+                event.stop_propagation();
+                event.prevent_default();
+            }
+        }) as Box<dyn FnMut(_)>);
+        self.canvas()?
+            .add_event_listener_with_callback("dragover", closure.as_ref().unchecked_ref())
+            .map_err(|e| {
+                CanvasError::EventListenerFail("dragover".to_string(), format!("{:?}", e))
+            })?;
+        self.closures_with_dragevent.push(closure);
+        Ok(())
+    }
+
+    fn add_dragleave_event_listener(&mut self, input: Rc<RefCell<Input>>) -> ThreeDResult<()> {
+        let closure = Closure::wrap(Box::new(move |event: web_sys::DragEvent| {
+            if !event.default_prevented() {
+                let mut input = input.borrow_mut();
+                if input.file_hovered {
+                    input.file_hovered = false;
+                    input.events.push(Event::HoveredFileCancelled);
+                    input.request_animation_frame();
+                }
+                event.stop_propagation();
+                event.prevent_default();
+            }
+        }) as Box<dyn FnMut(_)>);
+        self.canvas()?
+            .add_event_listener_with_callback("dragleave", closure.as_ref().unchecked_ref())
+            .map_err(|e| {
+                CanvasError::EventListenerFail("dragleave".to_string(), format!("{:?}", e))
+            })?;
+        self.closures_with_dragevent.push(closure);
+        Ok(())
+    }
+
+    fn add_drop_event_listener(&mut self, input: Rc<RefCell<Input>>) -> ThreeDResult<()> {
+        let closure = Closure::wrap(Box::new(move |event: web_sys::DragEvent| {
+            if !event.default_prevented() {
+                input.borrow_mut().file_hovered = false;
+                if let Some(files) = event.data_transfer().and_then(|d| d.files()) {
+                    for i in 0..files.length() {
+                        if let Some(file) = files.item(i) {
+                            let input = input.clone();
+                            wasm_bindgen_futures::spawn_local(async move {
+                                let name = file.name();
+                                match wasm_bindgen_futures::JsFuture::from(file.array_buffer())
+                                    .await
+                                {
+                                    Ok(buffer) => {
+                                        let bytes = js_sys::Uint8Array::new(&buffer).to_vec();
+                                        let mut input = input.borrow_mut();
+                                        input.events.push(Event::DroppedFile {
+                                            path: name.into(),
+                                            bytes,
+                                        });
+                                        input.request_animation_frame();
+                                    }
+                                    Err(e) => {
+                                        log::error!("Failed to read dropped file {}: {:?}", name, e)
+                                    }
+                                }
+                            });
+                        }
+                    }
+                }
+                event.stop_propagation();
+                event.prevent_default();
+            }
+        }) as Box<dyn FnMut(_)>);
+        self.canvas()?
+            .add_event_listener_with_callback("drop", closure.as_ref().unchecked_ref())
+            .map_err(|e| CanvasError::EventListenerFail("drop".to_string(), format!("{:?}", e)))?;
+        self.closures_with_dragevent.push(closure);
+        Ok(())
+    }
 }
 
 #[derive(Serialize)]
 struct ContextOptions {
     antialias: bool,
+    stencil: bool,
 }
 
 struct Input {
     window: Rc<web_sys::Window>,
+    canvas: web_sys::HtmlCanvasElement,
     render_loop_closure: Option<Closure<dyn FnMut()>>,
     render_requested: bool,
     events: Vec<Event>,
@@ -694,12 +875,19 @@ struct Input {
     last_position: Option<(i32, i32)>,
     last_zoom: Option<f64>,
     mouse_pressed: Option<MouseButton>,
+    file_hovered: bool,
+    fullscreen_request: Option<bool>,
+    cursor_grab_request: Option<bool>,
 }
 
 impl Input {
-    pub fn new(window: Rc<web_sys::Window>) -> Rc<RefCell<Self>> {
+    pub fn new(
+        window: Rc<web_sys::Window>,
+        canvas: web_sys::HtmlCanvasElement,
+    ) -> Rc<RefCell<Self>> {
         Rc::new(RefCell::new(Self {
             window,
+            canvas,
             render_loop_closure: None,
             render_requested: false,
             events: Vec::new(),
@@ -707,9 +895,42 @@ impl Input {
             last_position: None,
             last_zoom: None,
             mouse_pressed: None,
+            file_hovered: false,
+            fullscreen_request: None,
+            cursor_grab_request: None,
         }))
     }
 
+    ///
+    /// Handles requests that the browser only allows in response to user input, so this must be called from an input event listener.
+    ///
+    pub fn handle_requests(&mut self) {
+        let document = self.window.document().unwrap();
+        if let Some(fullscreen) = self.fullscreen_request.take() {
+            if fullscreen {
+                // Fails if fullscreen is not supported, in which case we stay in windowed mode
+                self.canvas.request_fullscreen().ok();
+            } else if document.fullscreen_element().is_some() {
+                document.exit_fullscreen();
+            }
+        }
+        if let Some(cursor_grab) = self.cursor_grab_request.take() {
+            if cursor_grab {
+                self.canvas.request_pointer_lock();
+            } else if self.is_cursor_grabbed() {
+                document.exit_pointer_lock();
+            }
+        }
+    }
+
+    pub fn is_cursor_grabbed(&self) -> bool {
+        self.window
+            .document()
+            .unwrap()
+            .pointer_lock_element()
+            .is_some()
+    }
+
     pub fn start_frame(&mut self) -> Vec<Event> {
         let events = self.events.clone();
         self.events.clear();
@@ -826,6 +1047,34 @@ impl Drop for Window {
                 .remove_event_listener_with_callback("keydown", closure.as_ref().unchecked_ref())
                 .unwrap();
         }
+
+        if let Some(closure) = self.closures_with_clipboardevent.pop() {
+            web_sys::window()
+                .unwrap()
+                .document()
+                .unwrap()
+                .remove_event_listener_with_callback("paste", closure.as_ref().unchecked_ref())
+                .unwrap();
+        }
+
+        if let Some(closure) = self.closures_with_dragevent.pop() {
+            self.canvas()
+                .unwrap()
+                .remove_event_listener_with_callback("drop", closure.as_ref().unchecked_ref())
+                .unwrap();
+        }
+        if let Some(closure) = self.closures_with_dragevent.pop() {
+            self.canvas()
+                .unwrap()
+                .remove_event_listener_with_callback("dragleave", closure.as_ref().unchecked_ref())
+                .unwrap();
+        }
+        if let Some(closure) = self.closures_with_dragevent.pop() {
+            self.canvas()
+                .unwrap()
+                .remove_event_listener_with_callback("dragover", closure.as_ref().unchecked_ref())
+                .unwrap();
+        }
     }
 }
 
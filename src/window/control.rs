@@ -6,10 +6,18 @@ mod camera_control;
 #[doc(inline)]
 pub use camera_control::*;
 
+mod input_map;
+#[doc(inline)]
+pub use input_map::*;
+
 mod orbit_control;
 #[doc(inline)]
 pub use orbit_control::*;
 
+mod pan_zoom_control;
+#[doc(inline)]
+pub use pan_zoom_control::*;
+
 mod first_person_control;
 #[doc(inline)]
 pub use first_person_control::*;
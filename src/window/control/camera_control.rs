@@ -5,6 +5,7 @@ use crate::window::*;
 /// A set of possible actions to apply to a camera when recieving input.
 ///
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum CameraAction {
     /// No action.
     None,
@@ -93,6 +94,12 @@ pub struct CameraControl {
     pub scroll_horizontal: CameraAction,
     /// Specifies what happens when scrolling vertically.
     pub scroll_vertical: CameraAction,
+    /// Specifies what happens when moving the mouse horizontally without pressing any buttons,
+    /// which is typically used for mouse-look together with a grabbed cursor (see [WindowSettings::cursor_grab](crate::WindowSettings::cursor_grab)).
+    pub move_horizontal: CameraAction,
+    /// Specifies what happens when moving the mouse vertically without pressing any buttons,
+    /// which is typically used for mouse-look together with a grabbed cursor (see [WindowSettings::cursor_grab](crate::WindowSettings::cursor_grab)).
+    pub move_vertical: CameraAction,
 }
 
 impl CameraControl {
@@ -116,7 +123,7 @@ impl CameraControl {
                     handled,
                     ..
                 } => {
-                    if !*handled && button.is_some() {
+                    if !*handled {
                         if let Some(b) = button {
                             let (control_horizontal, control_vertical) = match b {
                                 MouseButton::Left => {
@@ -129,16 +136,20 @@ impl CameraControl {
                                     (self.right_drag_horizontal, self.right_drag_vertical)
                                 }
                             };
-                            *handled = self.handle_action(camera, control_horizontal, delta.0)?;
-                            *handled |= self.handle_action(camera, control_vertical, delta.1)?;
+                            *handled = Self::handle_action(camera, control_horizontal, delta.0)?;
+                            *handled |= Self::handle_action(camera, control_vertical, delta.1)?;
+                            change |= *handled;
+                        } else {
+                            *handled = Self::handle_action(camera, self.move_horizontal, delta.0)?;
+                            *handled |= Self::handle_action(camera, self.move_vertical, delta.1)?;
                             change |= *handled;
                         }
                     }
                 }
                 Event::MouseWheel { delta, handled, .. } => {
                     if !*handled {
-                        *handled = self.handle_action(camera, self.scroll_horizontal, delta.0)?;
-                        *handled |= self.handle_action(camera, self.scroll_vertical, delta.1)?;
+                        *handled = Self::handle_action(camera, self.scroll_horizontal, delta.0)?;
+                        *handled |= Self::handle_action(camera, self.scroll_vertical, delta.1)?;
                         change |= *handled;
                     }
                 }
@@ -148,8 +159,7 @@ impl CameraControl {
         Ok(change)
     }
 
-    fn handle_action(
-        &mut self,
+    pub(super) fn handle_action(
         camera: &mut Camera,
         control_type: CameraAction,
         x: f64,
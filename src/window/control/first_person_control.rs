@@ -3,31 +3,94 @@ use crate::window::*;
 
 ///
 /// A control that makes the camera move like it is a person on the ground.
+/// Use the mouse to look around and move and the W, A, S and D keys to move forward, left, backward and right.
 ///
 pub struct FirstPersonControl {
-    control: CameraControl,
+    input_map: InputMap,
 }
 
 impl FirstPersonControl {
     /// Creates a new first person control with the given speed of movements.
     pub fn new(speed: f32) -> Self {
         Self {
-            control: CameraControl {
-                left_drag_horizontal: CameraAction::Yaw {
+            input_map: InputMap::new(
+                [
+                    InputMapping::new(
+                        InputBinding::MouseMotion {
+                            button: Some(MouseButton::Left),
+                            modifiers: None,
+                            axis: InputAxis::Horizontal,
+                        },
+                        CameraAction::Yaw {
+                            speed: std::f32::consts::PI / 1800.0,
+                        },
+                    ),
+                    InputMapping::new(
+                        InputBinding::MouseWheel {
+                            modifiers: None,
+                            axis: InputAxis::Vertical,
+                        },
+                        CameraAction::Forward { speed },
+                    ),
+                ]
+                .into_iter()
+                .chain(super::fly_control::wasd_mappings(speed))
+                .collect(),
+            ),
+        }
+    }
+
+    ///
+    /// Enables or disables mouse-look, ie. that the camera is rotated when moving the mouse without pressing any buttons.
+    /// This should be combined with grabbing the cursor (see [WindowSettings::cursor_grab](crate::WindowSettings::cursor_grab)) so that the cursor does not leave the window.
+    ///
+    pub fn set_mouse_look(&mut self, enabled: bool) {
+        self.input_map
+            .mappings
+            .retain(|m| !matches!(m.binding, InputBinding::MouseMotion { button: None, .. }));
+        if enabled {
+            self.input_map.mappings.push(InputMapping::new(
+                InputBinding::MouseMotion {
+                    button: None,
+                    modifiers: None,
+                    axis: InputAxis::Horizontal,
+                },
+                CameraAction::Yaw {
                     speed: std::f32::consts::PI / 1800.0,
                 },
-                scroll_vertical: CameraAction::Forward { speed },
-                ..Default::default()
-            },
+            ));
+            self.input_map.mappings.push(InputMapping::new(
+                InputBinding::MouseMotion {
+                    button: None,
+                    modifiers: None,
+                    axis: InputAxis::Vertical,
+                },
+                CameraAction::Pitch {
+                    speed: std::f32::consts::PI / 1800.0,
+                },
+            ));
         }
     }
 
-    /// Handles the events. Must be called each frame.
+    /// Returns the input mappings of this control.
+    pub fn input_map(&self) -> &InputMap {
+        &self.input_map
+    }
+
+    /// Returns the input mappings of this control which can be changed to rebind the inputs.
+    pub fn input_map_mut(&mut self) -> &mut InputMap {
+        &mut self.input_map
+    }
+
+    ///
+    /// Handles the events. Must be called each frame with the time in milliseconds since the last frame, for example [FrameInput::elapsed_time].
+    ///
     pub fn handle_events(
         &mut self,
         camera: &mut Camera,
         events: &mut [Event],
+        elapsed_time: f64,
     ) -> ThreeDResult<bool> {
-        self.control.handle_events(camera, events)
+        self.input_map.handle_events(camera, events, elapsed_time)
     }
 }
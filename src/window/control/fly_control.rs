@@ -3,36 +3,110 @@ use crate::window::*;
 
 ///
 /// A control that makes the camera fly through the 3D scene.
+/// Use the mouse to look around and move and the W, A, S and D keys to move forward, left, backward and right.
 ///
 pub struct FlyControl {
-    control: CameraControl,
+    input_map: InputMap,
 }
 
 impl FlyControl {
     /// Creates a new fly control with the given speed of movements.
     pub fn new(speed: f32) -> Self {
         Self {
-            control: CameraControl {
-                left_drag_horizontal: CameraAction::Yaw {
-                    speed: std::f32::consts::PI / 1800.0,
-                },
-                left_drag_vertical: CameraAction::Pitch {
-                    speed: std::f32::consts::PI / 1800.0,
-                },
-                scroll_vertical: CameraAction::Forward { speed },
-                right_drag_horizontal: CameraAction::Left { speed },
-                right_drag_vertical: CameraAction::Up { speed },
-                ..Default::default()
-            },
+            input_map: InputMap::new(
+                [
+                    (
+                        MouseButton::Left,
+                        InputAxis::Horizontal,
+                        CameraAction::Yaw {
+                            speed: std::f32::consts::PI / 1800.0,
+                        },
+                    ),
+                    (
+                        MouseButton::Left,
+                        InputAxis::Vertical,
+                        CameraAction::Pitch {
+                            speed: std::f32::consts::PI / 1800.0,
+                        },
+                    ),
+                    (
+                        MouseButton::Right,
+                        InputAxis::Horizontal,
+                        CameraAction::Left { speed },
+                    ),
+                    (
+                        MouseButton::Right,
+                        InputAxis::Vertical,
+                        CameraAction::Up { speed },
+                    ),
+                ]
+                .into_iter()
+                .map(|(button, axis, action)| {
+                    InputMapping::new(
+                        InputBinding::MouseMotion {
+                            button: Some(button),
+                            modifiers: None,
+                            axis,
+                        },
+                        action,
+                    )
+                })
+                .chain(std::iter::once(InputMapping::new(
+                    InputBinding::MouseWheel {
+                        modifiers: None,
+                        axis: InputAxis::Vertical,
+                    },
+                    CameraAction::Forward { speed },
+                )))
+                .chain(wasd_mappings(speed))
+                .collect(),
+            ),
         }
     }
 
-    /// Handles the events. Must be called each frame.
+    /// Returns the input mappings of this control.
+    pub fn input_map(&self) -> &InputMap {
+        &self.input_map
+    }
+
+    /// Returns the input mappings of this control which can be changed to rebind the inputs.
+    pub fn input_map_mut(&mut self) -> &mut InputMap {
+        &mut self.input_map
+    }
+
+    ///
+    /// Handles the events. Must be called each frame with the time in milliseconds since the last frame, for example [FrameInput::elapsed_time].
+    ///
     pub fn handle_events(
         &mut self,
         camera: &mut Camera,
         events: &mut [Event],
+        elapsed_time: f64,
     ) -> ThreeDResult<bool> {
-        self.control.handle_events(camera, events)
+        self.input_map.handle_events(camera, events, elapsed_time)
     }
 }
+
+///
+/// Mappings moving the camera forward, left, backward and right with the W, A, S and D keys.
+/// The camera moves a distance of `speed` per 10 milliseconds the keys are held down.
+///
+pub(super) fn wasd_mappings(speed: f32) -> impl Iterator<Item = InputMapping> {
+    [
+        (Key::W, CameraAction::Forward { speed }, 0.1),
+        (Key::A, CameraAction::Left { speed }, 0.1),
+        (Key::S, CameraAction::Forward { speed }, -0.1),
+        (Key::D, CameraAction::Left { speed }, -0.1),
+    ]
+    .into_iter()
+    .map(|(key, action, scale)| InputMapping {
+        scale,
+        ..InputMapping::new(
+            InputBinding::Key {
+                key,
+                modifiers: None,
+            },
+            action,
+        )
+    })
+}
@@ -0,0 +1,315 @@
+use crate::core::*;
+use crate::window::*;
+use std::collections::HashMap;
+
+///
+/// One of the two axes of a two-dimensional input like mouse motion and scrolling.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputAxis {
+    /// The horizontal axis.
+    Horizontal,
+    /// The vertical axis.
+    Vertical,
+}
+
+///
+/// An input which can be bound to a [CameraAction] using an [InputMapping].
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputBinding {
+    /// Holding down a key.
+    /// The input value is the time in milliseconds since the last frame, so the action is applied continuously while the key is held down.
+    Key {
+        /// The key.
+        key: Key,
+        /// The modifiers that must be pressed together with the key. If None is specified, the modifiers are ignored.
+        modifiers: Option<Modifiers>,
+    },
+    /// Moving the mouse (or a finger on the screen).
+    /// The input value is the movement along the given axis in logical pixels.
+    MouseMotion {
+        /// The mouse button that must be pressed while moving the mouse. If None is specified, no button must be pressed.
+        button: Option<MouseButton>,
+        /// The modifiers that must be pressed while moving the mouse. If None is specified, the modifiers are ignored.
+        modifiers: Option<Modifiers>,
+        /// The axis of the movement.
+        axis: InputAxis,
+    },
+    /// Scrolling with the mouse wheel or equivalent.
+    /// The input value is the scroll delta along the given axis.
+    MouseWheel {
+        /// The modifiers that must be pressed while scrolling. If None is specified, the modifiers are ignored.
+        modifiers: Option<Modifiers>,
+        /// The axis of the scrolling.
+        axis: InputAxis,
+    },
+    /// A gamepad axis, for example one of the axes of an analog stick.
+    /// The input value is the value of the axis (between -1 and 1) multiplied by the time in milliseconds since the last frame,
+    /// so the action is applied continuously while the axis is moved away from its center.
+    ///
+    /// The windows do not report gamepad input, so the axis values must be given using [InputMap::set_gamepad_axis],
+    /// for example from a gamepad library like [gilrs](https://crates.io/crates/gilrs).
+    GamepadAxis {
+        /// The index of the axis.
+        axis: u32,
+    },
+}
+
+///
+/// Specifies how the input value of an [InputMapping] is transformed before it is applied to the action.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResponseCurve {
+    /// The input value is used as is.
+    #[default]
+    Linear,
+    /// The absolute input value is raised to the power of the given exponent while keeping the sign,
+    /// which for example gives more precise control of small gamepad axis movements when the exponent is larger than one.
+    Power {
+        /// The exponent.
+        exponent: f32,
+    },
+}
+
+///
+/// Binds an input to a [CameraAction], see [InputMap].
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputMapping {
+    /// The input.
+    pub binding: InputBinding,
+    /// The action to apply when recieving the input.
+    pub action: CameraAction,
+    /// The input value is multiplied with this scale before it is applied to the action. Use a negative scale to invert the action,
+    /// for example to move backwards using a [CameraAction::Forward] action.
+    pub scale: f32,
+    /// Input values with an absolute value smaller than the dead-zone are ignored.
+    /// This is mostly useful for gamepad axes which rarely are exactly zero at their center.
+    pub dead_zone: f32,
+    /// The response curve which is applied to the input value after the dead-zone.
+    pub curve: ResponseCurve,
+    /// The acceleration of continuous inputs, ie. keys and gamepad axes.
+    /// The input value is multiplied with `1 + acceleration * t` where `t` is the time in seconds the input has been active.
+    pub acceleration: f32,
+}
+
+impl InputMapping {
+    ///
+    /// Creates a new mapping from the given input to the given action with a scale of one, no dead-zone, a linear response curve and no acceleration.
+    ///
+    pub fn new(binding: InputBinding, action: CameraAction) -> Self {
+        Self {
+            binding,
+            action,
+            scale: 1.0,
+            dead_zone: 0.0,
+            curve: ResponseCurve::Linear,
+            acceleration: 0.0,
+        }
+    }
+
+    fn value(&self, input: f64, active_time: f64) -> f64 {
+        if input.abs() < self.dead_zone as f64 {
+            return 0.0;
+        }
+        let value = match self.curve {
+            ResponseCurve::Linear => input,
+            ResponseCurve::Power { exponent } => input.signum() * input.abs().powf(exponent as f64),
+        };
+        value * self.scale as f64 * (1.0 + self.acceleration as f64 * active_time * 0.001)
+    }
+}
+
+///
+/// A configurable controller for the camera which applies [CameraAction]s when recieving the inputs specified by a list of [InputMapping]s.
+/// Compared to [CameraControl], it supports keys and gamepad axes, modifiers, dead-zones and acceleration.
+/// When the `serialize` feature is enabled, the input map can be serialized using [serde](https://crates.io/crates/serde) which makes it possible to store user defined bindings.
+///
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputMap {
+    /// The input mappings.
+    pub mappings: Vec<InputMapping>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    state: InputState,
+}
+
+#[derive(Clone, Debug, Default)]
+struct InputState {
+    modifiers: Modifiers,
+    // The time in milliseconds each pressed key has been held down
+    pressed_keys: HashMap<Key, f64>,
+    // The value and the time in milliseconds each gamepad axis has been outside the dead-zone
+    gamepad_axes: HashMap<u32, (f32, f64)>,
+}
+
+impl InputMap {
+    /// Creates a new input map with the given mappings.
+    pub fn new(mappings: Vec<InputMapping>) -> Self {
+        Self {
+            mappings,
+            state: InputState::default(),
+        }
+    }
+
+    ///
+    /// Sets the value of the gamepad axis with the given index, see [InputBinding::GamepadAxis]. The value should be between -1 and 1.
+    ///
+    pub fn set_gamepad_axis(&mut self, axis: u32, value: f32) {
+        let entry = self.state.gamepad_axes.entry(axis).or_insert((0.0, 0.0));
+        entry.0 = value;
+    }
+
+    ///
+    /// Handles the events. Must be called each frame with the time in milliseconds since the last frame, for example [FrameInput::elapsed_time],
+    /// which is used for the continuous key and gamepad axis inputs.
+    ///
+    pub fn handle_events(
+        &mut self,
+        camera: &mut Camera,
+        events: &mut [Event],
+        elapsed_time: f64,
+    ) -> ThreeDResult<bool> {
+        // Limited so continuous actions do not jump if no frames have been rendered for a while
+        let elapsed_time = elapsed_time.min(100.0);
+        let mut change = false;
+        for event in events.iter_mut() {
+            match event {
+                Event::ModifiersChange { modifiers } => {
+                    self.state.modifiers = *modifiers;
+                }
+                Event::KeyPress {
+                    kind,
+                    modifiers,
+                    handled,
+                } => {
+                    self.state.modifiers = *modifiers;
+                    if !*handled
+                        && self.mappings.iter().any(|m| {
+                            matches!(m.binding, InputBinding::Key { key, modifiers: binding }
+                                if key == *kind && matches_modifiers(binding, *modifiers))
+                        })
+                    {
+                        self.state.pressed_keys.entry(*kind).or_insert(0.0);
+                        *handled = true;
+                    }
+                }
+                Event::KeyRelease {
+                    kind, modifiers, ..
+                } => {
+                    self.state.modifiers = *modifiers;
+                    self.state.pressed_keys.remove(kind);
+                }
+                Event::MouseMotion {
+                    delta,
+                    button,
+                    modifiers,
+                    handled,
+                    ..
+                } if !*handled => {
+                    for mapping in self.mappings.iter() {
+                        if let InputBinding::MouseMotion {
+                            button: b,
+                            modifiers: m,
+                            axis,
+                        } = mapping.binding
+                        {
+                            if b == *button && matches_modifiers(m, *modifiers) {
+                                let input = match axis {
+                                    InputAxis::Horizontal => delta.0,
+                                    InputAxis::Vertical => delta.1,
+                                };
+                                *handled |= CameraControl::handle_action(
+                                    camera,
+                                    mapping.action,
+                                    mapping.value(input, 0.0),
+                                )?;
+                            }
+                        }
+                    }
+                    change |= *handled;
+                }
+                Event::MouseWheel {
+                    delta,
+                    modifiers,
+                    handled,
+                    ..
+                } if !*handled => {
+                    for mapping in self.mappings.iter() {
+                        if let InputBinding::MouseWheel { modifiers: m, axis } = mapping.binding {
+                            if matches_modifiers(m, *modifiers) {
+                                let input = match axis {
+                                    InputAxis::Horizontal => delta.0,
+                                    InputAxis::Vertical => delta.1,
+                                };
+                                *handled |= CameraControl::handle_action(
+                                    camera,
+                                    mapping.action,
+                                    mapping.value(input, 0.0),
+                                )?;
+                            }
+                        }
+                    }
+                    change |= *handled;
+                }
+                _ => {}
+            }
+        }
+
+        for mapping in self.mappings.iter() {
+            match mapping.binding {
+                InputBinding::Key { key, modifiers } => {
+                    if let Some(held_time) = self.state.pressed_keys.get(&key) {
+                        if matches_modifiers(modifiers, self.state.modifiers) {
+                            change |= CameraControl::handle_action(
+                                camera,
+                                mapping.action,
+                                mapping.value(elapsed_time, *held_time),
+                            )?;
+                        }
+                    }
+                }
+                InputBinding::GamepadAxis { axis } => {
+                    if let Some((value, active_time)) = self.state.gamepad_axes.get(&axis) {
+                        let value = mapping.value(*value as f64, *active_time);
+                        if value != 0.0 {
+                            change |= CameraControl::handle_action(
+                                camera,
+                                mapping.action,
+                                value * elapsed_time,
+                            )?;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for held_time in self.state.pressed_keys.values_mut() {
+            *held_time += elapsed_time;
+        }
+        for (axis, (value, active_time)) in self.state.gamepad_axes.iter_mut() {
+            let dead_zone = self
+                .mappings
+                .iter()
+                .filter(|m| m.binding == InputBinding::GamepadAxis { axis: *axis })
+                .map(|m| m.dead_zone)
+                .fold(0.0, f32::max);
+            if value.abs() < dead_zone || *value == 0.0 {
+                *active_time = 0.0;
+            } else {
+                *active_time += elapsed_time;
+            }
+        }
+        Ok(change)
+    }
+}
+
+fn matches_modifiers(binding: Option<Modifiers>, modifiers: Modifiers) -> bool {
+    binding.map(|m| m == modifiers).unwrap_or(true)
+}
@@ -1,40 +1,266 @@
 use crate::core::*;
+use crate::renderer::*;
 use crate::window::*;
 
 ///
 /// A control that makes the camera orbit around a target.
+/// Drag with the left mouse button to orbit and scroll to zoom.
+/// Panning and inertia are disabled by default, see [OrbitControl::pan_button] and [OrbitControl::inertia].
 ///
 pub struct OrbitControl {
-    control: CameraControl,
+    /// The target which the camera orbits around and zooms towards. Panning moves the target together with the camera.
+    pub target: Vec3,
+    /// The minimum distance to the target.
+    pub min_distance: f32,
+    /// The maximum distance to the target.
+    pub max_distance: f32,
+    /// The minimum angle between the camera up direction and the direction from the target to the camera,
+    /// ie. a larger angle prevents the camera from looking straight down at the target.
+    pub min_polar_angle: Radians,
+    /// The maximum angle between the camera up direction and the direction from the target to the camera,
+    /// ie. a smaller angle prevents the camera from looking straight up at the target.
+    pub max_polar_angle: Radians,
+    /// The mouse button to drag with to pan, for example [MouseButton::Right], or `None` to disable panning.
+    pub pan_button: Option<MouseButton>,
+    /// The time in milliseconds it takes for the orbit and pan motion to slow down to about a third of the speed after releasing the mouse button,
+    /// for example 100.0. Zero disables inertia so that the motion stops immediately.
+    pub inertia: f64,
+    orbit_velocity: Vec2,
+    pan_velocity: Vec2,
+    pressed_button: Option<MouseButton>,
 }
 
 impl OrbitControl {
     /// Creates a new orbit control with the given target and minimum and maximum distance to the target.
     pub fn new(target: Vec3, min_distance: f32, max_distance: f32) -> Self {
         Self {
-            control: CameraControl {
-                left_drag_horizontal: CameraAction::OrbitLeft { target, speed: 0.5 },
-                left_drag_vertical: CameraAction::OrbitUp { target, speed: 0.5 },
-                scroll_vertical: CameraAction::Zoom {
-                    min: min_distance,
-                    max: max_distance,
-                    speed: 0.1,
-                    target,
-                },
-                ..Default::default()
-            },
+            target,
+            min_distance,
+            max_distance,
+            min_polar_angle: radians(0.0),
+            max_polar_angle: radians(std::f32::consts::PI),
+            pan_button: None,
+            inertia: 0.0,
+            orbit_velocity: vec2(0.0, 0.0),
+            pan_velocity: vec2(0.0, 0.0),
+            pressed_button: None,
         }
     }
 
-    /// Handles the events. Must be called each frame.
+    ///
+    /// Handles the events. Must be called each frame with the time in milliseconds since the last frame, for example [FrameInput::elapsed_time],
+    /// which is used for the inertia.
+    ///
     pub fn handle_events(
         &mut self,
         camera: &mut Camera,
         events: &mut [Event],
+        elapsed_time: f64,
     ) -> ThreeDResult<bool> {
-        if let CameraAction::Zoom { speed, target, .. } = &mut self.control.scroll_horizontal {
-            *speed = 0.1 / target.distance(*camera.position());
+        self.handle(camera, events, elapsed_time, None)
+    }
+
+    ///
+    /// Handles the events like [handle_events](Self::handle_events) except that zooming is towards the point on the given geometries under the cursor,
+    /// found using [pick], instead of towards the target. If no geometry is under the cursor, it zooms towards the target. Must be called each frame.
+    ///
+    pub fn handle_events_with_zoom_to_cursor(
+        &mut self,
+        camera: &mut Camera,
+        frame_input: &mut FrameInput,
+        geometries: &[&dyn Geometry],
+    ) -> ThreeDResult<bool> {
+        let context = frame_input.context.clone();
+        let picking = Picking {
+            context: &context,
+            device_pixel_ratio: frame_input.device_pixel_ratio,
+            viewport: frame_input.viewport,
+            geometries,
+        };
+        let elapsed_time = frame_input.elapsed_time;
+        self.handle(camera, &mut frame_input.events, elapsed_time, Some(picking))
+    }
+
+    fn handle(
+        &mut self,
+        camera: &mut Camera,
+        events: &mut [Event],
+        elapsed_time: f64,
+        picking: Option<Picking>,
+    ) -> ThreeDResult<bool> {
+        // Limited so the inertia does not jump if no frames have been rendered for a while
+        let elapsed_time = elapsed_time.min(100.0);
+        if camera.is_animating() {
+            // Let the animation finish and follow the camera
+            self.target = *camera.target();
+            self.orbit_velocity = vec2(0.0, 0.0);
+            self.pan_velocity = vec2(0.0, 0.0);
+            return Ok(false);
+        }
+
+        let mut change = false;
+        let mut orbit = vec2(0.0, 0.0);
+        let mut pan = vec2(0.0, 0.0);
+        for event in events.iter_mut() {
+            match event {
+                Event::MousePress { button, .. } => {
+                    self.pressed_button = Some(*button);
+                    self.orbit_velocity = vec2(0.0, 0.0);
+                    self.pan_velocity = vec2(0.0, 0.0);
+                }
+                Event::MouseRelease { .. } | Event::MouseLeave => {
+                    self.pressed_button = None;
+                }
+                Event::MouseMotion {
+                    delta,
+                    button: Some(button),
+                    handled,
+                    ..
+                } if !*handled => {
+                    if Some(*button) == self.pan_button {
+                        pan += vec2(delta.0 as f32, delta.1 as f32);
+                        *handled = true;
+                    } else if *button == MouseButton::Left {
+                        orbit += vec2(delta.0 as f32, delta.1 as f32);
+                        *handled = true;
+                    }
+                }
+                Event::MouseWheel {
+                    delta,
+                    position,
+                    handled,
+                    ..
+                } if !*handled => {
+                    let point = if let Some(picking) = &picking {
+                        picking.pick(camera, *position)?
+                    } else {
+                        None
+                    };
+                    self.zoom(camera, point.unwrap_or(self.target), delta.1 as f32)?;
+                    *handled = true;
+                    change = true;
+                }
+                _ => {}
+            }
+        }
+
+        if self.pressed_button.is_some() {
+            // Track the velocity while dragging which is used when the mouse button is released
+            if elapsed_time > 0.0 {
+                self.orbit_velocity = orbit / elapsed_time as f32;
+                self.pan_velocity = pan / elapsed_time as f32;
+            }
+        } else if self.inertia > 0.0 {
+            orbit += self.orbit_velocity * elapsed_time as f32;
+            pan += self.pan_velocity * elapsed_time as f32;
+            let decay = (-elapsed_time / self.inertia).exp() as f32;
+            self.orbit_velocity *= decay;
+            self.pan_velocity *= decay;
+            if self.orbit_velocity.magnitude() < 0.001 {
+                self.orbit_velocity = vec2(0.0, 0.0);
+            }
+            if self.pan_velocity.magnitude() < 0.001 {
+                self.pan_velocity = vec2(0.0, 0.0);
+            }
+        } else {
+            self.orbit_velocity = vec2(0.0, 0.0);
+            self.pan_velocity = vec2(0.0, 0.0);
+        }
+
+        if orbit.magnitude2() > 0.0 {
+            self.orbit(camera, orbit)?;
+            change = true;
+        }
+        if pan.magnitude2() > 0.0 {
+            self.pan(camera, pan)?;
+            change = true;
         }
-        self.control.handle_events(camera, events)
+        Ok(change)
+    }
+
+    fn orbit(&mut self, camera: &mut Camera, delta: Vec2) -> ThreeDResult<()> {
+        let speed = std::f32::consts::PI / 600.0;
+        let up = camera.up().normalize();
+        let offset = camera.position() - self.target;
+        let distance = offset.magnitude();
+        let direction = offset / distance;
+        let mut horizontal = direction - up * direction.dot(up);
+        if horizontal.magnitude2() < 0.000001 {
+            horizontal = up.cross(camera.right_direction());
+        }
+        let horizontal =
+            Mat3::from_axis_angle(up, radians(-delta.x * speed)) * horizontal.normalize();
+
+        let epsilon = 0.001;
+        let min_polar = self.min_polar_angle.0.max(epsilon);
+        let max_polar = self.max_polar_angle.0.min(std::f32::consts::PI - epsilon);
+        let polar = (direction.dot(up).clamp(-1.0, 1.0).acos() - delta.y * speed)
+            .max(min_polar)
+            .min(max_polar);
+
+        let new_direction = up * polar.cos() + horizontal * polar.sin();
+        camera.set_view(self.target + new_direction * distance, self.target, up)
+    }
+
+    fn pan(&mut self, camera: &mut Camera, delta: Vec2) -> ThreeDResult<()> {
+        let distance = self.target.distance(*camera.position());
+        let height = match camera.projection_type() {
+            ProjectionType::Perspective { field_of_view_y } => {
+                2.0 * distance * (0.5 * field_of_view_y.0).tan()
+            }
+            ProjectionType::Orthographic { height } => *height,
+        };
+        // The size of a logical pixel in world units at the distance of the target, assuming a device pixel ratio of one
+        let pixel_size = height / camera.viewport().height.max(1) as f32;
+        let right = camera.right_direction().normalize();
+        let up = right.cross(camera.view_direction()).normalize();
+        let change = (-right * delta.x + up * delta.y) * pixel_size;
+        self.target += change;
+        camera.translate(&change)
+    }
+
+    fn zoom(&mut self, camera: &mut Camera, point: Vec3, delta: f32) -> ThreeDResult<()> {
+        if self.min_distance <= 0.0 {
+            Err(CoreError::NegativeDistance)?;
+        }
+        if self.max_distance < self.min_distance {
+            Err(CoreError::MinimumLargerThanMaximum)?;
+        }
+        let position = *camera.position();
+        let distance = self.target.distance(position);
+        let new_distance = (distance - 0.1 * delta)
+            .max(self.min_distance)
+            .min(self.max_distance);
+        // Scale the view around the point such that the point stays at the same place on the screen
+        let factor = new_distance / distance;
+        let new_position = point + (position - point) * factor;
+        self.target = point + (self.target - point) * factor;
+        let camera_target = point + (camera.target() - point) * factor;
+        let up = *camera.up();
+        camera.set_view(new_position, camera_target, up)?;
+        if let ProjectionType::Orthographic { height } = camera.projection_type() {
+            let height = height * factor;
+            let z_near = camera.z_near();
+            let z_far = camera.z_far();
+            camera.set_orthographic_projection(height, z_near, z_far)?;
+        }
+        Ok(())
+    }
+}
+
+struct Picking<'a> {
+    context: &'a Context,
+    device_pixel_ratio: f64,
+    viewport: Viewport,
+    geometries: &'a [&'a dyn Geometry],
+}
+
+impl Picking<'_> {
+    fn pick(&self, camera: &Camera, position: (f64, f64)) -> ThreeDResult<Option<Vec3>> {
+        let pixel = (
+            (self.device_pixel_ratio * position.0) as f32,
+            (self.viewport.height as f64 - self.device_pixel_ratio * position.1) as f32,
+        );
+        pick(self.context, camera, pixel, self.geometries)
     }
 }
@@ -0,0 +1,96 @@
+use crate::core::*;
+use crate::window::*;
+
+///
+/// A control for a [Camera2D], analogous to [OrbitControl] for 3D.
+/// Drag with the left or middle mouse button to pan, drag with the right mouse button to rotate around the center of the viewport and scroll to zoom towards the cursor.
+///
+pub struct PanZoomControl {
+    /// The minimum zoom, ie. number of pixels per world unit.
+    pub min_zoom: f32,
+    /// The maximum zoom, ie. number of pixels per world unit.
+    pub max_zoom: f32,
+    /// Whether dragging with the right mouse button rotates the view.
+    pub rotation_enabled: bool,
+}
+
+impl PanZoomControl {
+    /// Creates a new pan and zoom control with the given minimum and maximum zoom, ie. number of pixels per world unit.
+    pub fn new(min_zoom: f32, max_zoom: f32) -> Self {
+        Self {
+            min_zoom,
+            max_zoom,
+            rotation_enabled: true,
+        }
+    }
+
+    /// Handles the events. Must be called each frame.
+    pub fn handle_events(
+        &mut self,
+        camera: &mut Camera2D,
+        frame_input: &mut FrameInput,
+    ) -> ThreeDResult<bool> {
+        if self.min_zoom <= 0.0 {
+            Err(CoreError::NegativeDistance)?;
+        }
+        if self.max_zoom < self.min_zoom {
+            Err(CoreError::MinimumLargerThanMaximum)?;
+        }
+        let device_pixel_ratio = frame_input.device_pixel_ratio as f32;
+        let viewport = camera.viewport();
+        // Converts a position in logical pixels relative to the window to physical pixels relative to the top left corner of the viewport
+        let window_height = frame_input.viewport.height as f32;
+        let to_pixel = |position: (f64, f64)| {
+            vec2(
+                device_pixel_ratio * position.0 as f32 - viewport.x as f32,
+                device_pixel_ratio * position.1 as f32
+                    - (window_height - (viewport.y + viewport.height as i32) as f32),
+            )
+        };
+        let mut change = false;
+        for event in frame_input.events.iter_mut() {
+            match event {
+                Event::MouseMotion {
+                    delta,
+                    position,
+                    button: Some(button),
+                    handled,
+                    ..
+                } if !*handled => match button {
+                    MouseButton::Left | MouseButton::Middle => {
+                        camera.pan(vec2(delta.0 as f32, delta.1 as f32) * device_pixel_ratio)?;
+                        *handled = true;
+                        change = true;
+                    }
+                    MouseButton::Right if self.rotation_enabled => {
+                        let center =
+                            vec2(0.5 * viewport.width as f32, 0.5 * viewport.height as f32);
+                        let current = to_pixel(*position) - center;
+                        let previous =
+                            current - vec2(delta.0 as f32, delta.1 as f32) * device_pixel_ratio;
+                        let angle = previous.perp_dot(current).atan2(previous.dot(current));
+                        // The y-axis points down, so a clockwise motion on the screen is a positive angle
+                        camera.rotate_around(center, radians(-angle))?;
+                        *handled = true;
+                        change = true;
+                    }
+                    _ => {}
+                },
+                Event::MouseWheel {
+                    delta,
+                    position,
+                    handled,
+                    ..
+                } if !*handled => {
+                    let zoom = (camera.zoom() * (0.002 * delta.1 as f32).exp())
+                        .clamp(self.min_zoom, self.max_zoom);
+                    camera.zoom_towards(to_pixel(*position), zoom / camera.zoom())?;
+                    *handled = true;
+                    change = true;
+                }
+                _ => {}
+            }
+        }
+        Ok(change)
+    }
+}
@@ -1,7 +1,7 @@
 use crate::core::*;
 use crate::window::*;
-use glutin::event::{Event, WindowEvent};
-use glutin::event_loop::{ControlFlow, EventLoop};
+use glutin::event::{DeviceEvent, Event, WindowEvent};
+use glutin::event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget};
 use glutin::window::WindowBuilder;
 use glutin::ContextBuilder;
 use glutin::*;
@@ -19,6 +19,8 @@ pub enum WindowError {
     ContextCreation(#[from] glutin::ContextError),
     #[error("the number of MSAA samples must be a power of two")]
     InvalidNumberOfMSAASamples,
+    #[error("invalid window icon")]
+    InvalidIcon(#[from] window::BadIcon),
 }
 
 ///
@@ -28,21 +30,18 @@ pub struct Window {
     windowed_context: ContextWrapper<PossiblyCurrent, window::Window>,
     event_loop: EventLoop<()>,
     gl: crate::Context,
+    id: WindowId,
+    cursor_grab: bool,
 }
 
 impl Window {
     ///
     /// Constructs a new window with the given settings.
     ///
-    pub fn new(mut settings: WindowSettings) -> ThreeDResult<Window> {
+    pub fn new(settings: WindowSettings) -> ThreeDResult<Window> {
         let event_loop = EventLoop::new();
-        let mut wc = Self::new_windowed_context(&settings, &event_loop);
-        if wc.is_err() {
-            settings.multisamples = 0;
-            wc = Self::new_windowed_context(&settings, &event_loop);
-        }
-
-        let windowed_context = unsafe { wc?.make_current().unwrap() };
+        let (wc, cursor_grab) = Self::new_windowed_context(settings, &event_loop, None)?;
+        let windowed_context = unsafe { wc.make_current().unwrap() };
 
         let context = unsafe {
             crate::context::Context::from_loader_function(|s| {
@@ -53,18 +52,62 @@ impl Window {
             windowed_context,
             event_loop,
             gl: crate::core::Context::from_gl_context(std::rc::Rc::new(context))?,
+            id: WindowId::new(),
+            cursor_grab,
         })
     }
 
+    ///
+    /// Opens a new window with a context that shares resources with the context of the given main window.
+    ///
+    fn open_window(
+        id: WindowId,
+        settings: WindowSettings,
+        event_loop: &EventLoopWindowTarget<()>,
+        main_window: &WindowState,
+        context: &crate::Context,
+    ) -> ThreeDResult<WindowState> {
+        let (wc, cursor_grab) = Self::new_windowed_context(
+            settings,
+            event_loop,
+            Some(main_window.windowed_context().context()),
+        )?;
+        let windowed_context = unsafe { wc.make_current().map_err(|(_, e)| e)? };
+        let vao = context.init_shared_gl_context()?;
+        Ok(WindowState::new(id, windowed_context, vao, cursor_grab))
+    }
+
+    ///
+    /// Creates the window and context and applies the cursor settings.
+    /// Also returns whether the cursor is grabbed, since grabbing the cursor is not supported on all platforms.
+    ///
     fn new_windowed_context(
+        mut settings: WindowSettings,
+        event_loop: &EventLoopWindowTarget<()>,
+        shared_context: Option<&glutin::Context<PossiblyCurrent>>,
+    ) -> ThreeDResult<(WindowedContext<NotCurrent>, bool)> {
+        let mut wc =
+            Self::new_windowed_context_with_settings(&settings, event_loop, shared_context);
+        if wc.is_err() {
+            settings.multisamples = 0;
+            wc = Self::new_windowed_context_with_settings(&settings, event_loop, shared_context);
+        }
+        let wc = wc?;
+        wc.window().set_cursor_visible(settings.cursor_visible);
+        let cursor_grab = settings.cursor_grab && wc.window().set_cursor_grab(true).is_ok();
+        Ok((wc, cursor_grab))
+    }
+
+    fn new_windowed_context_with_settings(
         settings: &WindowSettings,
-        event_loop: &EventLoop<()>,
+        event_loop: &EventLoopWindowTarget<()>,
+        shared_context: Option<&glutin::Context<PossiblyCurrent>>,
     ) -> ThreeDResult<WindowedContext<NotCurrent>> {
         if settings.multisamples > 0 && !settings.multisamples.is_power_of_two() {
             Err(WindowError::InvalidNumberOfMSAASamples)?;
         }
         let borderless = settings.borderless;
-        let window_builder = if let Some((width, height)) = settings.max_size {
+        let mut window_builder = if let Some((width, height)) = settings.max_size {
             WindowBuilder::new()
                 .with_title(&settings.title)
                 .with_min_inner_size(dpi::LogicalSize::new(
@@ -84,92 +127,123 @@ impl Window {
                 .with_decorations(!borderless)
                 .with_maximized(true)
         };
+        window_builder = window_builder
+            .with_resizable(settings.resizable)
+            .with_transparent(settings.transparent)
+            .with_fullscreen(settings.fullscreen.and_then(|fullscreen| {
+                translate_fullscreen(
+                    fullscreen,
+                    event_loop.available_monitors(),
+                    event_loop.primary_monitor(),
+                )
+            }));
+        if let Some((x, y)) = settings.position {
+            window_builder = window_builder.with_position(dpi::LogicalPosition::new(x, y));
+        }
+        if let Some(ref icon) = settings.icon {
+            window_builder = window_builder.with_window_icon(Some(window::Icon::from_rgba(
+                icon.pixels.iter().flatten().copied().collect(),
+                icon.width,
+                icon.height,
+            )?));
+        }
 
-        Ok(ContextBuilder::new()
+        let context_builder = ContextBuilder::new()
             .with_multisampling(settings.multisamples as u16)
-            .with_vsync(settings.vsync)
-            .build_windowed(window_builder, event_loop)?)
+            .with_stencil_buffer(8)
+            .with_vsync(settings.vsync);
+        Ok(if let Some(shared_context) = shared_context {
+            context_builder
+                .with_shared_lists(shared_context)
+                .build_windowed(window_builder, event_loop)?
+        } else {
+            context_builder.build_windowed(window_builder, event_loop)?
+        })
     }
 
     ///
     /// Start the main render loop which calls the `callback` closure each frame.
+    /// If more windows are opened using [FrameOutput::open_windows], the `callback` closure is also called each frame for each of those windows.
     ///
     pub fn render_loop<F: 'static + FnMut(FrameInput) -> FrameOutput>(
         self,
         mut callback: F,
     ) -> ThreeDResult<()> {
-        let windowed_context = self.windowed_context;
-        let mut last_time = std::time::Instant::now();
-        let mut accumulated_time = 0.0;
-        let mut events = Vec::new();
-        let mut cursor_pos = None;
-        let mut modifiers = Modifiers::default();
-        let mut first_frame = true;
-        let mut mouse_pressed = None;
         let context = self.gl.clone();
-        self.event_loop.run(move |event, _, control_flow| {
-            match event {
+        let mut windows = vec![WindowState::new(
+            self.id,
+            self.windowed_context,
+            context.vertex_array(),
+            self.cursor_grab,
+        )];
+        let mut clipboard = arboard::Clipboard::new().ok();
+        self.event_loop
+            .run(move |event, event_loop, control_flow| match event {
                 Event::LoopDestroyed => {
                     return;
                 }
                 Event::MainEventsCleared => {
-                    windowed_context.window().request_redraw();
+                    for window in windows.iter() {
+                        window.window().request_redraw();
+                    }
                 }
-                Event::RedrawRequested(_) => {
-                    let now = std::time::Instant::now();
-                    let duration = now.duration_since(last_time);
-                    last_time = now;
-                    let elapsed_time =
-                        duration.as_secs() as f64 * 1000.0 + duration.subsec_nanos() as f64 * 1e-6;
-                    accumulated_time += elapsed_time;
-
-                    let (physical_width, physical_height): (u32, u32) =
-                        windowed_context.window().inner_size().into();
-                    let device_pixel_ratio = windowed_context.window().scale_factor();
-                    let (width, height): (u32, u32) = windowed_context
-                        .window()
-                        .inner_size()
-                        .to_logical::<f64>(device_pixel_ratio)
-                        .into();
-                    let frame_input = FrameInput {
-                        events: events.clone(),
-                        elapsed_time,
-                        accumulated_time,
-                        viewport: Viewport::new_at_origo(physical_width, physical_height),
-                        window_width: width,
-                        window_height: height,
-                        device_pixel_ratio: device_pixel_ratio,
-                        first_frame: first_frame,
-                        context: context.clone(),
+                Event::RedrawRequested(window_id) => {
+                    let index = if let Some(index) =
+                        windows.iter().position(|w| w.window().id() == window_id)
+                    {
+                        index
+                    } else {
+                        return;
                     };
-                    first_frame = false;
-                    events.clear();
+                    let window = &mut windows[index];
+                    window.make_current(&context);
+                    let frame_input = window.frame_input(&context);
                     let mut frame_output = callback(frame_input);
                     if let Ok(v) = std::env::var("THREE_D_SCREENSHOT") {
                         frame_output.screenshot = Some(v.into());
                     }
                     if let Ok(v) = std::env::var("THREE_D_EXIT") {
-                        if v.parse::<f64>().unwrap() < accumulated_time {
+                        if v.parse::<f64>().unwrap() < window.accumulated_time {
                             frame_output.exit = true;
                         }
                     }
 
                     if frame_output.exit {
-                        *control_flow = ControlFlow::Exit;
+                        if index == 0 {
+                            *control_flow = ControlFlow::Exit;
+                        }
                     } else {
                         if frame_output.swap_buffers {
-                            windowed_context.swap_buffers().unwrap();
+                            window.windowed_context().swap_buffers().unwrap();
                         }
-                        if frame_output.wait_next_event {
-                            *control_flow = ControlFlow::Wait;
-                        } else {
-                            *control_flow = ControlFlow::Poll;
-                            windowed_context.window().request_redraw();
+                        window.wait_next_event = frame_output.wait_next_event;
+                        if !frame_output.wait_next_event {
+                            window.window().request_redraw();
+                        }
+                        if let Some(fullscreen) = frame_output.fullscreen {
+                            let w = window.window();
+                            w.set_fullscreen(fullscreen.and_then(|fullscreen| {
+                                translate_fullscreen(
+                                    fullscreen,
+                                    w.available_monitors(),
+                                    w.current_monitor(),
+                                )
+                            }));
+                        }
+                        if let Some(cursor_visible) = frame_output.cursor_visible {
+                            window.window().set_cursor_visible(cursor_visible);
+                        }
+                        if let Some(cursor_grab) = frame_output.cursor_grab {
+                            // Grabbing the cursor is not supported on all platforms, in which case the cursor is not grabbed
+                            window.cursor_grab =
+                                window.window().set_cursor_grab(cursor_grab).is_ok() && cursor_grab;
                         }
                     }
 
                     #[cfg(feature = "image-io")]
                     if let Some(ref path) = frame_output.screenshot {
+                        let (physical_width, physical_height): (u32, u32) =
+                            window.window().inner_size().into();
                         let pixels =
                             RenderTarget::screen(&context, physical_width, physical_height)
                                 .read_color()
@@ -177,150 +251,57 @@ impl Window {
                         crate::Saver::save_pixels(path, &pixels, physical_width, physical_height)
                             .unwrap();
                     }
-                }
-                Event::WindowEvent { ref event, .. } => match event {
-                    WindowEvent::Resized(physical_size) => {
-                        windowed_context.resize(*physical_size);
-                    }
-                    WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
-                    WindowEvent::KeyboardInput { input, .. } => {
-                        if let Some(keycode) = input.virtual_keycode {
-                            use event::VirtualKeyCode;
-                            let state = input.state == event::ElementState::Pressed;
-                            if let Some(kind) = translate_virtual_key_code(keycode) {
-                                events.push(if state {
-                                    crate::Event::KeyPress {
-                                        kind,
-                                        modifiers,
-                                        handled: false,
-                                    }
-                                } else {
-                                    crate::Event::KeyRelease {
-                                        kind,
-                                        modifiers,
-                                        handled: false,
-                                    }
-                                });
-                            } else {
-                                if keycode == VirtualKeyCode::LControl
-                                    || keycode == VirtualKeyCode::RControl
-                                {
-                                    modifiers.ctrl = state;
-                                    if !cfg!(target_os = "macos") {
-                                        modifiers.command = state;
-                                    }
-                                    events.push(crate::Event::ModifiersChange { modifiers });
-                                } else if keycode == VirtualKeyCode::LAlt
-                                    || keycode == VirtualKeyCode::RAlt
-                                {
-                                    modifiers.alt = state;
-                                    events.push(crate::Event::ModifiersChange { modifiers });
-                                } else if keycode == VirtualKeyCode::LShift
-                                    || keycode == VirtualKeyCode::RShift
-                                {
-                                    modifiers.shift = state;
-                                    events.push(crate::Event::ModifiersChange { modifiers });
-                                } else if keycode == VirtualKeyCode::LWin
-                                    || keycode == VirtualKeyCode::RWin
-                                {
-                                    if cfg!(target_os = "macos") {
-                                        modifiers.command = state;
-                                        events.push(crate::Event::ModifiersChange { modifiers });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    WindowEvent::MouseWheel { delta, .. } => {
-                        if let Some(position) = cursor_pos {
-                            match delta {
-                                glutin::event::MouseScrollDelta::LineDelta(x, y) => {
-                                    let line_height = 24.0; // TODO
-                                    events.push(crate::Event::MouseWheel {
-                                        delta: (
-                                            (*x * line_height) as f64,
-                                            (*y * line_height) as f64,
-                                        ),
-                                        position,
-                                        modifiers,
-                                        handled: false,
-                                    });
-                                }
-                                glutin::event::MouseScrollDelta::PixelDelta(delta) => {
-                                    let d =
-                                        delta.to_logical(windowed_context.window().scale_factor());
-                                    events.push(crate::Event::MouseWheel {
-                                        delta: (d.x, d.y),
-                                        position,
-                                        modifiers,
-                                        handled: false,
-                                    });
-                                }
-                            }
-                        }
+
+                    if frame_output.exit && index > 0 {
+                        windows.remove(index).close(&context);
                     }
-                    WindowEvent::MouseInput { state, button, .. } => {
-                        if let Some(position) = cursor_pos {
-                            let button = match button {
-                                event::MouseButton::Left => Some(crate::MouseButton::Left),
-                                event::MouseButton::Middle => Some(crate::MouseButton::Middle),
-                                event::MouseButton::Right => Some(crate::MouseButton::Right),
-                                _ => None,
-                            };
-                            if let Some(b) = button {
-                                events.push(if *state == event::ElementState::Pressed {
-                                    mouse_pressed = Some(b);
-                                    crate::Event::MousePress {
-                                        button: b,
-                                        position,
-                                        modifiers,
-                                        handled: false,
-                                    }
-                                } else {
-                                    mouse_pressed = None;
-                                    crate::Event::MouseRelease {
-                                        button: b,
-                                        position,
-                                        modifiers,
-                                        handled: false,
-                                    }
-                                });
-                            }
+
+                    for (id, settings) in frame_output.open_windows.drain(..) {
+                        // The render loop cannot return an error, so a window that fails to open is skipped
+                        match Self::open_window(id, settings, event_loop, &windows[0], &context) {
+                            Ok(window) => windows.push(window),
+                            Err(e) => log::error!("Failed to open window {:?}: {}", id, e),
                         }
                     }
-                    WindowEvent::CursorMoved { position, .. } => {
-                        let p = position.to_logical(windowed_context.window().scale_factor());
-                        let delta = if let Some(last_pos) = cursor_pos {
-                            (p.x - last_pos.0, p.y - last_pos.1)
+
+                    if *control_flow != ControlFlow::Exit {
+                        *control_flow = if windows.iter().all(|w| w.wait_next_event) {
+                            ControlFlow::Wait
                         } else {
-                            (0.0, 0.0)
+                            ControlFlow::Poll
                         };
-                        events.push(crate::Event::MouseMotion {
-                            button: mouse_pressed,
-                            delta,
-                            position: (p.x, p.y),
-                            modifiers,
-                            handled: false,
-                        });
-                        cursor_pos = Some((p.x, p.y));
-                    }
-                    WindowEvent::ReceivedCharacter(ch) => {
-                        if is_printable_char(*ch) && !modifiers.ctrl && !modifiers.command {
-                            events.push(crate::Event::Text(ch.to_string()));
-                        }
                     }
-                    WindowEvent::CursorEntered { .. } => {
-                        events.push(crate::Event::MouseEnter);
+                }
+                Event::DeviceEvent {
+                    event: DeviceEvent::MouseMotion { delta },
+                    ..
+                } => {
+                    for window in windows.iter_mut() {
+                        window.handle_raw_mouse_motion(delta);
                     }
-                    WindowEvent::CursorLeft { .. } => {
-                        mouse_pressed = None;
-                        events.push(crate::Event::MouseLeave);
+                }
+                Event::WindowEvent {
+                    ref event,
+                    window_id,
+                } => {
+                    if let Some(index) = windows.iter().position(|w| w.window().id() == window_id) {
+                        match event {
+                            WindowEvent::Resized(physical_size) => {
+                                windows[index].windowed_context().resize(*physical_size);
+                            }
+                            WindowEvent::CloseRequested => {
+                                if index == 0 {
+                                    *control_flow = ControlFlow::Exit;
+                                } else {
+                                    windows.remove(index).close(&context);
+                                }
+                            }
+                            _ => windows[index].handle_window_event(event, &mut clipboard),
+                        }
                     }
-                    _ => (),
-                },
+                }
                 _ => (),
-            }
-        });
+            });
     }
 
     ///
@@ -349,6 +330,328 @@ impl Window {
     pub fn gl(&self) -> ThreeDResult<crate::Context> {
         Ok(self.gl.clone())
     }
+
+    ///
+    /// Returns the id of this window which is used to identify the [FrameInput] for this window.
+    ///
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+}
+
+struct WindowState {
+    id: WindowId,
+    windowed_context: Option<ContextWrapper<PossiblyCurrent, window::Window>>,
+    vao: crate::context::VertexArray,
+    last_time: std::time::Instant,
+    accumulated_time: f64,
+    events: Vec<crate::Event>,
+    cursor_pos: Option<(f64, f64)>,
+    modifiers: Modifiers,
+    first_frame: bool,
+    mouse_pressed: Option<crate::MouseButton>,
+    wait_next_event: bool,
+    cursor_grab: bool,
+    focused: bool,
+}
+
+impl WindowState {
+    fn new(
+        id: WindowId,
+        windowed_context: ContextWrapper<PossiblyCurrent, window::Window>,
+        vao: crate::context::VertexArray,
+        cursor_grab: bool,
+    ) -> Self {
+        Self {
+            id,
+            windowed_context: Some(windowed_context),
+            vao,
+            last_time: std::time::Instant::now(),
+            accumulated_time: 0.0,
+            events: Vec::new(),
+            cursor_pos: None,
+            modifiers: Modifiers::default(),
+            first_frame: true,
+            mouse_pressed: None,
+            wait_next_event: false,
+            cursor_grab,
+            focused: true,
+        }
+    }
+
+    fn windowed_context(&self) -> &ContextWrapper<PossiblyCurrent, window::Window> {
+        self.windowed_context.as_ref().unwrap()
+    }
+
+    fn window(&self) -> &window::Window {
+        self.windowed_context().window()
+    }
+
+    fn make_current(&mut self, context: &crate::Context) {
+        let windowed_context = self.windowed_context.take().unwrap();
+        self.windowed_context = Some(if windowed_context.is_current() {
+            windowed_context
+        } else {
+            unsafe { windowed_context.make_current().map_err(|(_, e)| e).unwrap() }
+        });
+        context.set_vertex_array(self.vao);
+    }
+
+    fn close(mut self, context: &crate::Context) {
+        self.make_current(context);
+        unsafe {
+            context.delete_vertex_array(self.vao);
+        }
+    }
+
+    fn frame_input(&mut self, context: &crate::Context) -> FrameInput {
+        let now = std::time::Instant::now();
+        let duration = now.duration_since(self.last_time);
+        self.last_time = now;
+        let elapsed_time =
+            duration.as_secs() as f64 * 1000.0 + duration.subsec_nanos() as f64 * 1e-6;
+        self.accumulated_time += elapsed_time;
+
+        let (physical_width, physical_height): (u32, u32) = self.window().inner_size().into();
+        let device_pixel_ratio = self.window().scale_factor();
+        let (width, height): (u32, u32) = self
+            .window()
+            .inner_size()
+            .to_logical::<f64>(device_pixel_ratio)
+            .into();
+        let frame_input = FrameInput {
+            events: self.events.clone(),
+            elapsed_time,
+            accumulated_time: self.accumulated_time,
+            viewport: Viewport::new_at_origo(physical_width, physical_height),
+            window_width: width,
+            window_height: height,
+            device_pixel_ratio: device_pixel_ratio,
+            first_frame: self.first_frame,
+            context: context.clone(),
+            window_id: self.id,
+        };
+        self.first_frame = false;
+        self.events.clear();
+        frame_input
+    }
+
+    fn handle_raw_mouse_motion(&mut self, delta: (f64, f64)) {
+        if self.cursor_grab && self.focused {
+            self.events.push(crate::Event::MouseMotion {
+                button: self.mouse_pressed,
+                delta,
+                position: self.cursor_pos.unwrap_or((0.0, 0.0)),
+                modifiers: self.modifiers,
+                handled: false,
+            });
+        }
+    }
+
+    fn handle_window_event(
+        &mut self,
+        event: &WindowEvent,
+        clipboard: &mut Option<arboard::Clipboard>,
+    ) {
+        match event {
+            WindowEvent::KeyboardInput { input, .. } => {
+                if let Some(keycode) = input.virtual_keycode {
+                    use event::VirtualKeyCode;
+                    let state = input.state == event::ElementState::Pressed;
+                    if let Some(kind) = translate_virtual_key_code(keycode) {
+                        if state && kind == Key::V && self.modifiers.command {
+                            if let Some(text) = clipboard.as_mut().and_then(|c| c.get_text().ok()) {
+                                self.events.push(crate::Event::Clipboard(text));
+                            }
+                        }
+                        self.events.push(if state {
+                            crate::Event::KeyPress {
+                                kind,
+                                modifiers: self.modifiers,
+                                handled: false,
+                            }
+                        } else {
+                            crate::Event::KeyRelease {
+                                kind,
+                                modifiers: self.modifiers,
+                                handled: false,
+                            }
+                        });
+                    } else {
+                        if keycode == VirtualKeyCode::LControl
+                            || keycode == VirtualKeyCode::RControl
+                        {
+                            self.modifiers.ctrl = state;
+                            if !cfg!(target_os = "macos") {
+                                self.modifiers.command = state;
+                            }
+                            self.events.push(crate::Event::ModifiersChange {
+                                modifiers: self.modifiers,
+                            });
+                        } else if keycode == VirtualKeyCode::LAlt || keycode == VirtualKeyCode::RAlt
+                        {
+                            self.modifiers.alt = state;
+                            self.events.push(crate::Event::ModifiersChange {
+                                modifiers: self.modifiers,
+                            });
+                        } else if keycode == VirtualKeyCode::LShift
+                            || keycode == VirtualKeyCode::RShift
+                        {
+                            self.modifiers.shift = state;
+                            self.events.push(crate::Event::ModifiersChange {
+                                modifiers: self.modifiers,
+                            });
+                        } else if keycode == VirtualKeyCode::LWin || keycode == VirtualKeyCode::RWin
+                        {
+                            if cfg!(target_os = "macos") {
+                                self.modifiers.command = state;
+                                self.events.push(crate::Event::ModifiersChange {
+                                    modifiers: self.modifiers,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                if let Some(position) = self.cursor_pos {
+                    match delta {
+                        glutin::event::MouseScrollDelta::LineDelta(x, y) => {
+                            let line_height = 24.0; // TODO
+                            self.events.push(crate::Event::MouseWheel {
+                                delta: ((*x * line_height) as f64, (*y * line_height) as f64),
+                                position,
+                                modifiers: self.modifiers,
+                                handled: false,
+                            });
+                        }
+                        glutin::event::MouseScrollDelta::PixelDelta(delta) => {
+                            let d = delta.to_logical(self.window().scale_factor());
+                            self.events.push(crate::Event::MouseWheel {
+                                delta: (d.x, d.y),
+                                position,
+                                modifiers: self.modifiers,
+                                handled: false,
+                            });
+                        }
+                    }
+                }
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                if let Some(position) = self.cursor_pos {
+                    let button = match button {
+                        event::MouseButton::Left => Some(crate::MouseButton::Left),
+                        event::MouseButton::Middle => Some(crate::MouseButton::Middle),
+                        event::MouseButton::Right => Some(crate::MouseButton::Right),
+                        _ => None,
+                    };
+                    if let Some(b) = button {
+                        self.events.push(if *state == event::ElementState::Pressed {
+                            self.mouse_pressed = Some(b);
+                            crate::Event::MousePress {
+                                button: b,
+                                position,
+                                modifiers: self.modifiers,
+                                handled: false,
+                            }
+                        } else {
+                            self.mouse_pressed = None;
+                            crate::Event::MouseRelease {
+                                button: b,
+                                position,
+                                modifiers: self.modifiers,
+                                handled: false,
+                            }
+                        });
+                    }
+                }
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let p = position.to_logical(self.window().scale_factor());
+                let delta = if let Some(last_pos) = self.cursor_pos {
+                    (p.x - last_pos.0, p.y - last_pos.1)
+                } else {
+                    (0.0, 0.0)
+                };
+                // When the cursor is grabbed, the mouse motion events are created from the raw mouse motion instead
+                if !self.cursor_grab {
+                    self.events.push(crate::Event::MouseMotion {
+                        button: self.mouse_pressed,
+                        delta,
+                        position: (p.x, p.y),
+                        modifiers: self.modifiers,
+                        handled: false,
+                    });
+                }
+                self.cursor_pos = Some((p.x, p.y));
+            }
+            WindowEvent::Focused(focused) => {
+                self.focused = *focused;
+            }
+            WindowEvent::ReceivedCharacter(ch) => {
+                if is_printable_char(*ch) && !self.modifiers.ctrl && !self.modifiers.command {
+                    self.events.push(crate::Event::Text(ch.to_string()));
+                }
+            }
+            WindowEvent::CursorEntered { .. } => {
+                self.events.push(crate::Event::MouseEnter);
+            }
+            WindowEvent::CursorLeft { .. } => {
+                self.mouse_pressed = None;
+                self.events.push(crate::Event::MouseLeave);
+            }
+            WindowEvent::HoveredFile(path) => {
+                self.events.push(crate::Event::HoveredFile {
+                    path: Some(path.clone()),
+                });
+            }
+            WindowEvent::HoveredFileCancelled => {
+                self.events.push(crate::Event::HoveredFileCancelled);
+            }
+            WindowEvent::DroppedFile(path) => match std::fs::read(path) {
+                Ok(bytes) => self.events.push(crate::Event::DroppedFile {
+                    path: path.clone(),
+                    bytes,
+                }),
+                Err(e) => log::error!("Failed to read dropped file {:?}: {}", path, e),
+            },
+            _ => (),
+        }
+    }
+}
+
+fn translate_fullscreen(
+    fullscreen: crate::Fullscreen,
+    mut monitors: impl Iterator<Item = monitor::MonitorHandle>,
+    current_monitor: Option<monitor::MonitorHandle>,
+) -> Option<window::Fullscreen> {
+    let monitor = |index: Option<usize>| {
+        if let Some(index) = index {
+            monitors.nth(index)
+        } else {
+            current_monitor
+        }
+    };
+    match fullscreen {
+        crate::Fullscreen::Borderless { monitor: index } => {
+            Some(window::Fullscreen::Borderless(monitor(index)))
+        }
+        crate::Fullscreen::Exclusive {
+            monitor: index,
+            size,
+        } => monitor(index)?
+            .video_modes()
+            .min_by_key(|mode| {
+                let s = mode.size();
+                let size_difference = if let Some((width, height)) = size {
+                    (s.width as i64 - width as i64).abs() + (s.height as i64 - height as i64).abs()
+                } else {
+                    -(s.width as i64 * s.height as i64)
+                };
+                (size_difference, -(mode.refresh_rate() as i64))
+            })
+            .map(window::Fullscreen::Exclusive),
+    }
 }
 
 fn is_printable_char(chr: char) -> bool {
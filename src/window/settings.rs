@@ -1,3 +1,41 @@
+///
+/// Fullscreen mode of a window, see [WindowSettings::fullscreen].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fullscreen {
+    /// Borderless fullscreen, ie. a window without decorations covering the entire monitor.
+    /// Does not change the video mode of the monitor and is therefore fast to enter and exit.
+    Borderless {
+        /// The index of the monitor to make the window fullscreen on.
+        /// If None is specified, the monitor which the window is currently on is used (or the primary monitor when opening the window).
+        monitor: Option<usize>,
+    },
+    /// Exclusive fullscreen, ie. the window takes exclusive control of the monitor and can change the video mode.
+    ///
+    /// On web, this is the same as [Fullscreen::Borderless].
+    Exclusive {
+        /// The index of the monitor to make the window fullscreen on.
+        /// If None is specified, the monitor which the window is currently on is used (or the primary monitor when opening the window).
+        monitor: Option<usize>,
+        /// The size (width, height) of the video mode in physical pixels. The supported video mode closest to this size is used.
+        /// If None is specified, the video mode with the largest size is used.
+        size: Option<(u32, u32)>,
+    },
+}
+
+///
+/// An icon of a window, see [WindowSettings::icon].
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowIcon {
+    /// The RGBA pixels of the icon, row by row starting from the top left corner.
+    pub pixels: Vec<[u8; 4]>,
+    /// The width of the icon in pixels.
+    pub width: u32,
+    /// The height of the icon in pixels.
+    pub height: u32,
+}
+
 ///
 /// Window settings.
 ///
@@ -24,6 +62,39 @@ pub struct WindowSettings {
     ///
     /// No effect on web.
     pub borderless: bool,
+    /// Opens the window in the given fullscreen mode if specified. Use [FrameOutput::fullscreen](crate::FrameOutput::fullscreen) to change the fullscreen mode at runtime.
+    ///
+    /// On web, the browser only allows entering fullscreen in response to user input,
+    /// so fullscreen is entered on the first mouse click or key press.
+    pub fullscreen: Option<Fullscreen>,
+    /// The icon of the window.
+    ///
+    /// No effect on web and macOS.
+    pub icon: Option<WindowIcon>,
+    /// Whether or not the window can be resized by the user.
+    ///
+    /// No effect on web.
+    pub resizable: bool,
+    /// The initial position (x, y) of the top left corner of the window in logical pixels relative to the top left corner of the desktop.
+    /// If None is specified, the position is chosen by the operating system.
+    ///
+    /// No effect on web.
+    pub position: Option<(i32, i32)>,
+    /// Whether or not the background of the window is transparent, meaning that the alpha value of the rendered pixels is used to blend with whatever is behind the window.
+    ///
+    /// No effect on web.
+    pub transparent: bool,
+    /// Whether or not the cursor is visible when it is inside the window.
+    /// Use [FrameOutput::cursor_visible](crate::FrameOutput::cursor_visible) to change the cursor visibility at runtime.
+    pub cursor_visible: bool,
+    /// Whether or not the cursor is grabbed, ie. it is confined to the window and cannot leave it.
+    /// When the cursor is grabbed, [Event::MouseMotion](crate::Event::MouseMotion) events contain raw deltas from the mouse
+    /// which is useful for mouse-look, see for example [FirstPersonControl::set_mouse_look](crate::FirstPersonControl::set_mouse_look).
+    /// Use [FrameOutput::cursor_grab](crate::FrameOutput::cursor_grab) to grab and release the cursor at runtime.
+    ///
+    /// On web, the browser only allows grabbing the cursor (pointer lock) in response to user input,
+    /// so the cursor is grabbed on the first mouse click or key press.
+    pub cursor_grab: bool,
 }
 impl Default for WindowSettings {
     fn default() -> Self {
@@ -34,6 +105,13 @@ impl Default for WindowSettings {
             vsync: true,
             multisamples: 4,
             borderless: false,
+            fullscreen: None,
+            icon: None,
+            resizable: true,
+            position: None,
+            transparent: false,
+            cursor_visible: true,
+            cursor_grab: false,
         }
     }
 }